@@ -2,17 +2,27 @@
 // It only perform "byte-transfer" one at a time. But since, SPI_CS is 
 // not managed by this module, multiple bytes are able to be sent in one transmission
 // by just not disenabling the line.
-module SpiMaster #(
-    parameter CPOL = 0,
-    parameter CPHA = 0
-)
+//
+// The SPI mode (CPOL/CPHA), the bit order and the clock divider are inputs, so that
+// they can be changed at runtime. They must be stable while a transfer is ongoing (busy high).
+module SpiMaster
 (
     // rst -- active high reset
     input wire rst,
     // rclk -- Reference clock 
-    // SPI clock is derived from rclk (SPI_CLK = rclk / 2)
+    // SPI clock is derived from rclk (SPI_CLK = rclk / (2 * (divider + 1)))
     input wire rclk,
 
+    // Configuration (sampled continuously, only change it while idle)
+    // cpol - Clock polarity (idle level of spi_clk)
+    input wire cpol,
+    // cpha - Clock phase (0: sample on the first edge, 1: on the second edge)
+    input wire cpha,
+    // lsb_first - Bit order (0: MSB first, 1: LSB first)
+    input wire lsb_first,
+    // divider - Clock divider, each half-period of spi_clk lasts (divider + 1) rclk cycles
+    input wire [15:0] divider,
+
     // SPI interface
     output wire spi_clk, // SPI clock
     output wire spi_mosi, // SPI Master Out Slave In
@@ -20,11 +30,11 @@ module SpiMaster #(
     // spi_cs -- chip select is not managed by this module, to allow multi-byte transfer.
 
     // SoC interface
-    // start - High to trigger the transfer.
+    // start - Rising edge to trigger the transfer.
     //         At that point, tx_data must already be ready.
     //         Note: This signal is only sampled on the rising edge of rclk.
-    //               If start is asserted when busy, it will be ignored until the
-    //               current transfer is complete.
+    //               A rising edge while busy is ignored, and so is a start signal
+    //               still high at the end of a transfer (only one byte per edge).
     input wire start, 
     // tx_data - the byte to be sent
     //           This signal must be valid when start is asserted.
@@ -52,6 +62,10 @@ module SpiMaster #(
     reg [1:0] state = STATE_IDLE;
     // Bit counter (number of bits already transferred)
     reg [2:0] bit_cnt = 3'b0;
+    // Previous start signal (to detect its rising edge)
+    reg start_prev = 1'b1;
+    // Prescaler (rclk cycles already spent in the current half-period)
+    reg [15:0] tick = 16'b0;
 
     // State machine
     always @(posedge rclk or posedge rst) begin
@@ -61,41 +75,58 @@ module SpiMaster #(
             ready <= 1'b0;
             rx_data <= 8'b0;
             tx_buffer <= 8'b0;
+            tick <= 16'b0;
+            // A start signal still high after the reset does not trigger a transfer
+            start_prev <= 1'b1;
         end else begin
+            start_prev <= start;
             case (state)
                 STATE_IDLE: begin
-                    if (start) begin
+                    if (start && !start_prev) begin
                         // Load the tx_buffer and start transfer
                         tx_buffer <= tx_data;
                         rx_data <= 8'b0;
                         bit_cnt <= 3'b0;
                         ready <= 1'b0;
-                        state <= (CPHA == 0) ? STATE_TRANSFER_WRITE : STATE_TRANSFER_READ;
+                        tick <= 16'b0;
+                        state <= (cpha == 1'b0) ? STATE_TRANSFER_WRITE : STATE_TRANSFER_READ;
                     end
                 end
                 STATE_TRANSFER_WRITE: begin
-                    // Shift out data on MOSI
-                    state <= STATE_TRANSFER_READ;
-                    if (CPHA == 1) begin
-                        // Shift out next bit
-                        tx_buffer <= {tx_buffer[6:0], 1'b0};
+                    if (tick != divider) begin
+                        // Wait for the end of the half-period of spi_clk
+                        tick <= tick + 1;
+                    end else begin
+                        // Shift out data on MOSI
+                        tick <= 16'b0;
+                        state <= STATE_TRANSFER_READ;
+                        if (cpha == 1'b1) begin
+                            // Shift out next bit
+                            tx_buffer <= lsb_first ? {1'b0, tx_buffer[7:1]} : {tx_buffer[6:0], 1'b0};
+                        end
                     end
                 end
                 STATE_TRANSFER_READ: begin
-                    // Shift in data from MISO
-                    rx_data <= {rx_data[6:0], spi_miso};
-                    if (CPHA == 0) begin
-                        // Shift out next bit
-                        tx_buffer <= {tx_buffer[6:0], 1'b0};
-                    end
-                    bit_cnt <= bit_cnt + 1;
-                    if (bit_cnt == 3'b111) begin
-                        // All bits received, go back to idle
-                        state <= STATE_IDLE;
-                        ready <= 1'b1;
+                    if (tick != divider) begin
+                        // Wait for the end of the half-period of spi_clk
+                        tick <= tick + 1;
                     end else begin
-                        // Continue transfer
-                        state <= STATE_TRANSFER_WRITE;
+                        // Shift in data from MISO
+                        tick <= 16'b0;
+                        rx_data <= lsb_first ? {spi_miso, rx_data[7:1]} : {rx_data[6:0], spi_miso};
+                        if (cpha == 1'b0) begin
+                            // Shift out next bit
+                            tx_buffer <= lsb_first ? {1'b0, tx_buffer[7:1]} : {tx_buffer[6:0], 1'b0};
+                        end
+                        bit_cnt <= bit_cnt + 1;
+                        if (bit_cnt == 3'b111) begin
+                            // All bits received, go back to idle
+                            state <= STATE_IDLE;
+                            ready <= 1'b1;
+                        end else begin
+                            // Continue transfer
+                            state <= STATE_TRANSFER_WRITE;
+                        end
                     end
                 end
                 default: begin
@@ -106,9 +137,9 @@ module SpiMaster #(
     end
 
     // SPI clock generation
-    assign spi_clk = rst ? CPOL : (state == STATE_TRANSFER_READ ? (1'b1 - CPOL) : CPOL);
-    // SPI MOSI output -- shift out the MSB first
-    assign spi_mosi = tx_buffer[7];
+    assign spi_clk = rst ? cpol : (state == STATE_TRANSFER_READ ? ~cpol : cpol);
+    // SPI MOSI output -- shift out the MSB (or the LSB) first
+    assign spi_mosi = lsb_first ? tx_buffer[0] : tx_buffer[7];
 
     // External state signals
     assign busy = state[1]; // busy when in transfer state
@@ -4,9 +4,15 @@
 /// A memory-mapped SPI Master peripheral module.
 /// This module wraps the SpiMaster module and provides a memory-mapped
 /// interface for controlling SPI transfers and accessing received data.
+/// The bytes to send go through a TX FIFO, and the received bytes through an
+/// RX FIFO: the software can queue bytes while the previous ones are shifted.
+/// The SPI mode, the bit order and the clock divider are set in the CONTROL
+/// register (CPOL/CPHA are only the reset values).
 module SpiMasterPeripheral #(
     parameter CPOL = 0,
-    parameter CPHA = 0
+    parameter CPHA = 0,
+    // FIFO_DEPTH_LOG2 - log2 of the depth of the TX and RX FIFOs (in bytes)
+    parameter FIFO_DEPTH_LOG2 = 3
 ) (
     // Reset and clock
     input wire clk,
@@ -21,17 +27,20 @@ module SpiMasterPeripheral #(
     input wire spi_miso,
     
     // Peripheral interface - Memory Mapped I/O
+    // mem_en - High for one clk cycle per access to the peripheral (read or write)
     // mem_addr - Address of the register to access (8-bit address space)
     // mem_wr_en - Write enable signals (4 bits for byte enables - bit mask for 4 bytes)
     // mem_wr_data - Data to write to the register (32 bits)
     // mem_rd_data - Data read from the register (32 bits)
     // memdata is little-endian
+    input wire mem_en,
     input wire [7:0] mem_addr,
     input wire [3:0] mem_wr_en,
     input wire [31:0] mem_wr_data,
     output wire [31:0] mem_rd_data,
 
     // Debug interface
+    // debug_busy - High while a transfer is ongoing or the TX FIFO is not empty
     output wire debug_busy,
     output wire debug_ready,
     output wire debug_tx,
@@ -56,14 +65,23 @@ module SpiMasterPeripheral #(
     reg busy = 1'b0;
     wire mmio_start; // start signal - one clk pulse to trigger transfer
     wire mmio_soft_reset; // reset signal - one clk pulse to trigger reset
+    wire mmio_busy; // transfer ongoing or TX FIFO not empty
+    wire start_pending; // the start handshake is not over yet
+    // Configuration (clock-domain: clk, quasi-static -- only changed while idle,
+    // so it is used in the rclk domain without synchronizer)
+    wire cfg_cpol;
+    wire cfg_cpha;
+    wire cfg_lsb_first;
+    wire [15:0] cfg_divider;
 
     // SpiMaster -- The actual SPI driver
-    SpiMaster #(
-        .CPOL(CPOL),
-        .CPHA(CPHA)
-    ) spi_master_inst (
+    SpiMaster spi_master_inst (
         .rst(driver_reset),
         .rclk(rclk),
+        .cpol(cfg_cpol),
+        .cpha(cfg_cpha),
+        .lsb_first(cfg_lsb_first),
+        .divider(cfg_divider),
         .spi_clk(spi_clk),
         .spi_mosi(spi_mosi),
         .spi_miso(spi_miso),
@@ -75,19 +93,30 @@ module SpiMasterPeripheral #(
     );
 
     // SpiMasterInnerPeripheral -- Memory-mapped I/O interface
-    SpiMasterInnerPeripheral spi_master_mmio_inst (
+    SpiMasterInnerPeripheral #(
+        .CPOL(CPOL),
+        .CPHA(CPHA),
+        .FIFO_DEPTH_LOG2(FIFO_DEPTH_LOG2)
+    ) spi_master_mmio_inst (
         .clk(clk),
         .rst(rst),
+        .mem_en(mem_en),
         .mem_addr(mem_addr),
         .mem_wr_en(mem_wr_en),
         .mem_wr_data(mem_wr_data),
         .mem_rd_data(mem_rd_data),
         .start(mmio_start),
         .soft_reset(mmio_soft_reset),
+        .start_pending(start_pending),
         .busy(busy),
         .ready(ready),
         .rx_data(rx_data),
-        .tx_data(tx_data)
+        .tx_data(tx_data),
+        .fifo_busy(mmio_busy),
+        .cpol(cfg_cpol),
+        .cpha(cfg_cpha),
+        .lsb_first(cfg_lsb_first),
+        .divider(cfg_divider)
     );
 
     // The funny part is there - Clock domain crossing between clk and rclk
@@ -138,6 +167,8 @@ module SpiMasterPeripheral #(
             soft_reset <= mmio_soft_reset ? 1'b1 : reset_clk_ack[1] ? 1'b0 : soft_reset;
         end
     end
+    // The next byte is only started once the start signal is back low on both sides
+    assign start_pending = start | start_clk_ack[1];
 
     // Clock domain crossing for rx_data, ready, busy signals
     reg [1:0] ready_clk_sync;        // 2-stage synchronizer for ready
@@ -157,7 +188,7 @@ module SpiMasterPeripheral #(
     end
 
     // Debug signals
-    assign debug_busy = busy;
+    assign debug_busy = mmio_busy;
     assign debug_ready = ready;
     assign debug_tx = tx_data;
     assign debug_rx = rx_data;
@@ -168,7 +199,15 @@ endmodule
 /// This module is not supposed to be used directly. It is there to split up
 /// the actual SpiMasterPeripheral between the actual mmap-io part and the 
 /// (clock-)domain-crossing part.
-module SpiMasterInnerPeripheral (
+/// It holds the TX and RX FIFOs, and starts the transfers of the bytes of the
+/// TX FIFO one after the other (a byte is started once the previous one is
+/// received, and the start handshake is over).
+/// It also holds the configuration of the SpiMaster (CONTROL register).
+module SpiMasterInnerPeripheral #(
+    parameter CPOL = 0,
+    parameter CPHA = 0,
+    parameter FIFO_DEPTH_LOG2 = 3
+) (
     // Reset and clock
     // clk - Peripheral clock domain (for memory-mapped I/O)
     input wire clk,
@@ -176,11 +215,13 @@ module SpiMasterInnerPeripheral (
     input wire rst,
     
     // Peripheral interface - Memory Mapped I/O
+    // mem_en - High for one clk cycle per access to the peripheral (read or write)
     // mem_addr - Address of the register to access (8-bit address space)
     // mem_wr_en - Write enable signals (4 bits for byte enables - bit mask for 4 bytes)
     // mem_wr_data - Data to write to the register (32 bits)
     // mem_rd_data - Data read from the register (32 bits)
     // memdata is little-endian
+    input wire mem_en,
     input wire [7:0] mem_addr,
     input wire [3:0] mem_wr_en,
     input wire [31:0] mem_wr_data,
@@ -189,20 +230,35 @@ module SpiMasterInnerPeripheral (
     // Trigger and data signals
     output reg start,
     output reg soft_reset,
+    input wire start_pending,
     input wire busy,
     input wire ready,
     input wire [7:0] rx_data,
-    output reg [7:0] tx_data
+    output reg [7:0] tx_data,
+    // fifo_busy - High while a transfer is ongoing or the TX FIFO is not empty
+    output wire fifo_busy,
+
+    // Configuration of the SpiMaster (see the CONTROL register)
+    output reg cpol,
+    output reg cpha,
+    output reg lsb_first,
+    output reg [15:0] divider
 );
     // -- Peripheral registers --
-    // REG_CONTROL: Control register
+    // REG_CONTROL: Control and configuration register
     //   Address offset: 0x00
-    //   Write: Bit 0 = start (trigger transfer), Bit 1 = reset (reset internal state)
-    //   Read: N/A (returns 0)
+    //   Write: Bit 0 = start (send the last written byte again), Bit 1 = reset (reset
+    //          internal state and clear the FIFOs), Bit 2 = clear the RX FIFO and the
+    //          overrun flag (commands, byte 0)
+    //          Bit 8 = CPOL, Bit 9 = CPHA, Bit 10 = LSB first, Bits [31:16] = clock
+    //          divider (SPI_CLK = rclk / (2 * (divider + 1))) -- each byte is only
+    //          written when its byte enable is set, and must only be changed while idle
+    //   Read: The configuration (the command bits read as 0)
     localparam REG_CONTROL = 10'h00;
     // REG_WRITE_DATA: Write-only register to write data to be sent over SPI
     //   Address offset: 0x04
-    //   Write: Writing a byte to this register triggers an SPI transfer.
+    //   Write: Writing a byte to this register pushes it to the TX FIFO (dropped when
+    //          full), it is sent as soon as the previous bytes are.
     //   Read: N/A (returns 0)
     localparam REG_WRITE_DATA = 10'h04;
     // REG_READ_DATA: Read-only register to read data received from SPI
@@ -213,46 +269,169 @@ module SpiMasterInnerPeripheral (
     // REG_STATUS: Read-only register to read status of the SPI master
     //   Address offset: 0x0C
     //   Write: N/A (ignored)
-    //   Read: Bit 0 = ready, Bit 1 = busy, Bit 2 = tx_busy
+    //   Read: Bit 0 = ready, Bit 1 = busy (transfer ongoing or TX FIFO not empty)
     localparam REG_STATUS = 10'h0C;
     // REG_READ_AND_STATUS: Read-only register to read both data and status
     //   Address offset: 0x10
     //   Write: N/A (ignored)
-    //   Read: Bits [7:0] = rx_data, Bit 8 = ready, Bit 9 = busy, Bit 10 = tx_busy
+    //   Read: Bits [7:0] = rx_data, Bit 8 = ready, Bit 9 = busy
     localparam REG_READ_AND_STATUS = 10'h10;
+    // REG_RX_DATA: Read-only register to pop a byte from the RX FIFO
+    //   Address offset: 0x14
+    //   Write: N/A (ignored)
+    //   Read: Bits [7:0] = received byte, Bit 8 = valid (the RX FIFO was not empty)
+    localparam REG_RX_DATA = 10'h14;
+    // REG_FIFO_STATUS: Read-only register to read the FIFO flags and levels
+    //   Address offset: 0x18
+    //   Write: N/A (ignored)
+    //   Read: Bit 0 = TX FIFO full, Bit 1 = TX FIFO empty, Bit 2 = RX FIFO not empty,
+    //         Bit 3 = RX FIFO full, Bit 4 = overrun (a byte was received while the RX
+    //         FIFO was full, it was dropped), Bits [15:8] = TX FIFO level,
+    //         Bits [23:16] = RX FIFO level
+    localparam REG_FIFO_STATUS = 10'h18;
     // -- End of Peripheral registers --
 
+    localparam FIFO_DEPTH = 1 << FIFO_DEPTH_LOG2;
+
+    // FIFOs
+    reg [7:0] tx_fifo [0:FIFO_DEPTH-1];
+    reg [FIFO_DEPTH_LOG2-1:0] tx_head; // next byte to send
+    reg [FIFO_DEPTH_LOG2-1:0] tx_tail; // next free slot
+    reg [FIFO_DEPTH_LOG2:0] tx_count;
+    reg [7:0] rx_fifo [0:FIFO_DEPTH-1];
+    reg [FIFO_DEPTH_LOG2-1:0] rx_head; // next byte to read
+    reg [FIFO_DEPTH_LOG2-1:0] rx_tail; // next free slot
+    reg [FIFO_DEPTH_LOG2:0] rx_count;
+
+    // Transfer state
+    reg [7:0] tx_last;   // last byte written (sent again by the start bit)
+    reg in_flight;       // a byte is being transferred
+    reg ready_prev;      // previous ready signal (to detect its rising edge)
+    reg overrun;         // sticky, a received byte was dropped
+
     // Helper signals 
     wire [9:0] mem_addr_ext = {mem_addr, 2'b00}; // Extend to word-aligned address
+    wire mem_write = mem_en && mem_wr_en[0];
+    wire mem_read = mem_en && (mem_wr_en == 4'b0000);
+    wire ctrl_write = mem_write && (mem_addr_ext == REG_CONTROL);
+    wire ctrl_access = mem_en && (mem_addr_ext == REG_CONTROL);
+    wire tx_full = (tx_count == FIFO_DEPTH);
+    wire tx_empty = (tx_count == 0);
+    wire rx_full = (rx_count == FIFO_DEPTH);
+    wire rx_empty = (rx_count == 0);
 
-    // Handle Reset and Write to memory-mapped I/O
+    // FIFO operations of this cycle
+    // - push: a write of WRITE_DATA, or of the start bit
+    // - pop: the next byte is started once the previous one is received
+    // - done: the byte in flight is received (rising edge of ready)
+    wire tx_push_data = mem_write && (mem_addr_ext == REG_WRITE_DATA);
+    wire tx_push = (tx_push_data || (ctrl_write && mem_wr_data[0])) && !tx_full;
+    wire tx_pop = !tx_empty && !in_flight && !start && !start_pending;
+    wire done = in_flight && ready && !ready_prev;
+    wire rx_pop = mem_read && (mem_addr_ext == REG_RX_DATA) && !rx_empty;
+    wire rx_push = done && (!rx_full || rx_pop);
+
+    assign fifo_busy = in_flight || !tx_empty;
+
+    // Handle Reset and Write to memory-mapped I/O, and the FIFOs
     always @(posedge clk or posedge rst) begin
         if (rst) begin
             start <= 1'b0;
             tx_data <= 8'b0;
             soft_reset <= 1'b0;
+            tx_head <= 0;
+            tx_tail <= 0;
+            tx_count <= 0;
+            rx_head <= 0;
+            rx_tail <= 0;
+            rx_count <= 0;
+            tx_last <= 8'b0;
+            in_flight <= 1'b0;
+            ready_prev <= 1'b0;
+            overrun <= 1'b0;
+            cpol <= CPOL;
+            cpha <= CPHA;
+            lsb_first <= 1'b0;
+            divider <= 16'b0;
         end else begin
-            // Handle writes
-            if (|mem_wr_en) begin
-                start <=    (mem_addr_ext == REG_CONTROL && mem_wr_en[0] && mem_wr_data[0]) ? 1'b1 : // CONTROL - start bit
-                            (mem_addr_ext == REG_WRITE_DATA && mem_wr_en[0]) ? 1'b1 : // WRITE_DATA - start transfer on write
-                            1'b0; // Clear start otherwise
-                soft_reset <= (mem_addr_ext == REG_CONTROL && mem_wr_en[0] && mem_wr_data[1]) ? 1'b1 : 1'b0; // CONTROL - reset bit
-                if (mem_addr_ext == REG_WRITE_DATA && mem_wr_en[0]) begin
-                    tx_data <= mem_wr_data[7:0];
-                end
-            end else begin
-                start <= 1'b0; // Clear start if no write
-                soft_reset <= 1'b0; // Clear reset if no write
+            // Start pulse for the byte popped from the TX FIFO
+            start <= tx_pop;
+            soft_reset <= ctrl_write && mem_wr_data[1]; // CONTROL - reset bit
+            ready_prev <= ready;
+
+            // Configuration (byte 1: mode and bit order, bytes 2-3: divider)
+            if (ctrl_access && mem_wr_en[1]) begin
+                cpol <= mem_wr_data[8];
+                cpha <= mem_wr_data[9];
+                lsb_first <= mem_wr_data[10];
+            end
+            if (ctrl_access && mem_wr_en[2]) begin
+                divider[7:0] <= mem_wr_data[23:16];
+            end
+            if (ctrl_access && mem_wr_en[3]) begin
+                divider[15:8] <= mem_wr_data[31:24];
+            end
+
+            // TX FIFO
+            if (tx_push) begin
+                tx_fifo[tx_tail] <= tx_push_data ? mem_wr_data[7:0] : tx_last;
+                tx_tail <= tx_tail + 1'b1;
+            end
+            if (tx_push_data) begin
+                tx_last <= mem_wr_data[7:0];
+            end
+            if (tx_pop) begin
+                tx_data <= tx_fifo[tx_head];
+                tx_head <= tx_head + 1'b1;
+                in_flight <= 1'b1;
+            end else if (done) begin
+                in_flight <= 1'b0;
+            end
+
+            // RX FIFO (the received byte is dropped if it is full)
+            if (rx_pop) begin
+                rx_head <= rx_head + 1'b1;
+            end
+            if (rx_push) begin
+                rx_fifo[rx_tail] <= rx_data;
+                rx_tail <= rx_tail + 1'b1;
+            end
+            if (done && !rx_push) begin
+                overrun <= 1'b1;
+            end
+
+            // Update the FIFO levels
+            tx_count <= tx_count + tx_push - tx_pop;
+            rx_count <= rx_count + rx_push - rx_pop;
+
+            // Clear the FIFOs (the reset also drops the byte in flight)
+            if (ctrl_write && mem_wr_data[1]) begin
+                start <= 1'b0;
+                tx_head <= 0;
+                tx_tail <= 0;
+                tx_count <= 0;
+                in_flight <= 1'b0;
+            end
+            if (ctrl_write && (mem_wr_data[1] || mem_wr_data[2])) begin
+                rx_head <= 0;
+                rx_tail <= 0;
+                rx_count <= 0;
+                overrun <= 1'b0;
             end
         end
     end
 
     // Handle Read from memory-mapped I/O (must be combinational)
+    // Note: a read of RX_DATA pops the RX FIFO
     assign mem_rd_data = rst ? 32'b0 :
-                         (mem_addr_ext == REG_WRITE_DATA || mem_addr_ext == REG_CONTROL) ? 32'b0 :
+                         (mem_addr_ext == REG_CONTROL) ? {divider, 5'b0, lsb_first, cpha, cpol, 8'b0} :
+                         (mem_addr_ext == REG_WRITE_DATA) ? 32'b0 :
                          (mem_addr_ext == REG_READ_DATA) ? {24'b0, rx_data} :
-                         (mem_addr_ext == REG_STATUS) ? {30'b0, busy, ready} :
-                         (mem_addr_ext == REG_READ_AND_STATUS) ? {22'b0, busy, ready, rx_data} :
+                         (mem_addr_ext == REG_STATUS) ? {30'b0, fifo_busy, ready} :
+                         (mem_addr_ext == REG_READ_AND_STATUS) ? {22'b0, fifo_busy, ready, rx_data} :
+                         (mem_addr_ext == REG_RX_DATA) ? {23'b0, !rx_empty, rx_fifo[rx_head]} :
+                         (mem_addr_ext == REG_FIFO_STATUS) ? {8'b0, {(7-FIFO_DEPTH_LOG2){1'b0}}, rx_count,
+                                                              {(7-FIFO_DEPTH_LOG2){1'b0}}, tx_count,
+                                                              3'b0, overrun, rx_full, !rx_empty, tx_empty, tx_full} :
                          32'b0; // Default case
 endmodule
\ No newline at end of file
@@ -106,6 +106,7 @@ module SpiMasterPeripheral_flash_tb (
     parameter CPHA = 0;
 
     // Testbench signals
+    reg mem_en;
     reg [9:0] mem_addr_ext;
     wire [7:0] mem_addr = mem_addr_ext[9:2];
     reg [3:0] mem_wr_en;
@@ -130,6 +131,7 @@ module SpiMasterPeripheral_flash_tb (
         .spi_miso(spi_miso),
 
         // Peripheral interface - Memory Mapped I/O
+        .mem_en(mem_en),
         .mem_addr(mem_addr),
         .mem_wr_en(mem_wr_en),
         .mem_wr_data(mem_wr_data),
@@ -145,6 +147,7 @@ module SpiMasterPeripheral_flash_tb (
     always @(posedge clk or posedge rst) begin
         if (rst) begin
             dut_rst <= 1;
+            mem_en <= 1'b0;
             mem_addr_ext <= 10'h00;
             mem_wr_en <= 4'b0000;
             mem_wr_data <= 32'h00000000;
@@ -163,6 +166,7 @@ module SpiMasterPeripheral_flash_tb (
                 mem_addr_ext <= 10'h00; // Address 0x00 - control register
                 mem_wr_en <= 4'b0001; // Write enable for byte 0
                 mem_wr_data <= 32'h00000002; // Write 0x02 to control register to issue reset
+                mem_en <= (seq == 1); // One access
                 seq <= seq + 1;
             end else if (seq >= 5 && seq <= 30) begin
                 // Clear write enable
@@ -173,6 +177,7 @@ module SpiMasterPeripheral_flash_tb (
                 mem_addr_ext <= 10'h04; // Address 0x04 - TX register
                 mem_wr_en <= 4'b0001; // Write enable for byte 0
                 mem_wr_data <= 32'h00000095; // Write 0x95 to TX register
+                mem_en <= (seq == 31); // One access
                 seq <= seq + 1;
             end else if (seq >= 35 && seq <= 39) begin
                 // Clear write enable
@@ -192,6 +197,7 @@ module SpiMasterPeripheral_flash_tb (
                 mem_addr_ext <= 10'h04; // Address 0x04 - TX register
                 mem_wr_en <= 4'b0001; // Write enable for byte 0
                 mem_wr_data <= 32'h0000003A; // Write 0x3A to TX register
+                mem_en <= (seq == 41); // One access
                 seq <= seq + 1;
             end else if (seq >= 45 && seq <= 49) begin
                 // Clear write enable
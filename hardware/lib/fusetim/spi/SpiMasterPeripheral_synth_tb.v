@@ -16,6 +16,7 @@ module SpiMasterPeripheral_synth_tb ();
     wire spi_clk;
     wire spi_mosi;
     reg spi_miso = 0;
+    reg mem_en;
     reg [9:0] mem_addr_ext;
     wire [7:0] mem_addr = mem_addr_ext[9:2];
     reg [3:0] mem_wr_en;
@@ -39,6 +40,7 @@ module SpiMasterPeripheral_synth_tb ();
         .spi_miso(spi_miso),
 
         // Peripheral interface - Memory Mapped I/O
+        .mem_en(mem_en),
         .mem_addr(mem_addr),
         .mem_wr_en(mem_wr_en),
         .mem_wr_data(mem_wr_data),
@@ -64,6 +66,7 @@ module SpiMasterPeripheral_synth_tb ();
 
         // Initialize signals
         rst = 1;
+        mem_en = 0;
         mem_addr_ext = 10'h00;
         mem_wr_en = 0;
         mem_wr_data = 32'h00000000;
@@ -77,7 +80,10 @@ module SpiMasterPeripheral_synth_tb ();
         mem_addr_ext = 10'h00; // Control Register
         mem_wr_en = 4'b0001; // Write enable - byte 0
         mem_wr_data = 32'h00000002; // Set the reset bit
-        #(4*CLK_PERIOD); // Wait the time of a store instruction - 4 cycles
+        mem_en = 1; // One access
+        #(CLK_PERIOD);
+        mem_en = 0;
+        #(3*CLK_PERIOD); // Wait the time of a store instruction - 4 cycles
         mem_wr_en = 0; // De-assert write enable
 
         #(8*4*CLK_PERIOD); // Wait some time - Reset takes a few clk cycles to propagate
@@ -86,7 +92,10 @@ module SpiMasterPeripheral_synth_tb ();
         mem_addr_ext = 10'h04; // Write Data Register
         mem_wr_en = 4'b0001; // Write enable - byte 0
         mem_wr_data = 32'h000000CA; // Data to write
-        #(4*CLK_PERIOD); // Wait the time of a store instruction - 4 cycles
+        mem_en = 1; // One access
+        #(CLK_PERIOD);
+        mem_en = 0;
+        #(3*CLK_PERIOD); // Wait the time of a store instruction - 4 cycles
         mem_wr_en = 0; // De-assert write enable
         mem_addr_ext = 10'h0C; // Status Register
 
@@ -13,7 +13,8 @@ module SpiMasterPeripheral_tb ();
     reg rclk;
     wire spi_clk;
     wire spi_mosi;
-    reg spi_miso = 0;
+    wire spi_miso = spi_mosi; // Loopback - the received bytes are the sent ones
+    reg mem_en;
     reg [9:0] mem_addr_ext;
     wire [7:0] mem_addr = mem_addr_ext[9:2];
     reg [3:0] mem_wr_en;
@@ -37,6 +38,7 @@ module SpiMasterPeripheral_tb ();
         .spi_miso(spi_miso),
 
         // Peripheral interface - Memory Mapped I/O
+        .mem_en(mem_en),
         .mem_addr(mem_addr),
         .mem_wr_en(mem_wr_en),
         .mem_wr_data(mem_wr_data),
@@ -53,7 +55,37 @@ module SpiMasterPeripheral_tb ();
         forever #(rclk_PERIOD/2) rclk = ~rclk;
     end
 
+    // Write a register (one clk cycle, as a word store of the CPU)
+    task mmio_write(input [9:0] addr, input [31:0] data);
+        begin
+            mem_addr_ext = addr;
+            mem_wr_en = 4'b1111; // Write enable - all bytes
+            mem_wr_data = data;
+            mem_en = 1;
+            #(CLK_PERIOD);
+            mem_en = 0;
+            mem_wr_en = 0; // De-assert write enable
+            #(3*CLK_PERIOD); // The time of a store instruction - 4 cycles
+        end
+    endtask
+
+    // Read a register (one clk cycle, as a load of the CPU)
+    task mmio_read(input [9:0] addr, output [31:0] data);
+        begin
+            mem_addr_ext = addr;
+            mem_en = 1;
+            #(CLK_PERIOD/4);
+            data = mem_rd_data; // Before the clk edge (a read of RX_DATA pops the RX FIFO)
+            #(3*CLK_PERIOD/4);
+            mem_en = 0;
+            #(3*CLK_PERIOD);
+        end
+    endtask
+
     // Test sequence
+    reg [31:0] value;
+    reg [7:0] expected [0:2];
+    integer i;
     initial begin
         // Initialize waveform dump
         $dumpfile("./SpiMasterPeripheral.vcd");
@@ -62,33 +94,56 @@ module SpiMasterPeripheral_tb ();
 
         // Initialize signals
         rst = 1;
+        mem_en = 0;
         mem_addr_ext = 10'h00;
         mem_wr_en = 0;
         mem_wr_data = 32'h00000000;
-        spi_miso = 1'b0;
 
         // Release reset
         #(2*CLK_PERIOD);
         rst = 0;
 
         // SPI Initialize (need a reset first)
-        mem_addr_ext = 10'h00; // Control Register
-        mem_wr_en = 4'b0001; // Write enable - byte 0
-        mem_wr_data = 32'h00000002; // Set the reset bit
-        #(4*CLK_PERIOD); // Wait the time of a store instruction - 4 cycles
-        mem_wr_en = 0; // De-assert write enable
-
+        mmio_write(10'h00, 32'h00000002); // Control Register - Set the reset bit
         #(8*4*CLK_PERIOD); // Wait some time - Reset takes a few clk cycles to propagate
 
-        // New transfer - Write 0xCA over SPI
-        mem_addr_ext = 10'h04; // Write Data Register
-        mem_wr_en = 4'b0001; // Write enable - byte 0
-        mem_wr_data = 32'h000000CA; // Data to write
-        #(4*CLK_PERIOD); // Wait the time of a store instruction - 4 cycles
-        mem_wr_en = 0; // De-assert write enable
-        mem_addr_ext = 10'h0C; // Status Register
+        // New transfer - Write 0xCA, 0xFE, 0x42 over SPI, back-to-back
+        expected[0] = 8'hCA;
+        expected[1] = 8'hFE;
+        expected[2] = 8'h42;
+        for (i = 0; i < 3; i = i + 1) begin
+            mmio_write(10'h04, {24'b0, expected[i]}); // Write Data Register
+        end
+
+        // Wait for the transfers to complete
+        mmio_read(10'h0C, value); // Status Register
+        while (value[1]) mmio_read(10'h0C, value);
+
+        // Check the received bytes (loopback), and the empty FIFO afterwards
+        mmio_read(10'h18, value); // FIFO Status Register
+        $display("FIFO status: 0x%h (expect RX level 3)", value);
+        if (value[23:16] != 3) $display("Test failed: RX FIFO level does not match expected value.");
+        for (i = 0; i < 3; i = i + 1) begin
+            mmio_read(10'h14, value); // RX Data Register
+            $display("Received data: 0x%h (expect 0x%h)", value[7:0], expected[i]);
+            if (!value[8] || value[7:0] != expected[i]) $display("Test failed: Received data does not match expected value.");
+        end
+        mmio_read(10'h14, value);
+        if (value[8]) $display("Test failed: RX FIFO should be empty.");
+
+        // Reconfigure - LSB first, divider 2 (SPI_CLK = rclk / 6), and read it back
+        mmio_write(10'h00, 32'h00020400); // Control Register - configuration
+        mmio_read(10'h00, value);
+        $display("Control: 0x%h (expect 0x00020400)", value);
+        if (value != 32'h00020400) $display("Test failed: Control register does not match expected value.");
 
-        #(128*CLK_PERIOD); // Wait for transfer to complete
+        // New transfer - Write 0x3C with the new configuration
+        mmio_write(10'h04, 32'h0000003C); // Write Data Register
+        mmio_read(10'h0C, value); // Status Register
+        while (value[1]) mmio_read(10'h0C, value);
+        mmio_read(10'h14, value); // RX Data Register
+        $display("Received data: 0x%h (expect 0x3c)", value[7:0]);
+        if (!value[8] || value[7:0] != 8'h3C) $display("Test failed: Received data does not match expected value.");
 
         // Finish simulation
         #(2*CLK_PERIOD);
@@ -22,12 +22,13 @@ module SpiMaster_tb ();
     reg spi_miso;
 
     // The Device Under Test (DUT)
-    SpiMaster #(
-        //.CPOL(CPOL),
-        //.CPHA(CPHA)
-    ) dut (
+    SpiMaster dut (
         .rst(rst),
         .rclk(rclk),
+        .cpol(CPOL[0]),
+        .cpha(CPHA[0]),
+        .lsb_first(1'b0),
+        .divider(16'd0),
         .spi_clk(spi_clk),
         .spi_mosi(spi_mosi),
         .spi_miso(spi_miso),
@@ -1,91 +1,161 @@
 /// Audio visualization module
 ///
-/// This module takes 8-bit audio sample (centered at 128), one sample per clock rise,
-/// and produces an 8-bit energy visualization output. 
+/// This module takes 8-bit audio samples (left and right, centered at a configurable reference level),
+/// one sample per clock rise, and produces an 8-bit energy visualization output (one bit per LED).
 ///
-/// To do so, this module compute the RMS energy of the audio signal over a window of 1024 samples, 
-/// and outputs the energy level as an 8-bit value. The energy level is computed as follows:
-/// 1. For each incoming audio sample, compute the squared difference from the center value (128):
-///    energy_sample = (audio_in - 128) * (audio_in - 128)
-/// 2. Accumulate the energy samples over a window of 1024 samples:
-///    energy_accum += energy_sample
-/// 3. After processing 1024 samples, compute the RMS energy:
-///    energy_rms = sqrt(energy_accum / 1024)
-/// 4. Normalize the RMS energy to fit into an 8-bit value (0-255):
-///    viz_out = (energy_rms / max_energy) * 255
+/// To do so, this module computes the mean energy of the audio signal over a window of ~4096 samples,
+/// and compares it to a set of thresholds. The energy level is computed as follows:
+/// 1. For each incoming audio sample, compute the squared difference from the reference level
+///    (128 at full volume, lower when the samples are scaled down by the volume control):
+///    energy_sample = (audio_in - reference) * (audio_in - reference)
+/// 2. Average the energy samples with a leaky integrator (time constant of 4096 samples):
+///    energy_avg += energy_sample - energy_avg / 4096
+/// 3. Amplify the mean energy by the sensitivity (each step doubles the energy, i.e. +3dB),
+///    and compare it to 8 thresholds to get a level between 0 and 8.
 ///
-/// Note: The max_energy is the maximum possible RMS energy, which occurs when all samples are at the maximum deviation from the center (0 or 255).
-///       Therefore, max_energy can be computed as:
-///       max_energy = sqrt(((255 - 128) * (255 - 128)) * 1024) / 1024) = sqrt(127 * 127) = 127
+/// The level is then displayed on the LEDs according to the selected mode:
+/// - 0: BAR   - the LEDs below the level are lit (VU-meter),
+/// - 1: DOT   - only the LED at the level is lit,
+/// - 2: PEAK  - bar, plus the highest recent level kept lit for ~0.5s and then decaying,
+/// - 3: SPLIT - left channel on LEDs 3-0 and right channel on LEDs 4-7 (4 levels each,
+///              both bars growing from the center).
 ///
-/// To optimize the computation, we avoid to recompute all the square root ops for each samples, instead
-/// we use a running sum (ring buffer for the samples, and a accumulator for the energy) to compute the energy in 
-/// a more efficient way. In the end, the result is the same, but the implementation is more efficient in logic/multipliers,
-/// but it requires more registers to store the intermediate values.
-///
-/// NOTE: Apparently, professionally, the RMS energy is computed over windows of 300ms, which for a sample rate of 48kHz, 
-/// corresponds to 13230 samples. So it costs quite a bit of memory to store the energy samples for such a large window.
-/// I tried with 13230 samples but yosys struggled to synthesize the design, so I went with a smaller window of 4096 samples,
-/// which is still a good approximation for visualization purposes (and does not make x10 on the synthesis phase).
+/// NOTE: This module used to keep the last 4096 energy samples in a ring buffer to compute an exact
+/// moving average. The leaky integrator gives the same time constant without the buffer, which leaves
+/// room to measure both channels.
+/// Apparently, professionally, the RMS energy is computed over windows of 300ms, which for a sample rate of 48kHz,
+/// corresponds to 13230 samples.
 module audio_viz (
     input clk,
     input [7:0] audio_in,
+    input [7:0] audio_in_r,
     output reg [7:0] viz_out,
     input audio_viz_en = 1'b1,
+    input [1:0] viz_mode,
+    input [3:0] viz_sensitivity,
+    input [7:0] viz_reference
 );
 
-    // 1. Make sure he input is signed and ready for being squared
-    wire signed [16:0] signed_audio = audio_in;
+    localparam MODE_BAR   = 2'd0;
+    localparam MODE_DOT   = 2'd1;
+    localparam MODE_PEAK  = 2'd2;
+    localparam MODE_SPLIT = 2'd3;
 
-    // 2. Compute the energy sample (squared difference from the center value)
-    reg signed [16:0] centered_audio;
-    reg [13:0] energy_sample;
-    always @(posedge clk) begin
-        if (audio_viz_en) begin
-            centered_audio <= signed_audio - 128; // This only work if volume is at max, otherwise the DC offset is not 128.
-            // This will be a 16-bit value, since the maximum value is 127*127 = 16129
-            energy_sample <= centered_audio * centered_audio; 
-        end
-    end
+    // Peak-hold timings (in samples, at 48kHz)
+    localparam PEAK_HOLD  = 24000; // ~500ms before the peak starts to decay
+    localparam PEAK_DECAY = 4000;  // ~80ms per LED while decaying
+
+    // 1-3. Energy levels of both channels
+    wire [3:0] level_l;
+    wire [3:0] level_r;
+    audio_viz_energy energy_l (
+        .clk(clk),
+        .en(audio_viz_en),
+        .audio_in(audio_in),
+        .reference(viz_reference),
+        .sensitivity(viz_sensitivity),
+        .level(level_l)
+    );
+    audio_viz_energy energy_r (
+        .clk(clk),
+        .en(audio_viz_en & (viz_mode == MODE_SPLIT)), // right channel only needed in split mode
+        .audio_in(audio_in_r),
+        .reference(viz_reference),
+        .sensitivity(viz_sensitivity),
+        .level(level_r)
+    );
 
-    // 3. Accumulate the energy samples over a window of 4096 samples
-    reg [25:0] energy_accum = 0; // 26 bits to store the accumulated energy (max (128*128) * (2^12) = ~2^26)
-    reg [11:0] samples_head = 0; // 12 bits to count up to 4096 samples
-    reg [13:0] energy_samples_buffer [4095:0]; // Buffer to store the last 4096 energy samples
+    // Bar of `lvl` LEDs (0-8)
+    function [7:0] bar(input [3:0] lvl);
+        bar = (9'h1 << lvl) - 1;
+    endfunction
+    // Single LED at `lvl` (1-8), none at 0
+    function [7:0] dot(input [3:0] lvl);
+        dot = (lvl == 0) ? 8'h00 : (8'h1 << (lvl - 1));
+    endfunction
+
+    // Peak-hold state
+    reg [3:0] peak = 0;
+    reg [14:0] peak_timer = 0;
 
     initial begin
-        energy_accum = 0;
-        samples_head = 0;
         viz_out = 0;
-        // Initialize the energy samples buffer to 0
-        for (int i = 0; i < 4096; i++) begin
-            energy_samples_buffer[i] = 0;
-        end
     end
 
     always @(posedge clk) begin
-        // Subtract the oldest energy sample from the accumulator
-        energy_accum <= energy_accum - energy_samples_buffer[samples_head] + energy_sample;
-        // Store the new energy sample in the buffer
-        energy_samples_buffer[samples_head] <= energy_sample;
-        // Move the head of the buffer
-        samples_head <= samples_head + 1;
+        if (level_l >= peak) begin
+            // New peak, hold it
+            peak <= level_l;
+            peak_timer <= PEAK_HOLD;
+        end else if (peak_timer != 0) begin
+            peak_timer <= peak_timer - 1;
+        end else begin
+            // Decay, one LED at a time
+            peak <= peak - 1;
+            peak_timer <= PEAK_DECAY;
+        end
     end
 
-    // 4. Compute the RMS energy and normalize it to fit into an 8-bit value
+    // 4. Display the levels on the LEDs
+    wire [3:0] split_l = (level_l + 1) >> 1; // 0-8 -> 0-4
+    wire [3:0] split_r = (level_r + 1) >> 1;
+    wire [7:0] bar_l = bar(split_l);
+    wire [7:0] bar_r = bar(split_r);
     always @(posedge clk) begin
         if (audio_viz_en) begin
-            viz_out[0] <= energy_accum[24:12] >= 4; // RMS > 0
-            viz_out[1] <= energy_accum[24:12] >= 16; // RMS > 0.125
-            viz_out[2] <= energy_accum[24:12] >= 64; // RMS > 0.25
-            viz_out[3] <= energy_accum[24:12] >= 256; // RMS > 0.5
-            viz_out[4] <= energy_accum[24:12] >= 512; // RMS > 1
-            viz_out[5] <= energy_accum[24:12] >= 1024; // RMS > 2
-            viz_out[6] <= energy_accum[24:12] >= 2048; // RMS > 4
-            viz_out[7] <= energy_accum[24:12] >= 4096; // RMS > 8
+            case (viz_mode)
+                MODE_BAR:   viz_out <= bar(level_l);
+                MODE_DOT:   viz_out <= dot(level_l);
+                MODE_PEAK:  viz_out <= bar(level_l) | dot(peak);
+                MODE_SPLIT: viz_out <= {bar_r[3:0], bar_l[0], bar_l[1], bar_l[2], bar_l[3]};
+            endcase
         end else begin
             viz_out <= 0; // If visualization is disabled, output 0
         end
     end
 
-endmodule 
\ No newline at end of file
+endmodule
+
+/// Energy meter of a single channel (see audio_viz).
+///
+/// Outputs the energy level of the channel, between 0 (silence) and 8 (full scale).
+module audio_viz_energy (
+    input clk,
+    input en,
+    input [7:0] audio_in,
+    input [7:0] reference,
+    input [3:0] sensitivity,
+    output reg [3:0] level
+);
+
+    // 1. Compute the energy sample (squared difference from the reference level)
+    reg signed [9:0] centered_audio = 0;
+    reg [15:0] energy_sample = 0; // max 255*255 = 65025
+    always @(posedge clk) begin
+        if (en) begin
+            centered_audio <= $signed({2'b00, audio_in}) - $signed({2'b00, reference});
+            energy_sample <= centered_audio * centered_audio;
+        end
+    end
+
+    // 2. Average the energy samples (leaky integrator over ~4096 samples)
+    reg [27:0] energy_avg = 0; // mean energy * 4096
+    always @(posedge clk) begin
+        if (en) begin
+            energy_avg <= energy_avg - (energy_avg >> 12) + energy_sample;
+        end else begin
+            energy_avg <= 0;
+        end
+    end
+
+    // 3. Amplify the mean energy and compare it to the thresholds
+    wire [31:0] energy = {16'b0, energy_avg[27:12]} << sensitivity;
+    initial begin
+        level = 0;
+    end
+    always @(posedge clk) begin
+        level <= (energy >= 4) + (energy >= 16) + (energy >= 64) + (energy >= 256)
+               + (energy >= 512) + (energy >= 1024) + (energy >= 2048) + (energy >= 4096);
+    end
+
+endmodule
@@ -0,0 +1,233 @@
+//! Animation module
+//! This module provides a small animation engine for the LEDs of the [`LedBank`].
+//!
+//! [`LedAnimator`] renders an [`Animation`] against Timer0 each time it is updated, so the
+//! animations go on without blocking, as long as the animator is updated regularly (e.g. from
+//! the main loop of the application):
+//! - `Static`: fixed levels,
+//! - `FadeTo`: a fade from the current levels to new levels,
+//! - `Breathe`: some LEDs fade out and in, endlessly,
+//! - `Chase`: a lit LED with a fading tail goes around, endlessly,
+//! - `Progress`: a fill from LED0, the last LED partially lit (a new fill fades in).
+//!
+//! The levels are perceptual brightness levels (0 = off, 255 = fully on), they are turned into
+//! PWM duty cycles with a gamma correction.
+
+use crate::gpio::LedBank;
+use crate::timer::{CopyableTimer0, Duration, Instant, Timer};
+
+/// Number of LEDs.
+pub const LEDS: usize = 8;
+
+/// Duration of the fade of the `Progress` animation to a new fill.
+pub const PROGRESS_FADE: Duration = Duration::millis(150);
+
+/// Levels of the tail of the `Chase` animation (head first).
+const CHASE_TAIL: [u8; 3] = [255, 96, 32];
+
+/// Animation of the LEDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Animation {
+    /// Fixed levels.
+    Static([u8; LEDS]),
+    /// Fade from the current levels to new levels.
+    FadeTo {
+        levels: [u8; LEDS],
+        duration: Duration,
+    },
+    /// The LEDs of a mask fade out and back in each period (the other LEDs are off).
+    Breathe { leds: u8, period: Duration },
+    /// A lit LED with a fading tail moves by one LED each step, from LED0 to LED7 and around.
+    Chase { step: Duration },
+    /// Fill the LEDs from LED0 with a progress (`done` out of `total`).
+    Progress { done: u32, total: u32 },
+}
+
+impl Animation {
+    /// All LEDs off.
+    pub const OFF: Self = Self::Static([0; LEDS]);
+
+    /// Fixed on/off states (at full brightness).
+    ///
+    /// # Arguments
+    ///
+    /// * `states` - The states of the LEDs.
+    pub fn states(states: [bool; LEDS]) -> Self {
+        Self::Static(states.map(|on| if on { 255 } else { 0 }))
+    }
+
+    /// Check if the animation ends (the endless animations never do).
+    #[inline(always)]
+    pub fn is_endless(&self) -> bool {
+        matches!(self, Self::Breathe { .. } | Self::Chase { .. })
+    }
+}
+
+/// Levels of a progress fill, the last LED is partially lit.
+///
+/// # Arguments
+///
+/// * `done` - The progress.
+/// * `total` - The progress when done (the LEDs are all off if 0), at most ~2 million.
+pub fn progress_levels(done: u32, total: u32) -> [u8; LEDS] {
+    if total == 0 {
+        return [0; LEDS];
+    }
+    // Fill in 1/255th of a LED
+    let fill = done.min(total).saturating_mul(LEDS as u32 * 255) / total;
+    core::array::from_fn(|i| fill.saturating_sub(i as u32 * 255).min(255) as u8)
+}
+
+/// Saturate a number of microseconds to 32 bits (~71 minutes).
+#[inline(always)]
+fn micros(ticks: u64) -> u32 {
+    ticks.min(u32::MAX as u64) as u32
+}
+
+/// PWM duty cycle of a brightness level (gamma correction, ~2).
+#[inline(always)]
+fn duty_cycle(level: u8) -> u8 {
+    // A LED at a level above 0 is never fully off
+    (level as u16 * level as u16).div_ceil(255) as u8
+}
+
+/// Animation engine of the LEDs, driven by Timer0.
+pub struct LedAnimator {
+    leds: LedBank,
+    timer: Timer<CopyableTimer0>,
+    animation: Animation,
+    started_at: Instant,
+    /// Levels when the animation started (for the fades)
+    from: [u8; LEDS],
+    /// Current levels
+    levels: [u8; LEDS],
+}
+
+impl LedAnimator {
+    /// Create a new animation engine, the LEDs keep their states (at full brightness).
+    ///
+    /// Timer0 must be enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `leds` - The LEDs.
+    pub fn new(mut leds: LedBank) -> Self {
+        let timer = Timer::new_timer0();
+        let levels = leds.read_all_states().map(|on| if on { 255 } else { 0 });
+        leds.set_all_levels(levels);
+        Self {
+            leds,
+            timer,
+            animation: Animation::Static(levels),
+            started_at: timer.get_counter(),
+            from: levels,
+            levels,
+        }
+    }
+
+    /// Bring down the animation engine and get the LEDs back.
+    ///
+    /// The LEDs keep their states, at full brightness.
+    pub fn bring_down(mut self) -> LedBank {
+        self.leds.set_all_brightness([255; LEDS]);
+        self.leds
+    }
+
+    /// Get the LEDs, e.g. to drive some of them directly while a `Static` animation is shown.
+    ///
+    /// The next update overrides them if the animation is not done.
+    #[inline(always)]
+    pub fn leds_mut(&mut self) -> &mut LedBank {
+        &mut self.leds
+    }
+
+    /// Get the current animation.
+    #[inline(always)]
+    pub fn animation(&self) -> &Animation {
+        &self.animation
+    }
+
+    /// Get the current levels of the LEDs.
+    #[inline(always)]
+    pub fn levels(&self) -> [u8; LEDS] {
+        self.levels
+    }
+
+    /// Start an animation, from the current levels, and render its first frame.
+    ///
+    /// Starting the current `Progress` or `Static` animation again has no effect (a fade is not
+    /// restarted).
+    ///
+    /// # Arguments
+    ///
+    /// * `animation` - The animation.
+    pub fn play(&mut self, animation: Animation) {
+        let same = animation == self.animation;
+        if same && matches!(animation, Animation::Progress { .. } | Animation::Static(_)) {
+            return;
+        }
+        self.animation = animation;
+        self.started_at = self.timer.get_counter();
+        self.from = self.levels;
+        self.update();
+    }
+
+    /// Check if the animation is done (never for the endless animations).
+    pub fn is_done(&self) -> bool {
+        let elapsed = self.timer.get_counter() - self.started_at;
+        match self.animation {
+            Animation::Static(_) => true,
+            Animation::FadeTo { duration, .. } => elapsed >= duration,
+            Animation::Progress { .. } => elapsed >= PROGRESS_FADE,
+            Animation::Breathe { .. } | Animation::Chase { .. } => false,
+        }
+    }
+
+    /// Render the animation at the current instant, the LEDs are only written on a change.
+    pub fn update(&mut self) {
+        // The times are computed on 32 bits (no 64-bit division): the fades saturate, the endless
+        // animations wrap around every ~71 minutes (a single glitch)
+        let elapsed = (self.timer.get_counter() - self.started_at).ticks();
+        let levels = match self.animation {
+            Animation::Static(levels) => levels,
+            Animation::FadeTo { levels, duration } => self.fade(levels, elapsed, duration),
+            Animation::Progress { done, total } => {
+                self.fade(progress_levels(done, total), elapsed, PROGRESS_FADE)
+            }
+            Animation::Breathe { leds, period } => {
+                // Triangle from 255 down to 0 and back to 255
+                let period = micros(period.ticks()).max(1);
+                let phase = (elapsed as u32 % period) / (period / 510).max(1);
+                let level = (255 - phase.min(510) as i32).unsigned_abs() as u8;
+                core::array::from_fn(|i| if leds & (1 << i) != 0 { level } else { 0 })
+            }
+            Animation::Chase { step } => {
+                let head = (elapsed as u32 / micros(step.ticks()).max(1)) as usize % LEDS;
+                core::array::from_fn(|i| {
+                    let behind = (head + LEDS - i) % LEDS;
+                    CHASE_TAIL.get(behind).copied().unwrap_or(0)
+                })
+            }
+        };
+
+        if levels != self.levels {
+            self.levels = levels;
+            self.leds.set_all_levels(levels.map(duty_cycle));
+        }
+    }
+
+    /// Levels of a fade from the levels at the start of the animation.
+    fn fade(&self, to: [u8; LEDS], elapsed: u64, duration: Duration) -> [u8; LEDS] {
+        let duration = duration.ticks();
+        if elapsed >= duration {
+            return to;
+        }
+        // Progress of the fade, in 1/256th
+        let (elapsed, duration) = (micros(elapsed), micros(duration));
+        let t = (elapsed / (duration / 256).max(1)).min(256) as i32;
+        core::array::from_fn(|i| {
+            let from = self.from[i] as i32;
+            (from + (to[i] as i32 - from) * t / 256) as u8
+        })
+    }
+}
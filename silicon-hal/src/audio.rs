@@ -4,6 +4,7 @@
 //!
 //! The AudioStreamer struct manages a ring buffer for audio samples and provides methods to write samples to the DAC.
 
+use crate::executor;
 use crate::pac;
 use crate::{dac::AudioDac, typesafe::Sealed};
 /// Marker trait for AudioStreamer states (Uninitialized and Initialized).
@@ -323,6 +324,17 @@ impl AudioStreamer<Stereo, Initialized> {
         }
         written
     }
+
+    /// Write all the pairs of left and right audio samples to the streamer (async).
+    ///
+    /// The task yields to the other tasks of the [`crate::executor`] while the buffer is full.
+    pub async fn write_all(&mut self, samples: &[(u8, u8)]) {
+        let mut written = self.write_samples(samples);
+        while written < samples.len() {
+            executor::yield_now().await;
+            written += self.write_samples(&samples[written..]);
+        }
+    }
 }
 
 impl AudioStreamer<Mono, Initialized> {
@@ -432,4 +444,15 @@ impl AudioStreamer<Mono, Initialized> {
         }
         written
     }
+
+    /// Write all the audio samples to the streamer (async).
+    ///
+    /// The task yields to the other tasks of the [`crate::executor`] while the buffer is full.
+    pub async fn write_all(&mut self, samples: &[u8]) {
+        let mut written = self.write_samples(samples);
+        while written < samples.len() {
+            executor::yield_now().await;
+            written += self.write_samples(&samples[written..]);
+        }
+    }
 }
@@ -0,0 +1,347 @@
+//! Crash module
+//! This module provides a persistent crash record, kept in RAM across the resets.
+//!
+//! The record lives in the `.uninit` section (see `memory.x`), which is neither loaded nor cleared
+//! at boot: the trap and panic handlers write it ([`record_fault`], [`record_panic`]) before
+//! stopping, and the application reads it back after the next reset ([`take`]). A magic word and
+//! a checksum tell a record from the garbage found in the RAM at power-up.
+//!
+//! The record holds the cause of the crash, the PC and trap value (faults), the location and the
+//! error ID (panics), the uptime and the last state of the application (see [`set_app_state`]).
+//!
+//! A reset by the [`watchdog`](crate::watchdog) without a record (the application was stuck, no
+//! handler ran) gives a record too, with the last state of the application only.
+
+use core::cell::{Cell, UnsafeCell};
+use core::fmt;
+use core::mem::MaybeUninit;
+
+use crate::fault::{Fault, FaultCause, Panic};
+use crate::text::TextBuf;
+use crate::timer::Timer;
+
+/// Maximum length of the file of a panic location, in bytes (the start is dropped if longer).
+pub const FILE_LEN: usize = 32;
+/// Maximum length of the name of an application state, in bytes.
+pub const APP_STATE_LEN: usize = 12;
+/// Maximum length of a record written on one line (see [`CrashRecord::write_line`]), in bytes.
+pub const LINE_LEN: usize = 128;
+
+/// Magic word of a valid record ("CRSH").
+const MAGIC: u32 = 0x4352_5348;
+
+/// Kind of crash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashKind {
+    /// CPU exception (trap)
+    Fault,
+    /// Panic of the application
+    Panic,
+    /// Reset by the watchdog (the application was stuck)
+    Watchdog,
+}
+
+/// A crash, as recorded by the trap or panic handler.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct CrashRecord {
+    kind: u8,
+    /// Exception code (fault) or error ID (panic, see [`Panic::id`])
+    pub code: u8,
+    file_len: u8,
+    app_state_len: u8,
+    /// Address of the faulting instruction (faults only)
+    pub pc: u32,
+    /// Trap value (faults only)
+    pub tval: u32,
+    /// Line of the panic (panics only)
+    pub line: u32,
+    /// Time since power-up, in milliseconds (0 for a watchdog reset)
+    pub uptime_ms: u32,
+    file: [u8; FILE_LEN],
+    app_state: [u8; APP_STATE_LEN],
+}
+
+impl CrashRecord {
+    /// Create a record with the uptime and the application state of now.
+    fn new(kind: CrashKind, code: u8) -> Self {
+        let uptime = Timer::new_timer0().get_counter().ticks();
+        Self::with_uptime(kind, code, micros_to_millis(uptime))
+    }
+
+    /// Create a record with the application state of now.
+    fn with_uptime(kind: CrashKind, code: u8, uptime_ms: u32) -> Self {
+        let mut record = Self {
+            kind: kind as u8,
+            code,
+            file_len: 0,
+            app_state_len: 0,
+            pc: 0,
+            tval: 0,
+            line: 0,
+            uptime_ms,
+            file: [0; FILE_LEN],
+            app_state: [0; APP_STATE_LEN],
+        };
+        let app_state = crate::interrupt::free(|| {
+            // Safety: The interrupts are disabled, nothing else accesses the name meanwhile. The
+            // name may hold any bytes (uninitialized RAM), its length is checked.
+            unsafe { APP_STATE.0.get().cast::<AppStateName>().read() }
+        });
+        let len = (app_state.len as usize).min(APP_STATE_LEN);
+        record.app_state[..len].copy_from_slice(&app_state.name[..len]);
+        record.app_state_len = len as u8;
+        record
+    }
+
+    /// Kind of crash.
+    pub fn kind(&self) -> CrashKind {
+        match self.kind {
+            1 => CrashKind::Panic,
+            2 => CrashKind::Watchdog,
+            _ => CrashKind::Fault,
+        }
+    }
+
+    /// File of the panic location (the end of the path, empty for a fault).
+    pub fn file(&self) -> &str {
+        core::str::from_utf8(&self.file[..self.file_len as usize]).unwrap_or("")
+    }
+
+    /// Last state of the application (see [`set_app_state`]).
+    pub fn app_state(&self) -> &str {
+        core::str::from_utf8(&self.app_state[..self.app_state_len as usize]).unwrap_or("")
+    }
+
+    /// Checksum of the record (a rotate-xor-add hash of its fields).
+    ///
+    /// No multiplication: the CPU has none, a software multiply per byte is slow and large.
+    fn checksum(&self) -> u32 {
+        let mut hash = mix(
+            0x811c_9dc5,
+            &[self.kind, self.code, self.file_len, self.app_state_len],
+        );
+        for word in [self.pc, self.tval, self.line, self.uptime_ms] {
+            hash = mix(hash, &word.to_le_bytes());
+        }
+        hash = mix(hash, &self.file);
+        mix(hash, &self.app_state)
+    }
+}
+
+/// Feed bytes to the checksum.
+#[inline(never)]
+fn mix(hash: u32, bytes: &[u8]) -> u32 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash.rotate_left(5) ^ byte as u32).wrapping_add(0x9e37_79b9)
+    })
+}
+
+/// Convert a time in microseconds to milliseconds (wrapping after 49 days).
+///
+/// A long division by 16-bit digits, so that only 32-bit divisions are needed (a 64-bit division
+/// pulls in a large software routine).
+fn micros_to_millis(micros: u64) -> u32 {
+    let mut millis = 0;
+    let mut rem = 0;
+    for shift in [48, 32, 16, 0] {
+        let digits = (rem << 16) | (micros >> shift) as u32 & 0xffff;
+        millis = (millis << 16) | digits / 1_000;
+        rem = digits % 1_000;
+    }
+    millis
+}
+
+impl CrashRecord {
+    /// Write the record on one line, e.g. `PANIC 3A at play.rs:42 state=playing uptime=1234ms`
+    /// (at most [`LINE_LEN`] bytes).
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The text to append the record to.
+    pub fn write_line(&self, line: &mut TextBuf) {
+        match self.kind() {
+            CrashKind::Fault => line
+                .push_str("TRAP ")
+                .push_str(FaultCause::from_code(self.code as usize).name())
+                .push_str(" (")
+                .push_dec(self.code as u32, 1)
+                .push_str(") pc=")
+                .push_hex(self.pc, 8)
+                .push_str(" tval=")
+                .push_hex(self.tval, 8),
+            CrashKind::Panic => line
+                .push_str("PANIC ")
+                .push_hex(self.code as u32, 2)
+                .push_str(" at ")
+                .push_str(self.file())
+                .push_str(":")
+                .push_dec(self.line, 1),
+            CrashKind::Watchdog => {
+                line.push_str("WATCHDOG state=").push_str(self.app_state());
+                return;
+            }
+        };
+        line.push_str(" state=")
+            .push_str(self.app_state())
+            .push_str(" uptime=")
+            .push_dec(self.uptime_ms, 1)
+            .push_str("ms");
+    }
+}
+
+impl fmt::Display for CrashRecord {
+    /// Write the record on one line (see [`CrashRecord::write_line`]).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0; LINE_LEN];
+        let mut line = TextBuf::new(&mut buf);
+        self.write_line(&mut line);
+        f.write_str(line.as_str())
+    }
+}
+
+/// Slot of the record in the `.uninit` section.
+#[repr(C)]
+struct Slot {
+    magic: u32,
+    record: CrashRecord,
+    checksum: u32,
+}
+
+/// Slot shared with the handlers, only accessed with the interrupts disabled.
+///
+/// The slot and the state name are copied with plain reads and writes, the critical sections
+/// keeping them in place: a volatile copy of a whole struct is done byte by byte (large code).
+struct Shared(UnsafeCell<MaybeUninit<Slot>>);
+
+// Safety: Single hart, the slot is only accessed in critical sections.
+unsafe impl Sync for Shared {}
+
+#[unsafe(link_section = ".uninit.crash")]
+static SLOT: Shared = Shared(UnsafeCell::new(MaybeUninit::uninit()));
+
+/// Name of the current application state.
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct AppStateName {
+    len: u8,
+    name: [u8; APP_STATE_LEN],
+}
+
+/// Name shared with the handlers, only accessed with the interrupts disabled.
+struct SharedName(UnsafeCell<MaybeUninit<AppStateName>>);
+
+// Safety: Single hart, the name is only accessed in critical sections.
+unsafe impl Sync for SharedName {}
+
+/// Name of the current application state, kept across the resets (for the watchdog resets).
+#[unsafe(link_section = ".uninit.crash")]
+static APP_STATE: SharedName = SharedName(UnsafeCell::new(MaybeUninit::uninit()));
+
+/// Record of the last crash, loaded at boot.
+struct Last(Cell<Option<CrashRecord>>);
+
+// Safety: Single hart, the record is only accessed in critical sections.
+unsafe impl Sync for Last {}
+
+static LAST: Last = Last(Cell::new(None));
+
+/// Set the name of the current application state, saved in the crash records.
+///
+/// # Arguments
+///
+/// * `name` - The name of the state (truncated to [`APP_STATE_LEN`] bytes), e.g. `menu`.
+pub fn set_app_state(name: &str) {
+    let mut state = AppStateName {
+        len: 0,
+        name: [0; APP_STATE_LEN],
+    };
+    let len = name.len().min(APP_STATE_LEN);
+    state.name[..len].copy_from_slice(&name.as_bytes()[..len]);
+    state.len = len as u8;
+    crate::interrupt::free(|| {
+        // Safety: The interrupts are disabled, nothing else accesses the name meanwhile
+        unsafe { APP_STATE.0.get().cast::<AppStateName>().write(state) };
+    });
+}
+
+/// Save a record, replacing the previous one.
+fn save(record: CrashRecord) {
+    crate::interrupt::free(|| {
+        let slot = Slot {
+            magic: MAGIC,
+            checksum: record.checksum(),
+            record,
+        };
+        // Safety: The interrupts are disabled, nothing else accesses the slot meanwhile
+        unsafe { SLOT.0.get().cast::<Slot>().write(slot) };
+    });
+}
+
+/// Record a CPU exception (from the trap handler).
+///
+/// # Arguments
+///
+/// * `fault` - The fault.
+pub fn record_fault(fault: &Fault) {
+    let mut record = CrashRecord::new(CrashKind::Fault, fault.cause.code());
+    record.pc = fault.pc;
+    record.tval = fault.tval;
+    save(record);
+}
+
+/// Record a panic (from the panic handler).
+///
+/// # Arguments
+///
+/// * `panic` - The panic.
+pub fn record_panic(panic: &Panic) {
+    let mut record = CrashRecord::new(CrashKind::Panic, panic.id());
+    // Keep the end of the path, the file name is more useful than the directories
+    let file = panic.file.as_bytes();
+    let mut start = file.len().saturating_sub(FILE_LEN);
+    while !panic.file.is_char_boundary(start) {
+        start += 1;
+    }
+    let file = &file[start..];
+    record.file[..file.len()].copy_from_slice(file);
+    record.file_len = file.len() as u8;
+    record.line = panic.line;
+    save(record);
+}
+
+/// Load the record of the last crash (from [`crate::init`], before the application sets its
+/// state), and clear it.
+pub(crate) fn load() {
+    crate::interrupt::free(|| {
+        let slot = SLOT.0.get().cast::<Slot>();
+        // Safety: The interrupts are disabled, nothing else accesses the slot meanwhile. The
+        // slot may hold any bytes (uninitialized RAM): the fields are plain integers and bytes,
+        // only trusted once the magic word and the checksum match.
+        let Slot {
+            magic,
+            record,
+            checksum,
+        } = unsafe { slot.read() };
+        // Safety: As above
+        unsafe { core::ptr::write_volatile(&raw mut (*slot).magic, 0) };
+        let valid = magic == MAGIC
+            && checksum == record.checksum()
+            && record.file_len as usize <= FILE_LEN
+            && record.app_state_len as usize <= APP_STATE_LEN;
+        let last = if valid {
+            Some(record)
+        } else if crate::watchdog::last_reset_was_watchdog() {
+            // The application was stuck, only its state is known
+            Some(CrashRecord::with_uptime(CrashKind::Watchdog, 0, 0))
+        } else {
+            None
+        };
+        LAST.0.set(last);
+    });
+}
+
+/// Take the record of the last crash, if any (loaded by [`crate::init`]).
+pub fn take() -> Option<CrashRecord> {
+    crate::interrupt::free(|| LAST.0.take())
+}
@@ -1,4 +1,5 @@
-use crate::timer::Timer;
+use crate::executor;
+use crate::timer::{Duration, Timer};
 
 pub use embedded_hal::delay::DelayNs;
 
@@ -77,6 +78,7 @@ impl DelayNs for IntrDelay {
 /// Delay peripheral based on TIMER0 (1MHz clock)
 ///
 /// TIMER0 is 64-bit clock which increments every 1 micros.
+/// It counts from power-up, the delays never return if it is disabled (see [`crate::timer::Timer0`]).
 #[derive(Clone, Copy)]
 pub struct Timer0Delay {
     _inner: (),
@@ -109,3 +111,21 @@ impl DelayNs for Timer0Delay {
         self.delay_us(ms * 1000);
     }
 }
+
+/// Async delays (for the [`crate::executor`]): the task sleeps instead of blocking the CPU.
+impl embedded_hal_async::delay::DelayNs for Timer0Delay {
+    #[inline(always)]
+    async fn delay_ns(&mut self, ns: u32) {
+        executor::sleep(Duration::micros(ns.div_ceil(1000) as u64)).await
+    }
+
+    #[inline(always)]
+    async fn delay_us(&mut self, us: u32) {
+        executor::sleep(Duration::micros(us as u64)).await
+    }
+
+    #[inline(always)]
+    async fn delay_ms(&mut self, ms: u32) {
+        executor::sleep(Duration::millis(ms as u64)).await
+    }
+}
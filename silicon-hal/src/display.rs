@@ -10,6 +10,10 @@ use embedded_hal::{
     spi::{Operation, SpiDevice},
 };
 
+#[cfg(feature = "graphics")]
+pub mod console;
+#[cfg(feature = "graphics")]
+pub mod font;
 pub mod spidisplay;
 
 use crate::{
@@ -20,7 +24,7 @@ use crate::{
             DisplayGpioConfig, DisplayOffCommand, DisplayOffsetCommand, DisplayOnCommand,
             FunctionSelectCommand, FunctionSelectParallelOption, MuxRatioCommand,
             Precharge2Command, PrechargeCommand, SetColumnCommand, SetGpioCommand, SetRemapCommand,
-            SetRowCommand, SetVslCommand, VcomhCommand, WriteRamCommand,
+            SetRowCommand, SetVslCommand, StartLineCommand, VcomhCommand, WriteRamCommand,
         },
     },
     typesafe::Sealed,
@@ -578,6 +582,21 @@ mod graphics {
         }
     }
 
+    impl<SPI, CS, DC, RST, DELAYER, PINERR, SPIERR> super::console::VerticalScroll
+        for DisplayPeripheral<SPI, CS, DC, RST, DELAYER, PINERR, SPIERR, Initialized>
+    where
+        SPI: SpiDevice<u8, Error = SPIERR>,
+        CS: OutputPin<Error = PINERR>,
+        DC: OutputPin<Error = PINERR>,
+        RST: OutputPin<Error = PINERR>,
+        DELAYER: crate::delay::DelayNs + Clone,
+    {
+        // The display start line of the SSD1351 (the display RAM has 128 rows, and wraps around)
+        fn set_scroll_offset(&mut self, row: u8) -> Result<(), Self::Error> {
+            self.send_command(&StartLineCommand::new(row))
+        }
+    }
+
     impl<SPI, CS, DC, RST, DELAYER, PINERR, SPIERR> OriginDimensions
         for DisplayPeripheral<SPI, CS, DC, RST, DELAYER, PINERR, SPIERR, Initialized>
     where
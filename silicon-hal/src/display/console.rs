@@ -0,0 +1,500 @@
+//! Text console
+//!
+//! A text console over any draw target with RGB565 colors (e.g. the OLED display), for the
+//! diagnostics screens, the logs and the debug overlays. It draws with the tiny font of the HAL
+//! ([`font`](super::font)) on a character grid, and implements `core::fmt::Write`:
+//!
+//! ```ignore
+//! let mut console = Console::with_hardware_scroll(&mut display, 1)?;
+//! writeln!(console, "\x1b[32mSD card ok\x1b[0m: {} MB", size_mb)?;
+//! ```
+//!
+//! The control characters are `\n` (new line), `\r` (carriage return), `\t` (next multiple of
+//! 4 columns) and `\x08` (backspace). The ANSI escape sequences are a subset of the VT100 ones:
+//! - `ESC[...m`: reset (0), bright (1), normal (22), foreground (30-37, 90-97, default 39) and
+//!   background (40-47, 100-107, default 49) colors,
+//! - `ESC[row;colH`: move the cursor (1-based, top-left corner without parameters),
+//! - `ESC[nA`, `ESC[nB`, `ESC[nC`, `ESC[nD`: move the cursor up, down, right, left,
+//! - `ESC[2J`: clear the screen, `ESC[K`: clear to the end of the line.
+//!
+//! A character written at the last column wraps to the next line only when the next character
+//! is written (as on a VT100). A new line on the last line scrolls the console: with a hardware
+//! vertical scroll ([`VerticalScroll`], e.g. the SSD1351 start line), the lines move up and only
+//! the new line is cleared, otherwise the screen is cleared and the console goes on at the top.
+
+use core::fmt;
+
+use embedded_graphics_core::{
+    pixelcolor::{Rgb565, RgbColor},
+    prelude::{DrawTarget, Point, Size},
+    primitives::Rectangle,
+};
+
+use super::font;
+
+/// Height of a line, in dots (the glyph and 3 dots of spacing).
+pub const LINE_HEIGHT: u8 = 8;
+
+/// Number of columns of a tab stop.
+const TAB: u8 = 4;
+
+/// Maximum number of parameters of an escape sequence.
+const MAX_PARAMS: usize = 4;
+
+/// Colors of the ANSI palette: black, red, green, yellow, blue, magenta, cyan and white,
+/// then their bright variants.
+const PALETTE: [Rgb565; 16] = [
+    Rgb565::new(0, 0, 0),
+    Rgb565::new(21, 0, 0),
+    Rgb565::new(0, 42, 0),
+    Rgb565::new(21, 42, 0),
+    Rgb565::new(0, 0, 21),
+    Rgb565::new(21, 0, 21),
+    Rgb565::new(0, 42, 21),
+    Rgb565::new(21, 42, 21),
+    Rgb565::new(10, 21, 10),
+    Rgb565::new(31, 10, 10),
+    Rgb565::new(10, 63, 10),
+    Rgb565::new(31, 63, 10),
+    Rgb565::new(10, 21, 31),
+    Rgb565::new(31, 10, 31),
+    Rgb565::new(10, 63, 31),
+    Rgb565::new(31, 63, 31),
+];
+
+/// Draw target with a hardware vertical scroll.
+///
+/// The memory of the display must have the height of the draw target, and wrap around (as the
+/// display RAM of the SSD1351).
+pub trait VerticalScroll: DrawTarget {
+    /// Show a row of the display memory at the top of the screen (the rows above are shown at
+    /// the bottom).
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row of the display memory.
+    fn set_scroll_offset(&mut self, row: u8) -> Result<(), Self::Error>;
+}
+
+/// Hardware scroll of the draw target.
+type ScrollFn<D> = fn(&mut D, u8) -> Result<(), <D as DrawTarget>::Error>;
+
+/// Color of the text (foreground or background).
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Color {
+    /// The default color of the console
+    Default,
+    /// A color of the ANSI palette (0-7, brightened by the bright attribute, or 8-15)
+    Ansi(u8),
+    /// Any color
+    Rgb(Rgb565),
+}
+
+/// State of the escape sequence parser.
+#[derive(Clone, Copy)]
+enum Escape {
+    /// No escape sequence
+    None,
+    /// `ESC` received
+    Esc,
+    /// `ESC[` received, with the parameters so far
+    Csi {
+        params: [u16; MAX_PARAMS],
+        count: u8,
+    },
+}
+
+/// Text console over a draw target.
+pub struct Console<'a, D>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    target: &'a mut D,
+    scroll: Option<ScrollFn<D>>,
+    origin: Point,
+    scale: u8,
+    columns: u8,
+    rows: u8,
+    /// Cursor
+    col: u8,
+    row: u8,
+    /// Whether the next character wraps to the next line (the cursor is past the last column)
+    wrap_pending: bool,
+    /// Line of the grid shown at the top of the screen (hardware scroll)
+    top: u8,
+    /// Colors
+    default_fg: Rgb565,
+    default_bg: Rgb565,
+    fg: Color,
+    bg: Color,
+    bright: bool,
+    escape: Escape,
+}
+
+impl<'a, D> Console<'a, D>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    /// Create a new console over the whole draw target, the cursor at the top-left corner.
+    ///
+    /// The draw target is not cleared. A new line on the last line clears the screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The draw target.
+    /// * `scale` - The scale of the font, up to 16 (1: characters of 4x8 pixels, a grid of 32x16
+    ///   on the OLED display).
+    pub fn new(target: &'a mut D, scale: u8) -> Self {
+        let scale = scale.clamp(1, 16);
+        let area = target.bounding_box();
+        let columns = area.size.width / (font::CELL_WIDTH * scale) as u32;
+        let rows = area.size.height / (LINE_HEIGHT * scale) as u32;
+        Self {
+            target,
+            scroll: None,
+            origin: area.top_left,
+            scale,
+            columns: columns.clamp(1, 255) as u8,
+            rows: rows.clamp(1, 255) as u8,
+            col: 0,
+            row: 0,
+            wrap_pending: false,
+            top: 0,
+            default_fg: Rgb565::WHITE,
+            default_bg: Rgb565::BLACK,
+            fg: Color::Default,
+            bg: Color::Default,
+            bright: false,
+            escape: Escape::None,
+        }
+    }
+
+    /// Get the number of columns of the grid.
+    #[inline(always)]
+    pub fn columns(&self) -> u8 {
+        self.columns
+    }
+
+    /// Get the number of rows of the grid.
+    #[inline(always)]
+    pub fn rows(&self) -> u8 {
+        self.rows
+    }
+
+    /// Get the position of the cursor (column, row).
+    #[inline(always)]
+    pub fn cursor(&self) -> (u8, u8) {
+        (self.col, self.row)
+    }
+
+    /// Move the cursor (clamped to the grid).
+    ///
+    /// # Arguments
+    ///
+    /// * `col` - The column.
+    /// * `row` - The row.
+    pub fn set_cursor(&mut self, col: u8, row: u8) {
+        self.col = col.min(self.columns - 1);
+        self.row = row.min(self.rows - 1);
+        self.wrap_pending = false;
+    }
+
+    /// Set the default colors (the colors after a reset, `ESC[0m`).
+    ///
+    /// # Arguments
+    ///
+    /// * `fg` - The default color of the text.
+    /// * `bg` - The default color of the background.
+    pub fn set_default_colors(&mut self, fg: Rgb565, bg: Rgb565) {
+        self.default_fg = fg;
+        self.default_bg = bg;
+    }
+
+    /// Set the colors of the next characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `fg` - The color of the text.
+    /// * `bg` - The color of the background.
+    pub fn set_colors(&mut self, fg: Rgb565, bg: Rgb565) {
+        self.fg = Color::Rgb(fg);
+        self.bg = Color::Rgb(bg);
+    }
+
+    /// Get back to the default colors, without the bright attribute.
+    pub fn reset_colors(&mut self) {
+        self.fg = Color::Default;
+        self.bg = Color::Default;
+        self.bright = false;
+    }
+
+    /// Clear the screen with the background color, and move the cursor to the top-left corner.
+    pub fn clear(&mut self) -> Result<(), D::Error> {
+        if let Some(scroll) = self.scroll
+            && self.top != 0
+        {
+            self.top = 0;
+            scroll(self.target, 0)?;
+        }
+        let area = self.target.bounding_box();
+        let bg = self.bg_color();
+        self.target.fill_solid(&area, bg)?;
+        self.set_cursor(0, 0);
+        Ok(())
+    }
+
+    /// Write bytes (text, control characters and escape sequences).
+    ///
+    /// The non-ASCII characters are drawn as `?`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), D::Error> {
+        for &c in bytes {
+            self.write_byte(c)?;
+        }
+        Ok(())
+    }
+
+    /// Write a byte.
+    fn write_byte(&mut self, c: u8) -> Result<(), D::Error> {
+        match self.escape {
+            Escape::None => {}
+            Escape::Esc => {
+                self.escape = if c == b'[' {
+                    Escape::Csi {
+                        params: [0; MAX_PARAMS],
+                        count: 0,
+                    }
+                } else {
+                    Escape::None
+                };
+                return Ok(());
+            }
+            Escape::Csi {
+                mut params,
+                mut count,
+            } => {
+                match c {
+                    b'0'..=b'9' => {
+                        count = count.max(1);
+                        let i = count as usize - 1;
+                        params[i] = params[i]
+                            .saturating_mul(10)
+                            .saturating_add((c - b'0') as u16);
+                        self.escape = Escape::Csi { params, count };
+                    }
+                    b';' => {
+                        // An empty parameter is a 0
+                        count = (count.max(1) + 1).min(MAX_PARAMS as u8);
+                        self.escape = Escape::Csi { params, count };
+                    }
+                    0x40..=0x7e => {
+                        self.escape = Escape::None;
+                        self.run_escape(c, &params[..count as usize])?;
+                    }
+                    // Intermediate bytes and unsupported sequences
+                    _ => {}
+                }
+                return Ok(());
+            }
+        }
+
+        match c {
+            0x1b => self.escape = Escape::Esc,
+            b'\n' => self.new_line()?,
+            b'\r' => {
+                self.col = 0;
+                self.wrap_pending = false;
+            }
+            b'\t' => {
+                let col = (self.col / TAB + 1) * TAB;
+                self.col = col.min(self.columns - 1);
+            }
+            0x08 => {
+                self.col = self.col.saturating_sub(1);
+                self.wrap_pending = false;
+            }
+            // UTF-8 continuation bytes (the first byte of a character is drawn as `?`)
+            0x80..=0xbf => {}
+            0x20..=0x7e | 0xc0..=0xff => {
+                let c = if c.is_ascii() { c } else { b'?' };
+                if self.wrap_pending {
+                    self.new_line()?;
+                }
+                self.draw_char(c)?;
+                if self.col + 1 < self.columns {
+                    self.col += 1;
+                } else {
+                    self.wrap_pending = true;
+                }
+            }
+            // Other control characters
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Run an escape sequence (`ESC[`, its parameters and its final byte).
+    fn run_escape(&mut self, command: u8, params: &[u16]) -> Result<(), D::Error> {
+        let param = |i: usize| params.get(i).copied().unwrap_or(0);
+        // Cursor moves by at least one
+        let count = param(0).clamp(1, 255) as u8;
+        match command {
+            b'm' => {
+                if params.is_empty() {
+                    self.reset_colors();
+                }
+                for &p in params {
+                    self.set_attribute(p);
+                }
+            }
+            b'H' | b'f' => {
+                let row = param(0).clamp(1, 256) - 1;
+                let col = param(1).clamp(1, 256) - 1;
+                self.set_cursor(col as u8, row as u8);
+            }
+            b'A' => self.set_cursor(self.col, self.row.saturating_sub(count)),
+            b'B' => self.set_cursor(self.col, self.row.saturating_add(count)),
+            b'C' => self.set_cursor(self.col.saturating_add(count), self.row),
+            b'D' => self.set_cursor(self.col.saturating_sub(count), self.row),
+            b'J' if param(0) == 2 => self.clear()?,
+            b'K' => {
+                let (col, row) = (self.col, self.row);
+                self.clear_cells(col, row, self.columns - col)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Apply a graphic attribute (`ESC[...m` parameter).
+    fn set_attribute(&mut self, attribute: u16) {
+        match attribute {
+            0 => self.reset_colors(),
+            1 => self.bright = true,
+            22 => self.bright = false,
+            30..=37 => self.fg = Color::Ansi((attribute - 30) as u8),
+            39 => self.fg = Color::Default,
+            40..=47 => self.bg = Color::Ansi((attribute - 40) as u8),
+            49 => self.bg = Color::Default,
+            90..=97 => self.fg = Color::Ansi((attribute - 90) as u8 + 8),
+            100..=107 => self.bg = Color::Ansi((attribute - 100) as u8 + 8),
+            _ => {}
+        }
+    }
+
+    /// Get the color of the text.
+    fn fg_color(&self) -> Rgb565 {
+        match self.fg {
+            Color::Default => self.default_fg,
+            Color::Ansi(i) if self.bright && i < 8 => PALETTE[i as usize + 8],
+            Color::Ansi(i) => PALETTE[i as usize],
+            Color::Rgb(color) => color,
+        }
+    }
+
+    /// Get the color of the background.
+    fn bg_color(&self) -> Rgb565 {
+        match self.bg {
+            Color::Default => self.default_bg,
+            Color::Ansi(i) => PALETTE[i as usize],
+            Color::Rgb(color) => color,
+        }
+    }
+
+    /// Get the top-left corner of a cell of the grid.
+    fn cell_origin(&self, col: u8, row: u8) -> Point {
+        // With the hardware scroll, the rows of the screen are rotated in the display memory
+        let line = (self.top as u32 + row as u32) % self.rows as u32;
+        let x = col as u32 * (font::CELL_WIDTH * self.scale) as u32;
+        let y = line * (LINE_HEIGHT * self.scale) as u32;
+        self.origin + Point::new(x as i32, y as i32)
+    }
+
+    /// Draw a character at the cursor.
+    fn draw_char(&mut self, c: u8) -> Result<(), D::Error> {
+        let origin = self.cell_origin(self.col, self.row);
+        let (fg, bg) = (self.fg_color(), self.bg_color());
+        font::draw_cell(self.target, origin, self.scale, LINE_HEIGHT, c, fg, bg)
+    }
+
+    /// Clear cells of a row with the background color.
+    fn clear_cells(&mut self, col: u8, row: u8, count: u8) -> Result<(), D::Error> {
+        let size = Size::new(
+            count as u32 * (font::CELL_WIDTH * self.scale) as u32,
+            (LINE_HEIGHT * self.scale) as u32,
+        );
+        let area = Rectangle::new(self.cell_origin(col, row), size);
+        let bg = self.bg_color();
+        self.target.fill_solid(&area, bg)
+    }
+
+    /// Move the cursor to the start of the next line, scrolling if needed.
+    fn new_line(&mut self) -> Result<(), D::Error> {
+        self.col = 0;
+        self.wrap_pending = false;
+        if self.row + 1 < self.rows {
+            self.row += 1;
+            return Ok(());
+        }
+        match self.scroll {
+            Some(scroll) => {
+                // The top line becomes the (cleared) bottom line
+                self.top = (self.top + 1) % self.rows;
+                let offset = self.top as u32 * (LINE_HEIGHT * self.scale) as u32;
+                scroll(self.target, offset as u8)?;
+                self.clear_cells(0, self.rows - 1, self.columns)
+            }
+            None => self.clear(),
+        }
+    }
+}
+
+impl<'a, D> Console<'a, D>
+where
+    D: VerticalScroll<Color = Rgb565>,
+{
+    /// Create a new console over the whole draw target, scrolling with the hardware vertical
+    /// scroll of the target (the scroll is reset when the console is dropped).
+    ///
+    /// The scale must be a power of two (the lines must fill the display memory), else the
+    /// console clears the screen on a new line on the last line, as a [`Console::new`].
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The draw target.
+    /// * `scale` - The scale of the font.
+    pub fn with_hardware_scroll(target: &'a mut D, scale: u8) -> Result<Self, D::Error> {
+        target.set_scroll_offset(0)?;
+        let height = target.bounding_box().size.height;
+        let mut console = Self::new(target, scale);
+        if console.rows as u32 * (LINE_HEIGHT * console.scale) as u32 == height {
+            console.scroll = Some(D::set_scroll_offset);
+        }
+        Ok(console)
+    }
+}
+
+impl<D> Drop for Console<'_, D>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    fn drop(&mut self) {
+        if let Some(scroll) = self.scroll
+            && self.top != 0
+        {
+            let _ = scroll(self.target, 0);
+        }
+    }
+}
+
+impl<D> fmt::Write for Console<'_, D>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
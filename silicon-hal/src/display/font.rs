@@ -0,0 +1,138 @@
+//! Tiny 3x5 font
+//!
+//! A minimal font for the text drawn by the HAL itself (fault reports, log console), as no
+//! font is available without `embedded-graphics`. Only the upper case letters have a glyph,
+//! the lower case letters are drawn in upper case.
+
+use embedded_graphics_core::{
+    pixelcolor::Rgb565,
+    prelude::{DrawTarget, Point, Size},
+    primitives::Rectangle,
+};
+
+/// Width of a glyph, in dots.
+pub const GLYPH_WIDTH: u8 = 3;
+/// Height of a glyph, in dots.
+pub const GLYPH_HEIGHT: u8 = 5;
+/// Width of a character cell (glyph and spacing), in dots.
+pub const CELL_WIDTH: u8 = 4;
+/// Height of a character cell (glyph and spacing), in dots.
+pub const CELL_HEIGHT: u8 = 7;
+
+/// Get the 3x5 glyph of a character (5 rows of 3 dots, top row in the upper bits).
+///
+/// The characters without a glyph are blank.
+pub fn glyph(c: u8) -> u16 {
+    match c.to_ascii_uppercase() {
+        b'0' => 0b111_101_101_101_111,
+        b'1' => 0b010_110_010_010_111,
+        b'2' => 0b110_001_010_100_111,
+        b'3' => 0b110_001_010_001_110,
+        b'4' => 0b101_101_111_001_001,
+        b'5' => 0b111_100_110_001_110,
+        b'6' => 0b011_100_111_101_111,
+        b'7' => 0b111_001_010_010_010,
+        b'8' => 0b111_101_111_101_111,
+        b'9' => 0b111_101_111_001_110,
+        b'A' => 0b010_101_111_101_101,
+        b'B' => 0b110_101_110_101_110,
+        b'C' => 0b011_100_100_100_011,
+        b'D' => 0b110_101_101_101_110,
+        b'E' => 0b111_100_110_100_111,
+        b'F' => 0b111_100_110_100_100,
+        b'G' => 0b011_100_101_101_011,
+        b'H' => 0b101_101_111_101_101,
+        b'I' => 0b111_010_010_010_111,
+        b'J' => 0b001_001_001_101_010,
+        b'K' => 0b101_101_110_101_101,
+        b'L' => 0b100_100_100_100_111,
+        b'M' => 0b101_111_111_101_101,
+        b'N' => 0b110_101_101_101_101,
+        b'O' => 0b010_101_101_101_010,
+        b'P' => 0b110_101_110_100_100,
+        b'Q' => 0b010_101_101_110_011,
+        b'R' => 0b110_101_110_101_101,
+        b'S' => 0b011_100_010_001_110,
+        b'T' => 0b111_010_010_010_010,
+        b'U' => 0b101_101_101_101_111,
+        b'V' => 0b101_101_101_101_010,
+        b'W' => 0b101_101_111_111_101,
+        b'X' => 0b101_101_010_101_101,
+        b'Y' => 0b101_101_010_010_010,
+        b'Z' => 0b111_001_010_100_111,
+        b':' => 0b000_010_000_010_000,
+        b'-' => 0b000_000_111_000_000,
+        b'.' => 0b000_000_000_000_010,
+        b',' => 0b000_000_000_010_100,
+        b'/' => 0b001_001_010_100_100,
+        b'_' => 0b000_000_000_000_111,
+        b'(' => 0b010_100_100_100_010,
+        b')' => 0b010_001_001_001_010,
+        b'[' => 0b110_100_100_100_110,
+        b']' => 0b011_001_001_001_011,
+        b'!' => 0b010_010_010_000_010,
+        b'?' => 0b110_001_010_000_010,
+        b'=' => 0b000_111_000_111_000,
+        b'+' => 0b000_010_111_010_000,
+        b'\'' => 0b010_010_000_000_000,
+        b'"' => 0b101_101_000_000_000,
+        b'<' => 0b001_010_100_010_001,
+        b'>' => 0b100_010_001_010_100,
+        b'%' => 0b101_001_010_100_101,
+        b'#' => 0b101_111_101_111_101,
+        b'*' => 0b000_101_010_101_000,
+        _ => 0,
+    }
+}
+
+/// Check if a dot of a glyph is set.
+///
+/// # Arguments
+///
+/// * `glyph` - The glyph (see [`glyph`]).
+/// * `col` - The column of the dot (0 to 2).
+/// * `row` - The row of the dot (0 to 4).
+#[inline(always)]
+pub fn is_dot_set(glyph: u16, col: u8, row: u8) -> bool {
+    col < GLYPH_WIDTH && row < GLYPH_HEIGHT && glyph & (1 << (14 - (row * 3 + col))) != 0
+}
+
+/// Draw a character cell (the glyph and its spacing) at once.
+///
+/// # Arguments
+///
+/// * `target` - The draw target.
+/// * `top_left` - The top-left corner of the cell.
+/// * `scale` - The scale of the font (one dot is a square of `scale` x `scale` pixels).
+/// * `height` - The height of the cell, in dots (e.g. [`CELL_HEIGHT`]).
+/// * `c` - The character.
+/// * `color` - The color of the glyph.
+/// * `background` - The color of the rest of the cell.
+pub fn draw_cell<D>(
+    target: &mut D,
+    top_left: Point,
+    scale: u8,
+    height: u8,
+    c: u8,
+    color: Rgb565,
+    background: Rgb565,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let scale = scale.max(1);
+    let glyph = glyph(c);
+    let (width, height) = (CELL_WIDTH * scale, height * scale);
+    let area = Rectangle::new(top_left, Size::new(width as u32, height as u32));
+    // The glyph is at the top-left of the cell (1 dot of spacing on the right, the rest below)
+    let colors = (0..height).flat_map(|y| {
+        (0..width).map(move |x| {
+            if is_dot_set(glyph, x / scale, y / scale) {
+                color
+            } else {
+                background
+            }
+        })
+    });
+    target.fill_contiguous(&area, colors)
+}
@@ -0,0 +1,213 @@
+//! Executor module
+//! This module provides a tiny cooperative async executor, for the single hart of the SoC.
+//!
+//! The tasks are futures pinned by the caller (no allocation), they are polled by
+//! [`Executor::run`] until they all complete:
+//!
+//! ```ignore
+//! let mut executor = Executor::new(timer0.take_alarm0().unwrap());
+//! let streaming = pin!(async { /* ... */ });
+//! let ui = pin!(async { /* ... */ });
+//! executor.run(&mut [streaming, ui]);
+//! ```
+//!
+//! The wakers are polling wakers: a future waiting for a peripheral (e.g. a free slot in the
+//! Audio Streamer FIFO, the end of a SPI transfer, a button press) wakes its task again right
+//! away, so that it is polled again after the other ready tasks.
+//!
+//! The futures waiting for an instant ([`sleep`], [`sleep_until`], the async
+//! [`Timer0Delay`](crate::delay::Timer0Delay)) do not wake their task: the executor is driven by
+//! Timer0, it wakes the tasks once the earliest deadline is reached. When all the tasks are
+//! sleeping, the executor waits for this deadline with its [`Alarm`].
+//!
+//! Note: Only one executor can run at a time, and the futures of this module (and the async
+//! implementations of the HAL) are only meant to be polled by it.
+
+use core::{
+    cell::Cell,
+    future::{Future, poll_fn},
+    pin::Pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+use crate::timer::{Alarm, Duration, Instant, Timer};
+
+/// Maximum number of tasks of the executor.
+pub const MAX_TASKS: usize = 32;
+
+/// Shared state of the executor, only accessed with the interrupts disabled.
+struct State {
+    /// Tasks to poll (bit n for the task n)
+    ready: Cell<u32>,
+    /// Earliest instant a task is sleeping until
+    deadline: Cell<Option<Instant>>,
+}
+
+// Safety: Single hart, the state is only accessed in critical sections.
+unsafe impl Sync for State {}
+
+static STATE: State = State {
+    ready: Cell::new(0),
+    deadline: Cell::new(None),
+};
+
+static VTABLE: RawWakerVTable =
+    RawWakerVTable::new(waker_clone, waker_wake, waker_wake, waker_drop);
+
+unsafe fn waker_clone(data: *const ()) -> RawWaker {
+    RawWaker::new(data, &VTABLE)
+}
+
+unsafe fn waker_wake(data: *const ()) {
+    // The data of the waker is the index of its task
+    let mask = 1u32 << (data as usize);
+    crate::interrupt::free(|| STATE.ready.set(STATE.ready.get() | mask));
+}
+
+unsafe fn waker_drop(_data: *const ()) {}
+
+/// Get the waker of a task.
+fn task_waker(task: usize) -> Waker {
+    // Safety: The vtable functions only use the data as a task index.
+    unsafe { Waker::from_raw(RawWaker::new(task as *const (), &VTABLE)) }
+}
+
+/// Cooperative executor, driven by Timer0.
+pub struct Executor {
+    alarm: Alarm,
+}
+
+impl Executor {
+    /// Create a new executor.
+    ///
+    /// # Arguments
+    ///
+    /// * `alarm` - The alarm of Timer0 used to wait for the sleeping tasks.
+    pub fn new(alarm: Alarm) -> Self {
+        Self { alarm }
+    }
+
+    /// Stop the executor and get the alarm back.
+    pub fn into_alarm(mut self) -> Alarm {
+        self.alarm.cancel();
+        self.alarm
+    }
+
+    /// Run tasks until they all complete.
+    ///
+    /// The tasks are polled in order, each time they are woken. Timer0 must be enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The tasks to run (at most [`MAX_TASKS`]).
+    pub fn run(&mut self, tasks: &mut [Pin<&mut dyn Future<Output = ()>>]) {
+        assert!(tasks.len() <= MAX_TASKS);
+        let all = (1u64 << tasks.len()).wrapping_sub(1) as u32;
+        let timer = Timer::new_timer0();
+        let mut done = 0u32;
+
+        // All the tasks are polled once at start
+        crate::interrupt::free(|| {
+            STATE.ready.set(all);
+            STATE.deadline.set(None);
+        });
+
+        while done != all {
+            let now = timer.get_counter();
+            let ready = crate::interrupt::free(|| {
+                // Wake all the tasks once the earliest deadline is reached
+                // (the futures are polled again until they are ready, and register their deadline again)
+                if STATE.deadline.get().is_some_and(|deadline| deadline <= now) {
+                    STATE.deadline.set(None);
+                    STATE.ready.set(all);
+                }
+                STATE.ready.replace(0)
+            }) & !done;
+
+            if ready == 0 {
+                // Nothing to do until the earliest deadline (if any)
+                if let Some(deadline) = crate::interrupt::free(|| STATE.deadline.get()) {
+                    self.alarm.schedule_at(deadline);
+                    self.alarm.wait();
+                }
+                continue;
+            }
+
+            for (n, task) in tasks.iter_mut().enumerate() {
+                if ready & (1 << n) == 0 {
+                    continue;
+                }
+                let waker = task_waker(n);
+                let mut cx = Context::from_waker(&waker);
+                if task.as_mut().poll(&mut cx).is_ready() {
+                    done |= 1 << n;
+                }
+            }
+        }
+    }
+}
+
+/// Yield to the other tasks: the task is polled again after the other ready tasks.
+pub async fn yield_now() {
+    let mut yielded = false;
+    poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+/// Wait until a condition is true, yielding to the other tasks while it is false.
+///
+/// # Arguments
+///
+/// * `condition` - The condition, checked each time the task is polled.
+pub async fn poll_until(mut condition: impl FnMut() -> bool) {
+    poll_fn(|cx| {
+        if condition() {
+            return Poll::Ready(());
+        }
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+/// Sleep until an instant of Timer0.
+///
+/// # Arguments
+///
+/// * `deadline` - The instant to wake up at.
+pub async fn sleep_until(deadline: Instant) {
+    let timer = Timer::new_timer0();
+    poll_fn(|_cx| {
+        if timer.get_counter() >= deadline {
+            return Poll::Ready(());
+        }
+        // Register the deadline, the executor wakes the task once it is reached
+        crate::interrupt::free(|| {
+            if STATE
+                .deadline
+                .get()
+                .is_none_or(|earliest| deadline < earliest)
+            {
+                STATE.deadline.set(Some(deadline));
+            }
+        });
+        Poll::Pending
+    })
+    .await
+}
+
+/// Sleep for a given duration.
+///
+/// # Arguments
+///
+/// * `duration` - The duration to sleep for.
+pub async fn sleep(duration: Duration) {
+    sleep_until(Timer::new_timer0().get_counter() + duration).await
+}
@@ -0,0 +1,483 @@
+//! Fault module
+//! This module provides the decoding and the reporting of the CPU exceptions (traps).
+//!
+//! The Ice-V core raises an exception on an illegal instruction, a misaligned jump or access,
+//! an `ecall`/`ebreak`, and an access to an unmapped peripheral address. The trap handler then
+//! finds the cause in `mcause`, the faulting instruction in `mepc` and the faulting address (or
+//! instruction) in `mtval`.
+//!
+//! With the `rt` feature, the HAL installs the default exception handler of `riscv-rt`: it stops
+//! the application and reports the fault ([`Fault::report`]):
+//! - on the OLED display (with the `graphics` feature): the cause, the PC and the trap value,
+//! - on the LEDs: a blink code, repeated forever (see [`Fault::blink_count`]).
+//!
+//! The fault is also saved in the persistent crash record (see [`crate::crash`]).
+//!
+//! An application can still provide its own `ExceptionHandler`, and call [`Fault::report`] itself.
+//!
+//! The panics of an application can be reported the same way from its panic handler
+//! ([`Panic::report`]): a crash screen with the location and the message of the panic, and a
+//! blink code of a short error ID on the LEDs (see [`Panic::id`]). The location is only known if
+//! the firmware is built with `-Z location-detail=file,line`.
+
+use core::fmt::{self, Write as _};
+
+use embedded_hal::delay::DelayNs;
+
+use crate::delay::INTR_DELAY;
+
+/// Cause of a CPU exception (`mcause` exception codes of the Ice-V core).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultCause {
+    /// Jump or branch to a misaligned target (code 0)
+    InstructionMisaligned,
+    /// Illegal instruction (code 2)
+    IllegalInstruction,
+    /// `ebreak` instruction (code 3)
+    Breakpoint,
+    /// Misaligned load (code 4)
+    LoadMisaligned,
+    /// Load from an unmapped peripheral address (code 5)
+    LoadFault,
+    /// Misaligned store (code 6)
+    StoreMisaligned,
+    /// Store to an unmapped peripheral address (code 7)
+    StoreFault,
+    /// `ecall` instruction (code 11)
+    EnvironmentCall,
+    /// Any other exception code
+    Unknown(u8),
+}
+
+impl FaultCause {
+    /// Decode an `mcause` exception code.
+    pub fn from_code(code: usize) -> Self {
+        match code {
+            0 => FaultCause::InstructionMisaligned,
+            2 => FaultCause::IllegalInstruction,
+            3 => FaultCause::Breakpoint,
+            4 => FaultCause::LoadMisaligned,
+            5 => FaultCause::LoadFault,
+            6 => FaultCause::StoreMisaligned,
+            7 => FaultCause::StoreFault,
+            11 => FaultCause::EnvironmentCall,
+            code => FaultCause::Unknown(code as u8),
+        }
+    }
+
+    /// Get the `mcause` exception code.
+    pub fn code(&self) -> u8 {
+        match self {
+            FaultCause::InstructionMisaligned => 0,
+            FaultCause::IllegalInstruction => 2,
+            FaultCause::Breakpoint => 3,
+            FaultCause::LoadMisaligned => 4,
+            FaultCause::LoadFault => 5,
+            FaultCause::StoreMisaligned => 6,
+            FaultCause::StoreFault => 7,
+            FaultCause::EnvironmentCall => 11,
+            FaultCause::Unknown(code) => *code,
+        }
+    }
+
+    /// Get a short name of the cause (upper case, at most 16 characters).
+    pub fn name(&self) -> &'static str {
+        match self {
+            FaultCause::InstructionMisaligned => "JUMP MISALIGNED",
+            FaultCause::IllegalInstruction => "ILLEGAL INSTR",
+            FaultCause::Breakpoint => "BREAKPOINT",
+            FaultCause::LoadMisaligned => "LOAD MISALIGNED",
+            FaultCause::LoadFault => "LOAD FAULT",
+            FaultCause::StoreMisaligned => "STORE MISALIGNED",
+            FaultCause::StoreFault => "STORE FAULT",
+            FaultCause::EnvironmentCall => "ECALL",
+            FaultCause::Unknown(_) => "UNKNOWN",
+        }
+    }
+}
+
+/// A CPU exception, as seen by the trap handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fault {
+    /// Cause of the exception
+    pub cause: FaultCause,
+    /// Address of the faulting instruction (`mepc`)
+    pub pc: u32,
+    /// Trap value (`mtval`): the faulting address (jump target, load/store address),
+    /// the faulting instruction (illegal instruction) or 0
+    pub tval: u32,
+}
+
+impl Fault {
+    /// Read the fault from the CSRs of the CPU.
+    ///
+    /// Only meaningful inside the exception handler.
+    pub fn read() -> Self {
+        Self {
+            cause: FaultCause::from_code(riscv::register::mcause::read().code()),
+            pc: riscv::register::mepc::read() as u32,
+            tval: riscv::register::mtval::read() as u32,
+        }
+    }
+
+    /// Number of blinks of the LED blink code: the exception code plus one
+    /// (so that an instruction misaligned fault still blinks once).
+    #[inline(always)]
+    pub fn blink_count(&self) -> u32 {
+        self.cause.code() as u32 + 1
+    }
+
+    /// Report the fault on the OLED display (with the `graphics` feature) and on the LEDs.
+    ///
+    /// This function never returns: the LED blink code is repeated forever (the watchdog is fed
+    /// meanwhile, so the report stays until the next power-up).
+    ///
+    /// # Safety
+    ///
+    /// The SPI1, OLED and LED peripherals are stolen: the application must not use them anymore
+    /// (e.g. call this function from an exception handler, with the interrupts disabled).
+    pub unsafe fn report(&self) -> ! {
+        #[cfg(feature = "graphics")]
+        {
+            // Safety: The application is stopped, nothing else drives the OLED display
+            unsafe { screen::draw(self) };
+        }
+
+        // Safety: The application is stopped, nothing else drives the LEDs
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        let mut delay = INTR_DELAY;
+        loop {
+            crate::watchdog::feed();
+            for _ in 0..self.blink_count() {
+                set_leds(&gpio, 0xff);
+                delay.delay_ms(200);
+                set_leds(&gpio, 0x00);
+                delay.delay_ms(200);
+            }
+            delay.delay_ms(1000);
+        }
+    }
+}
+
+/// Maximum length of the message of a panic, in bytes (longer messages are truncated).
+pub const PANIC_MESSAGE_LEN: usize = 96;
+
+/// A panic, as seen by the panic handler.
+pub struct Panic<'a> {
+    /// Source file of the panic (`<redacted>` if the locations are not kept)
+    pub file: &'a str,
+    /// Line of the panic (0 if the locations are not kept)
+    pub line: u32,
+    message: [u8; PANIC_MESSAGE_LEN],
+    message_len: usize,
+}
+
+impl<'a> Panic<'a> {
+    /// Get the location and the (truncated) message of a panic.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - The information given to the panic handler.
+    pub fn from_info(info: &'a core::panic::PanicInfo) -> Self {
+        let (file, line) = match info.location() {
+            Some(location) => (location.file(), location.line()),
+            None => ("<redacted>", 0),
+        };
+        let mut panic = Self {
+            file,
+            line,
+            message: [0; PANIC_MESSAGE_LEN],
+            message_len: 0,
+        };
+        let _ = write!(panic, "{}", info.message());
+        panic
+    }
+
+    /// Get the message (truncated to [`PANIC_MESSAGE_LEN`] bytes).
+    pub fn message(&self) -> &str {
+        core::str::from_utf8(&self.message[..self.message_len]).unwrap_or("")
+    }
+
+    /// Short error ID of the panic, a hash of its location and message (never 0): the same
+    /// panic always gives the same ID, even without the location details (redacted locations).
+    pub fn id(&self) -> u8 {
+        // FNV-1a of the file, the line and the message, folded to 8 bits
+        let mut hash: u32 = 0x811c_9dc5;
+        let line = self.line.to_le_bytes();
+        let bytes = self.file.as_bytes().iter().chain(&line);
+        for &byte in bytes.chain(self.message().as_bytes()) {
+            hash = (hash ^ byte as u32).wrapping_mul(0x0100_0193);
+        }
+        let id = (hash ^ (hash >> 8) ^ (hash >> 16) ^ (hash >> 24)) as u8;
+        id.max(1)
+    }
+
+    /// Report the panic on the OLED display (with the `graphics` feature) and on the LEDs.
+    ///
+    /// The LEDs show the error ID in binary (LED7 is the most significant bit) for 1.5s, then
+    /// blink twice, repeated forever: the ID can still be read if the display is dead. The
+    /// watchdog is fed meanwhile, so the report stays until the next power-up.
+    ///
+    /// # Safety
+    ///
+    /// The SPI1, OLED and LED peripherals are stolen: the application must not use them anymore
+    /// (e.g. call this function from the panic handler, with the interrupts disabled).
+    pub unsafe fn report(&self) -> ! {
+        // Safety: The application is stopped, the LEDs are driven by the GPIO again
+        unsafe { crate::gpio::AudioViz::disable() };
+
+        #[cfg(feature = "graphics")]
+        {
+            // Safety: The application is stopped, nothing else drives the OLED display
+            unsafe { screen::draw_panic(self) };
+        }
+
+        // Safety: The application is stopped, nothing else drives the LEDs
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        let mut delay = INTR_DELAY;
+        loop {
+            crate::watchdog::feed();
+            set_leds(&gpio, self.id());
+            delay.delay_ms(1500);
+            set_leds(&gpio, 0x00);
+            delay.delay_ms(500);
+            for _ in 0..2 {
+                set_leds(&gpio, 0xff);
+                delay.delay_ms(100);
+                set_leds(&gpio, 0x00);
+                delay.delay_ms(100);
+            }
+            delay.delay_ms(300);
+        }
+    }
+}
+
+impl fmt::Write for Panic<'_> {
+    /// Append to the message, truncated to whole characters.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            let len = c.len_utf8();
+            if self.message_len + len > PANIC_MESSAGE_LEN {
+                break;
+            }
+            c.encode_utf8(&mut self.message[self.message_len..]);
+            self.message_len += len;
+        }
+        Ok(())
+    }
+}
+
+/// Set the state of all the LEDs at once.
+#[inline(always)]
+fn set_leds(gpio: &crate::pac::Gpio, states: u8) {
+    unsafe {
+        gpio.led()
+            .write_with_zero(|w| w.bits((0b11111111 << 8) | states as u16));
+    }
+}
+
+#[cfg(feature = "graphics")]
+pub(crate) mod screen {
+    //! Fault report on the OLED display, with the tiny 3x5 font of the HAL.
+    use core::convert::Infallible;
+
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    use super::{Fault, Panic};
+    use crate::{
+        delay::{INTR_DELAY, IntrDelay},
+        display::{DisplayPeripheral, Initialized, font},
+        gpio::{Gpio, IntoPin, Pin},
+        spi::{self, Spi, Spi1, SpiBusDevice},
+        text::TextBuf,
+    };
+
+    /// Scale of the font (one dot is a square of SCALE x SCALE pixels).
+    const SCALE: u8 = 2;
+    /// Horizontal advance of a character, in pixels.
+    const ADVANCE: u8 = font::CELL_WIDTH * SCALE;
+    /// Height of a line of text, in pixels.
+    const LINE_HEIGHT: u8 = font::CELL_HEIGHT * SCALE;
+
+    /// OLED display re-created from the stolen peripherals (the same type as the display of an
+    /// application over [`SpiBusDevice`], so that its driver is only linked once).
+    pub(crate) type Screen = DisplayPeripheral<
+        SpiBusDevice<Spi1, IntrDelay>,
+        Pin<crate::gpio::spi_oled_bank::SpiOledCs>,
+        Pin<crate::gpio::spi_oled_bank::SpiOledDc>,
+        Pin<crate::gpio::spi_oled_bank::SpiOledRes>,
+        IntrDelay,
+        Infallible,
+        spi::Error,
+        Initialized,
+    >;
+
+    /// Re-create the OLED display from scratch (the application may have left it in any state).
+    ///
+    /// # Returns
+    ///
+    /// The display, or `None` if the SPI1 peripheral does not respond (nothing can be drawn).
+    ///
+    /// # Safety
+    ///
+    /// The SPI1 peripheral and the OLED pins are stolen.
+    pub(crate) unsafe fn open() -> Option<Screen> {
+        let (cs, _, _, dc, rst) = Gpio::new().take_oled().unwrap();
+        let mut spi = Spi::new(Spi1::new(), INTR_DELAY);
+        spi.initialize().ok()?;
+        DisplayPeripheral::new(
+            SpiBusDevice::new(spi),
+            cs.into_pin(),
+            dc.into_pin(),
+            rst.into_pin(),
+            INTR_DELAY,
+        )
+        .initialize()
+        .ok()
+    }
+
+    /// Draw the fault report on the OLED display.
+    ///
+    /// # Safety
+    ///
+    /// The SPI1 peripheral and the OLED pins are stolen.
+    pub(super) unsafe fn draw(fault: &Fault) {
+        // Safety: Forwarded to the caller
+        let Some(mut screen) = (unsafe { open() }) else {
+            return;
+        };
+
+        let _ = screen.fill_rectangle(0, 0, 127, 127, Rgb565::BLACK);
+        let _ = screen.fill_rectangle(0, 0, 127, LINE_HEIGHT + SCALE, Rgb565::RED);
+        draw_text(&mut screen, 2 * SCALE, 2 * SCALE, "TRAP", Rgb565::WHITE);
+
+        let mut code = [b' '; 2];
+        code[0] = b'0' + fault.cause.code() / 10;
+        code[1] = b'0' + fault.cause.code() % 10;
+        draw_line(&mut screen, 2, b"CAUSE ", &code);
+        draw_text(
+            &mut screen,
+            0,
+            3 * LINE_HEIGHT,
+            fault.cause.name(),
+            Rgb565::YELLOW,
+        );
+        draw_line(&mut screen, 5, b"PC   ", &hex(fault.pc));
+        draw_line(&mut screen, 6, b"TVAL ", &hex(fault.tval));
+    }
+
+    /// Draw the panic report (crash screen) on the OLED display: the message is cut to the
+    /// lines of the screen (the whole message is kept in the crash record).
+    ///
+    /// # Safety
+    ///
+    /// The SPI1 peripheral and the OLED pins are stolen.
+    pub(super) unsafe fn draw_panic(panic: &Panic) {
+        // Safety: Forwarded to the caller
+        let Some(mut screen) = (unsafe { open() }) else {
+            return;
+        };
+
+        // Header on a red line, then the location and the message
+        let _ = screen.fill_rectangle(0, 0, 127, 127, Rgb565::BLACK);
+        let _ = screen.fill_rectangle(0, 0, 127, LINE_HEIGHT + SCALE, Rgb565::RED);
+        draw_text(&mut screen, 2 * SCALE, 2 * SCALE, "PANIC", Rgb565::WHITE);
+        let id = hex(panic.id() as u32);
+        draw_text(
+            &mut screen,
+            128 - 5 * ADVANCE,
+            2 * SCALE,
+            "ID",
+            Rgb565::WHITE,
+        );
+        draw_bytes(
+            &mut screen,
+            128 - 2 * ADVANCE,
+            2 * SCALE,
+            &id[6..],
+            Rgb565::WHITE,
+        );
+
+        let mut location = [0; 48];
+        let mut location = TextBuf::new(&mut location);
+        location.push_str(panic.file);
+        location.push_str(":");
+        location.push_dec(panic.line, 0);
+        draw_text(&mut screen, 0, 2 * LINE_HEIGHT, "AT", Rgb565::YELLOW);
+        draw_wrapped(&mut screen, 3, 2, location.as_str().as_bytes());
+        draw_text(&mut screen, 0, 5 * LINE_HEIGHT, "MESSAGE", Rgb565::YELLOW);
+        draw_wrapped(&mut screen, 6, 3, panic.message().as_bytes());
+    }
+
+    /// Draw a text over some lines, cut to the width of the screen.
+    fn draw_wrapped(screen: &mut Screen, line: u8, lines: u8, text: &[u8]) {
+        let columns = (128 / ADVANCE) as usize;
+        for (i, chunk) in text.chunks(columns).take(lines as usize).enumerate() {
+            draw_bytes(
+                screen,
+                0,
+                (line + i as u8) * LINE_HEIGHT,
+                chunk,
+                Rgb565::WHITE,
+            );
+        }
+    }
+
+    /// Draw a labelled value on a line of text.
+    fn draw_line(screen: &mut Screen, line: u8, label: &[u8], value: &[u8]) {
+        let y = line * LINE_HEIGHT;
+        draw_bytes(screen, 0, y, label, Rgb565::WHITE);
+        draw_bytes(screen, label.len() as u8 * ADVANCE, y, value, Rgb565::GREEN);
+    }
+
+    /// Format a word as 8 hexadecimal digits.
+    fn hex(value: u32) -> [u8; 8] {
+        let mut digits = [b'0'; 8];
+        for (i, digit) in digits.iter_mut().enumerate() {
+            let nibble = ((value >> (28 - 4 * i)) & 0xf) as u8;
+            *digit = if nibble < 10 {
+                b'0' + nibble
+            } else {
+                b'A' + nibble - 10
+            };
+        }
+        digits
+    }
+
+    fn draw_text(screen: &mut Screen, x: u8, y: u8, text: &str, color: Rgb565) {
+        draw_bytes(screen, x, y, text.as_bytes(), color);
+    }
+
+    fn draw_bytes(screen: &mut Screen, x: u8, y: u8, text: &[u8], color: Rgb565) {
+        for (i, &c) in text.iter().enumerate() {
+            let x = x as usize + i * ADVANCE as usize;
+            if x + 3 * SCALE as usize > 128 {
+                break;
+            }
+            draw_glyph(screen, x as u8, y, font::glyph(c), color);
+        }
+    }
+
+    /// Draw a glyph, one dot at a time.
+    fn draw_glyph(screen: &mut Screen, x: u8, y: u8, glyph: u16, color: Rgb565) {
+        for row in 0..font::GLYPH_HEIGHT {
+            for col in 0..font::GLYPH_WIDTH {
+                if font::is_dot_set(glyph, col, row) {
+                    let (x0, y0) = (x + col * SCALE, y + row * SCALE);
+                    let _ = screen.fill_rectangle(x0, y0, x0 + SCALE - 1, y0 + SCALE - 1, color);
+                }
+            }
+        }
+    }
+}
+
+/// Default exception handler: report the fault and stop.
+#[cfg(feature = "rt")]
+#[unsafe(export_name = "ExceptionHandler")]
+fn exception_handler(_trap_frame: &riscv_rt::TrapFrame) -> ! {
+    crate::interrupt::disable();
+    let fault = Fault::read();
+    crate::crash::record_fault(&fault);
+    // Safety: The application is stopped, the interrupts are disabled
+    unsafe { fault.report() }
+}
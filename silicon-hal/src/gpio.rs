@@ -2,6 +2,7 @@ use core::convert::Infallible;
 
 use paste::paste;
 
+use crate::executor;
 use crate::typesafe::Sealed;
 use embedded_hal::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
 
@@ -161,6 +162,34 @@ pub mod led_bank {
         Led6 => 6,
         Led7 => 7,
     }
+
+    impl<I> Pin<I>
+    where
+        I: LedBankPin,
+    {
+        /// Set the brightness of the LED (PWM duty cycle), applied while the LED is on.
+        ///
+        /// # Arguments
+        ///
+        /// * `duty` - The duty cycle, from 0 (off) to 255 (fully on, the default).
+        #[inline(always)]
+        pub fn set_brightness(&mut self, duty: u8) {
+            // Safety: Volatile write, the duty cycle register of this LED is only written by its pin
+            let gpio = unsafe { pac::Gpio::steal() };
+            unsafe {
+                gpio.led_pwm(I::BANK_ID as usize)
+                    .write_with_zero(|w| w.duty().bits(duty));
+            }
+        }
+
+        /// Get the brightness of the LED (PWM duty cycle).
+        #[inline(always)]
+        pub fn brightness(&self) -> u8 {
+            // Safety: Read-only, have no other effect
+            let gpio = unsafe { pac::Gpio::steal() };
+            gpio.led_pwm(I::BANK_ID as usize).read().duty().bits()
+        }
+    }
 }
 
 pub mod btn_bank {
@@ -223,6 +252,91 @@ pub mod btn_bank {
         Btn5 => 5,
         Btn6 => 6,
     }
+
+    /// Bank IDs of the buttons of the [`BtnBank`](crate::gpio::BtnBank) (bit mask).
+    pub(crate) const BANK_MASK: u8 = 0b0111_1110;
+
+    /// Edges of the buttons latched by the hardware, see [`BtnBank::take_events`](crate::gpio::BtnBank::take_events).
+    ///
+    /// The latches are set on the debounced edges and stay set until they are taken, so a press
+    /// is not missed even if it is released before the buttons are read.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct BtnEvents {
+        /// Buttons pressed since the events were last taken (same order as `read_all_states`).
+        pub pressed: [bool; 6],
+        /// Buttons released since the events were last taken.
+        pub released: [bool; 6],
+    }
+
+    impl BtnEvents {
+        /// Check if no button was pressed nor released.
+        #[inline(always)]
+        pub fn is_empty(&self) -> bool {
+            !self.pressed.contains(&true) && !self.released.contains(&true)
+        }
+    }
+
+    /// Read and clear the rise and fall latches of some buttons.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - The bank IDs of the buttons (bit mask).
+    ///
+    /// # Returns
+    ///
+    /// * The rise and fall latches of the buttons (bit masks).
+    #[inline(always)]
+    pub(crate) fn take_latches(mask: u8) -> (u8, u8) {
+        // Safety: The latches are write-1-to-clear, only the latches of the given buttons are cleared.
+        let gpio = unsafe { pac::Gpio::steal() };
+        let rise = gpio.btn_rise().read().bits() & mask;
+        let fall = gpio.btn_fall().read().bits() & mask;
+        // A new edge latched after the read is kept, unless it is on a button with an edge already read
+        unsafe {
+            gpio.btn_rise().write_with_zero(|w| w.bits(rise));
+            gpio.btn_fall().write_with_zero(|w| w.bits(fall));
+        }
+        (rise, fall)
+    }
+
+    impl<I> Pin<I>
+    where
+        I: BtnBankPin,
+    {
+        /// Check if the button was pressed since the last call, and clear its press latch.
+        #[inline(always)]
+        pub fn take_pressed(&mut self) -> bool {
+            let mask = 1 << I::BANK_ID;
+            // Safety: The latches are write-1-to-clear, only the latch of this button is cleared.
+            let gpio = unsafe { pac::Gpio::steal() };
+            let pressed = gpio.btn_rise().read().btn_rise(I::BANK_ID).bit_is_set();
+            if pressed {
+                unsafe { gpio.btn_rise().write_with_zero(|w| w.bits(mask)) };
+            }
+            pressed
+        }
+
+        /// Check if the button was released since the last call, and clear its release latch.
+        #[inline(always)]
+        pub fn take_released(&mut self) -> bool {
+            let mask = 1 << I::BANK_ID;
+            // Safety: The latches are write-1-to-clear, only the latch of this button is cleared.
+            let gpio = unsafe { pac::Gpio::steal() };
+            let released = gpio.btn_fall().read().btn_fall(I::BANK_ID).bit_is_set();
+            if released {
+                unsafe { gpio.btn_fall().write_with_zero(|w| w.bits(mask)) };
+            }
+            released
+        }
+
+        /// Get the number of presses of the button (4-bit counter, wrapping around).
+        #[inline(always)]
+        pub fn press_count(&self) -> u8 {
+            // Safety: Read-only, have no other effect
+            let gpio = unsafe { pac::Gpio::steal() };
+            gpio.btn_count().read().btn_count(I::BANK_ID).bits()
+        }
+    }
 }
 
 pub mod spi_sdcard_bank {
@@ -549,6 +663,47 @@ where
     }
 }
 
+/// Async wait for the input pins (for the [`crate::executor`]): the pin is polled each time the
+/// task is polled, the task yields to the other tasks meanwhile.
+///
+/// Note: The edges are only seen if the pin keeps its new level until the task is polled.
+impl<I> embedded_hal_async::digital::Wait for Pin<I>
+where
+    I: InputCapablePin,
+{
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        while self.pin.is_low()? {
+            executor::yield_now().await;
+        }
+        Ok(())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        while self.pin.is_high()? {
+            executor::yield_now().await;
+        }
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_low().await?;
+        self.wait_for_high().await
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_high().await?;
+        self.wait_for_low().await
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        if self.pin.is_high()? {
+            self.wait_for_low().await
+        } else {
+            self.wait_for_high().await
+        }
+    }
+}
+
 pub struct SpiPins<MOSI, CLK, MISO>
 where
     MOSI: OutputCapablePin,
@@ -919,6 +1074,45 @@ impl LedBank {
             });
         }
     }
+
+    /// Read all LED states in one operation
+    /// This function reads the states of all LEDs in one operation by reading the LED register once.
+    #[inline(always)]
+    pub fn read_all_states(&self) -> [bool; 8] {
+        // Safety: Read-only, have no other effect
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        let reg = gpio.led().read();
+        core::array::from_fn(|i| reg.led_output(i as u8).bit_is_set())
+    }
+
+    /// Set the brightness of all LEDs (PWM duty cycles, from 0 = off to 255 = fully on).
+    ///
+    /// The brightness only applies while a LED is on, see [`LedBank::set_all_levels`].
+    #[inline(always)]
+    pub fn set_all_brightness(&mut self, duties: [u8; 8]) {
+        // Safety: We have exclusive access to all LED pins.
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        for (i, duty) in duties.into_iter().enumerate() {
+            unsafe {
+                gpio.led_pwm(i).write_with_zero(|w| w.duty().bits(duty));
+            }
+        }
+    }
+
+    /// Read the brightness of all LEDs (PWM duty cycles).
+    #[inline(always)]
+    pub fn read_all_brightness(&self) -> [u8; 8] {
+        // Safety: Read-only, have no other effect
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        core::array::from_fn(|i| gpio.led_pwm(i).read().duty().bits())
+    }
+
+    /// Set the level of all LEDs: their brightness, and their state (a LED at level 0 is off).
+    #[inline(always)]
+    pub fn set_all_levels(&mut self, levels: [u8; 8]) {
+        self.set_all_brightness(levels);
+        self.set_all_states(levels.map(|level| level != 0));
+    }
 }
 
 pub struct BtnBank {
@@ -993,6 +1187,40 @@ impl BtnBank {
         ];
         Ok(states)
     }
+
+    /// Take the latched events of all buttons in one operation
+    /// The buttons pressed or released since the last call are returned, and their latches are cleared.
+    #[inline(always)]
+    pub fn take_events(&mut self) -> btn_bank::BtnEvents {
+        let (rise, fall) = btn_bank::take_latches(btn_bank::BANK_MASK);
+        btn_bank::BtnEvents {
+            pressed: core::array::from_fn(|i| rise & (1 << (i + 1)) != 0),
+            released: core::array::from_fn(|i| fall & (1 << (i + 1)) != 0),
+        }
+    }
+
+    /// Read the press counters of all buttons in one operation (4-bit counters, wrapping around).
+    #[inline(always)]
+    pub fn press_counts(&self) -> [u8; 6] {
+        // Safety: Read-only, have no other effect
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        let reg = gpio.btn_count().read();
+        core::array::from_fn(|i| reg.btn_count(i as u8 + 1).bits())
+    }
+}
+
+/// Display mode of the [`AudioViz`] energy meter.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AudioVizMode {
+    /// VU-meter: the LEDs below the energy level are lit.
+    Bar = 0,
+    /// Only the LED at the energy level is lit.
+    Dot = 1,
+    /// VU-meter, plus the highest recent level held for ~0.5s before decaying.
+    PeakHold = 2,
+    /// Stereo: left channel on LEDs 3-0 and right channel on LEDs 4-7 (4 levels each, growing
+    /// from the center).
+    Split = 3,
 }
 
 /// AudioViz is a hardware vizualization tool that provides an LED energy-meter
@@ -1003,6 +1231,10 @@ impl BtnBank {
 /// amplitude range, allowing users to easily see the intensity of the audio signal at
 /// any given moment.
 ///
+/// The display mode, the sensitivity and the reference level (sample value of silence) can be
+/// configured, so the meter stays meaningful whatever the volume is. The configuration is kept
+/// when the AudioViz is brought down and enabled again.
+///
 /// AudioViz takes ownership of the LED bank to ensure exclusive access to the LEDs.
 pub struct AudioViz(LedBank);
 
@@ -1024,6 +1256,55 @@ impl AudioViz {
         self.0
     }
 
+    /// Set the display mode of the energy meter.
+    #[inline(always)]
+    pub fn set_mode(&mut self, mode: AudioVizMode) {
+        // Safety: We own the LED bank, the AudioViz configuration is ours.
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        gpio.audio_viz()
+            .modify(|_, w| unsafe { w.mode().bits(mode as u8) });
+    }
+
+    /// Get the display mode of the energy meter.
+    #[inline(always)]
+    pub fn mode(&self) -> AudioVizMode {
+        // Safety: Read-only, have no other effect
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        match gpio.audio_viz().read().mode().bits() {
+            0 => AudioVizMode::Bar,
+            1 => AudioVizMode::Dot,
+            2 => AudioVizMode::PeakHold,
+            _ => AudioVizMode::Split,
+        }
+    }
+
+    /// Set the sensitivity of the energy meter.
+    ///
+    /// # Arguments
+    ///
+    /// * `sensitivity` - The gain applied to the measured energy (0-15, saturated), each step doubles
+    ///   the energy (+3dB). Use 2 more steps every time the amplitude of the samples is halved.
+    #[inline(always)]
+    pub fn set_sensitivity(&mut self, sensitivity: u8) {
+        // Safety: We own the LED bank, the AudioViz configuration is ours.
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        gpio.audio_viz()
+            .modify(|_, w| unsafe { w.sensitivity().bits(sensitivity.min(15)) });
+    }
+
+    /// Set the reference level of the energy meter.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The sample value of silence (128 for unsigned 8-bit samples at full volume).
+    #[inline(always)]
+    pub fn set_reference(&mut self, reference: u8) {
+        // Safety: We own the LED bank, the AudioViz configuration is ours.
+        let gpio = unsafe { crate::pac::Gpio::steal() };
+        gpio.audio_viz()
+            .modify(|_, w| unsafe { w.reference().bits(reference) });
+    }
+
     /// Enable the AudioViz functionality.
     ///
     /// This function enables the AudioViz functionality by configuring the LED bank for audio visualization.
@@ -1031,7 +1312,7 @@ impl AudioViz {
     pub unsafe fn enable() {
         // Safety: Caller must ensure that the LED bank is not used for GPIO while AudioViz is enabled.
         let gpio = unsafe { crate::pac::Gpio::steal() };
-        gpio.audio_viz().write(|w| w.enable().set_bit());
+        gpio.audio_viz().modify(|_, w| w.enable().set_bit());
     }
 
     /// Disable the AudioViz functionality.
@@ -1040,6 +1321,6 @@ impl AudioViz {
     pub unsafe fn disable() {
         // Safety: Caller must ensure that AudioViz is not used after disabling.
         let gpio = unsafe { crate::pac::Gpio::steal() };
-        gpio.audio_viz().write(|w| w.enable().clear_bit());
+        gpio.audio_viz().modify(|_, w| w.enable().clear_bit());
     }
 }
@@ -0,0 +1,381 @@
+//! Input module
+//! This module provides a button event layer on top of the [`BtnBank`].
+//!
+//! [`ButtonInput`] samples the buttons against Timer0 each time it is polled, debounces them,
+//! and turns their changes into [`ButtonEvent`]s, queued until they are read:
+//! - `Pressed` / `Released`: debounced edges (a new level must be stable for the debounce duration),
+//! - `LongPress`: a button held for the long-press duration (once per press),
+//! - `Repeat`: a held button repeats, after the repeat delay, at the repeat period of the button,
+//! - `Chord`: a button pressed while a modifier button is held (instead of `Pressed`).
+//!
+//! The buttons used in a chord (the modifier and the other button) are flagged as consumed in
+//! their `Released` event, so that they do not trigger their own action. The buttons already
+//! held when the input is created are flagged the same way.
+//!
+//! The timings and the modifier buttons are set with an [`InputConfig`]. The buttons should be
+//! polled more often than the debounce duration (e.g. every ~10ms): a press released before it
+//! is sampled is still caught by the hardware latches (see [`BtnBank::take_events`]), and sent
+//! as `Pressed` (or `Chord`) followed by `Released`.
+
+use crate::gpio::BtnBank;
+use crate::timer::{CopyableTimer0, Duration, Instant, Timer};
+
+/// Number of buttons.
+pub const BUTTONS: usize = 6;
+
+/// Maximum number of queued events, the oldest events are dropped when the queue is full.
+pub const QUEUE_SIZE: usize = 16;
+
+/// A button of the [`BtnBank`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Button {
+    Btn1,
+    Btn2,
+    Btn3,
+    Btn4,
+    Btn5,
+    Btn6,
+}
+
+impl Button {
+    /// All the buttons, in order.
+    pub const ALL: [Button; BUTTONS] = [
+        Button::Btn1,
+        Button::Btn2,
+        Button::Btn3,
+        Button::Btn4,
+        Button::Btn5,
+        Button::Btn6,
+    ];
+
+    /// Index of the button (as in [`BtnBank::read_all_states`]).
+    #[inline(always)]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Bit mask of the button (for [`InputConfig::modifiers`]).
+    #[inline(always)]
+    pub const fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Event of the buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonEvent {
+    /// A button was pressed, while no modifier was held.
+    Pressed(Button),
+    /// A button was released.
+    Released {
+        button: Button,
+        /// Number of repeats while the button was held.
+        repeats: u16,
+        /// Whether the button was used in a chord (or already held when the input was created).
+        consumed: bool,
+    },
+    /// A button has been held for the long-press duration (not sent for consumed buttons).
+    LongPress(Button),
+    /// A held button repeats.
+    Repeat {
+        button: Button,
+        /// Number of repeats since the button was pressed.
+        count: u16,
+        /// Modifier held when the button was pressed (the chord goes on while it is held).
+        modifier: Option<Button>,
+    },
+    /// A button was pressed while a modifier was held.
+    Chord { modifier: Button, button: Button },
+}
+
+/// Timings and modifiers of the button event layer.
+#[derive(Debug, Clone, Copy)]
+pub struct InputConfig {
+    /// Duration a new level must be stable to be taken into account.
+    pub debounce: Duration,
+    /// Duration a button must be held to send a `LongPress` event.
+    pub long_press: Duration,
+    /// Duration a button must be held before it starts repeating.
+    pub repeat_delay: Duration,
+    /// Duration between two repeats of a held button, for each button.
+    pub repeat_periods: [Duration; BUTTONS],
+    /// Modifier buttons (bit mask, see [`Button::mask`]), the other buttons pressed while
+    /// a modifier is held send `Chord` events.
+    pub modifiers: u8,
+}
+
+impl InputConfig {
+    /// Default configuration: 20ms debounce, 1s long press, repeats every 250ms after 300ms,
+    /// and no modifier.
+    pub const DEFAULT: Self = Self {
+        debounce: Duration::millis(20),
+        long_press: Duration::secs(1),
+        repeat_delay: Duration::millis(300),
+        repeat_periods: [Duration::millis(250); BUTTONS],
+        modifiers: 0,
+    };
+
+    /// Check if a button is a modifier.
+    #[inline(always)]
+    pub fn is_modifier(&self, button: Button) -> bool {
+        self.modifiers & button.mask() != 0
+    }
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// State of a single button.
+#[derive(Clone, Copy)]
+struct ButtonState {
+    /// Last sampled level, and when it changed.
+    raw: bool,
+    changed_at: Instant,
+    /// Debounced level, and when it was pressed.
+    pressed: bool,
+    pressed_at: Instant,
+    /// Instant of the next repeat, and number of repeats since the button was pressed.
+    next_repeat: Instant,
+    repeats: u16,
+    /// Whether the long press was sent.
+    long_press: bool,
+    /// Whether the button was used in a chord.
+    consumed: bool,
+    /// Modifier held when the button was pressed (if any).
+    modifier: Option<Button>,
+}
+
+impl ButtonState {
+    fn new(pressed: bool, now: Instant) -> Self {
+        Self {
+            raw: pressed,
+            changed_at: now,
+            pressed,
+            pressed_at: now,
+            next_repeat: now,
+            repeats: 0,
+            long_press: false,
+            // Buttons already held are ignored until released
+            consumed: pressed,
+            modifier: None,
+        }
+    }
+}
+
+/// Fixed-size queue of button events.
+struct EventQueue {
+    events: [Option<ButtonEvent>; QUEUE_SIZE],
+    head: usize,
+    len: usize,
+}
+
+impl EventQueue {
+    const fn new() -> Self {
+        Self {
+            events: [None; QUEUE_SIZE],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, event: ButtonEvent) {
+        if self.len == QUEUE_SIZE {
+            // Drop the oldest event
+            self.pop();
+        }
+        self.events[(self.head + self.len) % QUEUE_SIZE] = Some(event);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<ButtonEvent> {
+        if self.len == 0 {
+            return None;
+        }
+        let event = self.events[self.head].take();
+        self.head = (self.head + 1) % QUEUE_SIZE;
+        self.len -= 1;
+        event
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Button event layer: debounced buttons, sampled against Timer0.
+pub struct ButtonInput {
+    btns: BtnBank,
+    timer: Timer<CopyableTimer0>,
+    config: InputConfig,
+    buttons: [ButtonState; BUTTONS],
+    queue: EventQueue,
+}
+
+impl ButtonInput {
+    /// Create a new button event layer.
+    ///
+    /// Buttons already held are ignored until released. Timer0 must be enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `btns` - The buttons.
+    /// * `config` - The timings and the modifiers.
+    pub fn new(mut btns: BtnBank, config: InputConfig) -> Self {
+        let timer = Timer::new_timer0();
+        let now = timer.get_counter();
+        // Drop the edges latched before the input was created
+        btns.take_events();
+        let Ok(states) = btns.read_all_states();
+        Self {
+            btns,
+            timer,
+            config,
+            buttons: states.map(|pressed| ButtonState::new(pressed, now)),
+            queue: EventQueue::new(),
+        }
+    }
+
+    /// Bring down the event layer and get the buttons back.
+    pub fn bring_down(self) -> BtnBank {
+        self.btns
+    }
+
+    /// Get the configuration.
+    #[inline(always)]
+    pub fn config(&self) -> &InputConfig {
+        &self.config
+    }
+
+    /// Change the configuration, it applies to the next samples.
+    pub fn set_config(&mut self, config: InputConfig) {
+        self.config = config;
+    }
+
+    /// Check if a button is held (debounced level).
+    #[inline(always)]
+    pub fn is_held(&self, button: Button) -> bool {
+        self.buttons[button.index()].pressed
+    }
+
+    /// Drop the queued events.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Sample the buttons, and queue the resulting events.
+    pub fn sample(&mut self) {
+        let now = self.timer.get_counter();
+        let events = self.btns.take_events();
+        let Ok(states) = self.btns.read_all_states();
+        for button in Button::ALL {
+            let i = button.index();
+            let state = &mut self.buttons[i];
+            if states[i] != state.raw {
+                state.raw = states[i];
+                state.changed_at = now;
+            }
+
+            if state.raw != state.pressed && now >= state.changed_at + self.config.debounce {
+                if state.raw {
+                    self.press(button, now);
+                } else {
+                    self.release(button);
+                }
+            } else if state.pressed {
+                self.hold(button, now);
+            } else if events.pressed[i] && !state.raw {
+                // Pressed and released between two samples, only seen by the latches
+                self.press(button, now);
+                self.release(button);
+            }
+        }
+    }
+
+    /// Handle the (debounced) press of a button.
+    fn press(&mut self, button: Button, now: Instant) {
+        // Chord with the modifier held for the longest time (if any)
+        let modifier = Button::ALL
+            .into_iter()
+            .filter(|&m| m != button && self.config.is_modifier(m) && self.is_held(m))
+            .min_by_key(|&m| self.buttons[m.index()].pressed_at);
+
+        let state = &mut self.buttons[button.index()];
+        state.pressed = true;
+        state.pressed_at = now;
+        state.next_repeat = now + self.config.repeat_delay;
+        state.repeats = 0;
+        state.long_press = false;
+        state.consumed = modifier.is_some();
+        state.modifier = modifier;
+
+        match modifier {
+            Some(modifier) => {
+                self.buttons[modifier.index()].consumed = true;
+                self.queue.push(ButtonEvent::Chord { modifier, button });
+            }
+            None => self.queue.push(ButtonEvent::Pressed(button)),
+        }
+    }
+
+    /// Handle the (debounced) release of a button.
+    fn release(&mut self, button: Button) {
+        let state = &mut self.buttons[button.index()];
+        state.pressed = false;
+        let (repeats, consumed) = (state.repeats, state.consumed);
+        self.queue.push(ButtonEvent::Released {
+            button,
+            repeats,
+            consumed,
+        });
+    }
+
+    /// Handle a held button (long press and repeats).
+    fn hold(&mut self, button: Button, now: Instant) {
+        let i = button.index();
+        // A button pressed in a chord only repeats while its modifier is held,
+        // the other consumed buttons do not repeat.
+        let state = self.buttons[i];
+        let repeating = match state.modifier {
+            Some(modifier) => self.is_held(modifier),
+            None => !state.consumed,
+        };
+
+        let state = &mut self.buttons[i];
+        if !state.consumed && !state.long_press && now >= state.pressed_at + self.config.long_press
+        {
+            state.long_press = true;
+            self.queue.push(ButtonEvent::LongPress(button));
+        }
+
+        if repeating && now >= state.next_repeat {
+            let period = self.config.repeat_periods[i];
+            state.repeats = state.repeats.saturating_add(1);
+            state.next_repeat += period;
+            if state.next_repeat <= now {
+                // Skip the missed repeats
+                state.next_repeat = now + period;
+            }
+            let (count, modifier) = (state.repeats, state.modifier);
+            self.queue.push(ButtonEvent::Repeat {
+                button,
+                count,
+                modifier,
+            });
+        }
+    }
+
+    /// Get the next queued event (without sampling the buttons).
+    #[inline(always)]
+    pub fn next_event(&mut self) -> Option<ButtonEvent> {
+        self.queue.pop()
+    }
+
+    /// Sample the buttons, and get the next queued event.
+    pub fn poll(&mut self) -> Option<ButtonEvent> {
+        self.sample();
+        self.next_event()
+    }
+}
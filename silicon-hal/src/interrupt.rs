@@ -0,0 +1,149 @@
+//! Interrupt module
+//! This module provides an interface for the interrupt controller (INTC) of the SoC.
+//!
+//! The Ice-V core has a single machine external interrupt, driven by the INTC, which gathers
+//! the interrupt sources of the peripherals ([`Interrupt`]). The sources are edge-triggered:
+//! an event sets the pending flag of its source, and the CPU is interrupted as long as an enabled
+//! source is pending.
+//!
+//! With the `rt` feature, the HAL provides the machine external interrupt handler: it clears
+//! the pending flags of the active sources and calls their handlers, which are registered with
+//! the [`external_interrupt`](crate::external_interrupt) attribute:
+//!
+//! ```ignore
+//! #[silicon_hal::external_interrupt(silicon_hal::pac::Interrupt::BUTTONS)]
+//! fn buttons() {
+//!     // ...
+//! }
+//! ```
+//!
+//! Sources without a handler use the `DefaultHandler` of `riscv-rt`.
+//!
+//! An interrupt is only taken once its source is enabled with [`Intc::enable`] and the
+//! interrupts are globally enabled with [`enable`].
+//!
+//! Note: The handlers should clear the cause of the event (e.g. the match flag of a Timer0
+//! alarm), but not the pending flag, which is cleared before they are called.
+
+use riscv::InterruptNumber;
+
+use crate::pac;
+pub use crate::pac::Interrupt;
+
+/// Interrupt controller (INTC).
+pub struct Intc {
+    _private: (),
+}
+
+impl Intc {
+    /// Creates a new instance of the INTC peripheral.
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Get the register block of the INTC.
+    #[inline(always)]
+    fn regs() -> &'static pac::intc::RegisterBlock {
+        // Safety: The enable register is only modified through the singleton (in a critical
+        // section), the pending register is write 1 to clear.
+        unsafe { &*pac::Intc::ptr() }
+    }
+
+    /// Enable the interrupt of a source.
+    ///
+    /// A source which is already pending interrupts the CPU right away (see [`Intc::unpend`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `interrupt` - The interrupt source to enable.
+    pub fn enable(&mut self, interrupt: Interrupt) {
+        let mask = 1u8 << interrupt.number();
+        riscv::interrupt::free(|| {
+            Self::regs()
+                .enable()
+                .modify(|r, w| unsafe { w.bits(r.bits() | mask) });
+        });
+    }
+
+    /// Disable the interrupt of a source.
+    ///
+    /// The source is still latched as pending, but it does not interrupt the CPU anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `interrupt` - The interrupt source to disable.
+    pub fn disable(&mut self, interrupt: Interrupt) {
+        let mask = 1u8 << interrupt.number();
+        riscv::interrupt::free(|| {
+            Self::regs()
+                .enable()
+                .modify(|r, w| unsafe { w.bits(r.bits() & !mask) });
+        });
+    }
+
+    /// Check if the interrupt of a source is enabled.
+    #[inline(always)]
+    pub fn is_enabled(&self, interrupt: Interrupt) -> bool {
+        Self::regs().enable().read().bits() & (1u8 << interrupt.number()) != 0
+    }
+
+    /// Check if a source is pending (its event occurred and was not handled yet).
+    #[inline(always)]
+    pub fn is_pending(&self, interrupt: Interrupt) -> bool {
+        Self::regs().pending().read().bits() & (1u8 << interrupt.number()) != 0
+    }
+
+    /// Clear the pending flag of a source (e.g. to ignore the past events before enabling it).
+    #[inline(always)]
+    pub fn unpend(&mut self, interrupt: Interrupt) {
+        Self::regs()
+            .pending()
+            .write(|w| unsafe { w.bits(1u8 << interrupt.number()) });
+    }
+}
+
+/// Globally enable the (machine external) interrupts of the CPU.
+///
+/// # Safety
+///
+/// Do not call this function inside a critical section (e.g. [`free`]).
+#[inline(always)]
+pub unsafe fn enable() {
+    unsafe {
+        riscv::interrupt::enable_interrupt(riscv::interrupt::Interrupt::MachineExternal);
+        riscv::interrupt::enable();
+    }
+}
+
+/// Globally disable the interrupts of the CPU.
+#[inline(always)]
+pub fn disable() {
+    riscv::interrupt::disable();
+}
+
+/// Execute a closure with the interrupts disabled (critical section).
+///
+/// The interrupts are re-enabled afterwards, if they were enabled before.
+#[inline(always)]
+pub fn free<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    riscv::interrupt::free(f)
+}
+
+/// Machine external interrupt handler: dispatch the active sources of the INTC to their handlers.
+#[cfg(feature = "rt")]
+#[riscv_rt::core_interrupt(riscv::interrupt::Interrupt::MachineExternal)]
+fn machine_external() {
+    let intc = Intc::regs();
+    let active = intc.active().read().bits();
+    for n in 0..=Interrupt::MAX_INTERRUPT_NUMBER {
+        if active & (1u8 << n) != 0 {
+            // Clear the pending flag first, so that a new event during the handler is not lost
+            intc.pending().write(|w| unsafe { w.bits(1u8 << n) });
+            // Safety: The vector table only holds the interrupt handlers (or DefaultHandler)
+            unsafe { (pac::__EXTERNAL_INTERRUPTS[n]._handler)() };
+        }
+    }
+}
@@ -2,30 +2,51 @@
 
 pub use silicon_pac as pac;
 
+pub mod animation;
 pub mod audio;
+pub mod crash;
 pub mod dac;
 pub mod delay;
 pub mod display;
+pub mod executor;
+pub mod fault;
 pub mod gpio;
+pub mod input;
+pub mod interrupt;
+#[cfg(any(feature = "log-serial", feature = "log-console"))]
+pub mod logger;
+pub mod sound;
 pub mod spi;
+pub mod text;
 pub mod timer;
 pub mod typesafe;
+pub mod uart;
+pub mod watchdog;
 
 #[cfg(feature = "rt")]
-pub use riscv_rt::entry;
+pub use riscv_rt::{entry, external_interrupt};
 
 pub struct Peripheral {
     pub gpio: gpio::Gpio,
     pub spi0: spi::Spi0,
     pub spi1: spi::Spi1,
     pub dac: dac::AudioDac,
+    pub sound: sound::SoundGen,
+    pub timer0: timer::Timer0,
+    pub intc: interrupt::Intc,
+    pub uart: uart::Uart,
 }
 
 pub fn init() -> Peripheral {
+    crash::load();
     Peripheral {
         gpio: gpio::Gpio::new(),
         spi0: spi::Spi0::new(),
         spi1: spi::Spi1::new(),
         dac: dac::AudioDac::new(),
+        sound: sound::SoundGen::new(),
+        timer0: timer::Timer0::new(),
+        intc: interrupt::Intc::new(),
+        uart: uart::Uart::new(),
     }
 }
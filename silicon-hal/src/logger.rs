@@ -0,0 +1,336 @@
+//! Logger module
+//! This module provides a backend for the `log` crate, so that the application can log with the
+//! `log::error!`, `log::warn!`, `log::info!`, `log::debug!` and `log::trace!` macros.
+//!
+//! The records are written in a compact format, one line each: the level letter, the last
+//! segment of the module path and the message (e.g. `E menu: cannot open the album`).
+//!
+//! The records go to the sinks selected by cargo features (the module only exists with at least
+//! one of them, both are off by default):
+//! - `log-serial`: the UART, once it is handed over with [`attach_serial`],
+//! - `log-console`: an on-screen scrolling [`console`], kept in RAM and drawn on the OLED display
+//!   on demand.
+//!
+//! The levels are filtered at compile time with the `max_level_*` and `release_max_level_*`
+//! features of the `log` crate (the records above the static maximum level are compiled out),
+//! then at run time with the level given to [`init`].
+
+pub use log::{Level, LevelFilter};
+
+/// The logger, forwarding the records to the sinks.
+struct Logger;
+
+static LOGGER: Logger = Logger;
+
+/// Install the logger.
+///
+/// # Arguments
+///
+/// * `level` - The maximum level of the records (in addition to the static maximum level).
+pub fn init(level: LevelFilter) {
+    crate::interrupt::free(|| {
+        // Safety: Single hart, and the interrupts are disabled: nothing logs meanwhile
+        unsafe {
+            let _ = log::set_logger_racy(&LOGGER);
+            log::set_max_level_racy(level);
+        }
+    });
+}
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            #[cfg(feature = "log-serial")]
+            serial::write_record(record);
+            #[cfg(feature = "log-console")]
+            console::write_record(record);
+        }
+    }
+
+    fn flush(&self) {
+        #[cfg(feature = "log-serial")]
+        serial::flush();
+    }
+}
+
+/// Letter of a level.
+#[inline(always)]
+fn level_letter(level: Level) -> char {
+    match level {
+        Level::Error => 'E',
+        Level::Warn => 'W',
+        Level::Info => 'I',
+        Level::Debug => 'D',
+        Level::Trace => 'T',
+    }
+}
+
+/// Write a record in the compact format (without the line ending).
+fn format_record(w: &mut impl core::fmt::Write, record: &log::Record) -> core::fmt::Result {
+    // Only the last segment of the module path (e.g. `menu` for `silicon::app::menu`), found
+    // without the string searchers of `core` (large)
+    let target = record.target();
+    let target = match target.bytes().rposition(|c| c == b':') {
+        Some(colon) => target.get(colon + 1..).unwrap_or(target),
+        None => target,
+    };
+    write!(
+        w,
+        "{} {}: {}",
+        level_letter(record.level()),
+        target,
+        record.args()
+    )
+}
+
+#[cfg(feature = "log-serial")]
+pub use serial::attach_serial;
+
+#[cfg(feature = "log-serial")]
+mod serial {
+    //! Serial sink: the records are sent on the UART (blocking), one line each.
+    use core::fmt::Write as _;
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    use crate::uart::Uart;
+
+    /// Whether the UART was handed over to the logger.
+    static ATTACHED: AtomicBool = AtomicBool::new(false);
+
+    /// Hand the UART over to the logger, the records are sent on it from now on.
+    ///
+    /// The UART keeps its configuration (e.g. its baud rate).
+    ///
+    /// # Arguments
+    ///
+    /// * `uart` - The UART.
+    pub fn attach_serial(_uart: Uart) {
+        // The logger now owns the UART (a singleton without state, re-created to write)
+        ATTACHED.store(true, Ordering::Relaxed);
+    }
+
+    pub(super) fn write_record(record: &log::Record) {
+        if !ATTACHED.load(Ordering::Relaxed) {
+            return;
+        }
+        // The records of the interrupt handlers may be interleaved, the bytes are never lost
+        let mut uart = Uart::new();
+        let _ = super::format_record(&mut uart, record);
+        let _ = uart.write_str("\r\n");
+    }
+
+    pub(super) fn flush() {
+        if ATTACHED.load(Ordering::Relaxed) {
+            Uart::new().flush();
+        }
+    }
+}
+
+#[cfg(feature = "log-console")]
+pub mod console {
+    //! On-screen log console: the last lines of the log, kept in RAM, and drawn on the OLED
+    //! display on demand (the application owns the display) with the [`font`] of the text
+    //! console.
+    //!
+    //! The long records wrap onto the next lines (indented), the oldest lines scroll out.
+    use core::cell::UnsafeCell;
+    use core::fmt;
+
+    use embedded_graphics_core::{
+        pixelcolor::{Rgb565, RgbColor},
+        prelude::{DrawTarget, Point},
+    };
+    use log::Level;
+
+    use crate::display::{console, font};
+
+    /// Number of characters per line (a 128 pixels wide display).
+    pub const COLUMNS: usize = 128 / font::CELL_WIDTH as usize;
+    /// Number of lines kept (a 128 pixels high display).
+    pub const LINES: usize = 128 / console::LINE_HEIGHT as usize;
+
+    /// A line of the console.
+    #[derive(Clone, Copy)]
+    struct Line {
+        level: Level,
+        len: u8,
+        text: [u8; COLUMNS],
+    }
+
+    impl Line {
+        const EMPTY: Self = Self {
+            level: Level::Info,
+            len: 0,
+            text: [b' '; COLUMNS],
+        };
+    }
+
+    /// Ring buffer of the lines.
+    struct Buffer {
+        lines: [Line; LINES],
+        /// Oldest line
+        head: usize,
+        len: usize,
+        /// Number of lines written so far
+        written: u32,
+    }
+
+    impl Buffer {
+        /// Start a new line (dropping the oldest line if the buffer is full).
+        fn new_line(&mut self, level: Level) -> &mut Line {
+            if self.len == LINES {
+                self.head = (self.head + 1) % LINES;
+            } else {
+                self.len += 1;
+            }
+            self.written = self.written.wrapping_add(1);
+            let line = &mut self.lines[(self.head + self.len - 1) % LINES];
+            *line = Line {
+                level,
+                ..Line::EMPTY
+            };
+            line
+        }
+
+        /// Get the current line.
+        fn last_line(&mut self) -> &mut Line {
+            &mut self.lines[(self.head + self.len + LINES - 1) % LINES]
+        }
+    }
+
+    /// Buffer shared with the logger, only accessed with the interrupts disabled.
+    struct Shared(UnsafeCell<Buffer>);
+
+    // Safety: Single hart, the buffer is only accessed in critical sections.
+    unsafe impl Sync for Shared {}
+
+    static BUFFER: Shared = Shared(UnsafeCell::new(Buffer {
+        lines: [Line::EMPTY; LINES],
+        head: 0,
+        len: 0,
+        written: 0,
+    }));
+
+    /// Execute a closure on the buffer, with the interrupts disabled.
+    #[inline(always)]
+    fn with_buffer<R>(f: impl FnOnce(&mut Buffer) -> R) -> R {
+        // Safety: The interrupts are disabled, nothing else accesses the buffer meanwhile
+        crate::interrupt::free(|| f(unsafe { &mut *BUFFER.0.get() }))
+    }
+
+    /// Writer of a record, wrapping the long lines.
+    struct LineWriter<'a> {
+        buffer: &'a mut Buffer,
+        level: Level,
+    }
+
+    impl fmt::Write for LineWriter<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for &c in s.as_bytes() {
+                if c == b'\n' {
+                    self.buffer.new_line(self.level).len = 2;
+                    continue;
+                }
+                let mut line = self.buffer.last_line();
+                if line.len as usize == COLUMNS {
+                    // Wrap, indented
+                    line = self.buffer.new_line(self.level);
+                    line.len = 2;
+                }
+                line.text[line.len as usize] = c;
+                line.len += 1;
+            }
+            Ok(())
+        }
+    }
+
+    pub(super) fn write_record(record: &log::Record) {
+        with_buffer(|buffer| {
+            buffer.new_line(record.level());
+            let mut writer = LineWriter {
+                buffer,
+                level: record.level(),
+            };
+            let _ = super::format_record(&mut writer, record);
+        });
+    }
+
+    /// Number of lines written so far (wrapping), to redraw the console only on a change.
+    pub fn lines_written() -> u32 {
+        with_buffer(|buffer| buffer.written)
+    }
+
+    /// Drop all the lines.
+    pub fn clear() {
+        with_buffer(|buffer| {
+            buffer.head = 0;
+            buffer.len = 0;
+        });
+    }
+
+    /// Color of the lines of a level.
+    fn level_color(level: Level) -> Rgb565 {
+        match level {
+            Level::Error => Rgb565::RED,
+            Level::Warn => Rgb565::YELLOW,
+            Level::Info => Rgb565::WHITE,
+            Level::Debug => Rgb565::CYAN,
+            Level::Trace => Rgb565::new(12, 24, 12),
+        }
+    }
+
+    /// Draw the console, the oldest line at the top, on a black background.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The draw target (e.g. the OLED display), at least 128x128 pixels.
+    pub fn draw<D>(target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let area = target.bounding_box();
+        target.fill_solid(&area, Rgb565::BLACK)?;
+        for row in 0..LINES {
+            // Copy the line, so that the interrupts are not disabled while drawing
+            let line = with_buffer(|buffer| {
+                (row < buffer.len).then(|| buffer.lines[(buffer.head + row) % LINES])
+            });
+            let Some(line) = line else {
+                break;
+            };
+            // The cells of the text console, without its escape sequences (not needed here)
+            let y = row as i32 * console::LINE_HEIGHT as i32;
+            let color = level_color(line.level);
+            for (col, &c) in line.text[..line.len as usize].iter().enumerate() {
+                let top_left = Point::new(col as i32 * font::CELL_WIDTH as i32, y);
+                font::draw_cell(
+                    target,
+                    top_left,
+                    1,
+                    console::LINE_HEIGHT,
+                    c,
+                    color,
+                    Rgb565::BLACK,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Show the console on the OLED display, e.g. once the application stopped.
+    ///
+    /// # Safety
+    ///
+    /// The SPI1 peripheral and the OLED pins are stolen: the application must not use them
+    /// anymore.
+    pub unsafe fn show() {
+        // Safety: Forwarded to the caller
+        if let Some(mut screen) = unsafe { crate::fault::screen::open() } {
+            let _ = draw(&mut screen);
+        }
+    }
+}
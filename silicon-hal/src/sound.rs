@@ -0,0 +1,391 @@
+//! Sound Generator module
+//! This module provides an interface for the hardware Sound Generator peripheral.
+//!
+//! The Sound Generator has 3 independent voices (square, triangle, noise or sawtooth), each with
+//! its own frequency, volume and ADSR envelope. The voices are mixed in hardware on top of the
+//! DAC / Audio Streamer output, so beeps and UI sounds cost no CPU time while music is playing.
+//!
+//! A note is played by setting the frequency of a voice and opening its gate, the envelope then
+//! runs on its own: the level rises to the maximum (attack), falls to the sustain level (decay),
+//! and falls to 0 once the gate is closed (release). With a sustain level of 0, the note ends by
+//! itself after the decay (one-shot sounds).
+
+use crate::pac;
+
+/// Number of voices of the Sound Generator.
+pub const VOICES: usize = 3;
+
+/// Sampling rate of the Sound Generator (same as the Audio Streamer).
+const SAMPLE_RATE: u32 = 48_000;
+
+/// Phase steps of the notes of the 8th octave (C8 to B8), in Q4 fixed-point.
+///
+/// Lower octaves are obtained by halving these values.
+const OCTAVE8_STEPS: [u32; 12] = [
+    91445, 96882, 102643, 108747, 115213, 122064, 129322, 137012, 145160, 153791, 162936, 172625,
+];
+
+/// Highest octave supported by [`Note`] (B9 is ~15.8kHz).
+pub const MAX_OCTAVE: u8 = 9;
+
+/// Waveform of a voice.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Waveform {
+    /// Square wave (50% duty cycle), the classic "beep".
+    Square = 0,
+    /// Triangle wave, softer than the square wave.
+    Triangle = 1,
+    /// White noise, the frequency sets how fast the noise changes.
+    Noise = 2,
+    /// Sawtooth wave, brighter than the square wave.
+    Sawtooth = 3,
+}
+
+/// Pitch class of a note (in the chromatic scale).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Pitch {
+    C = 0,
+    CSharp = 1,
+    D = 2,
+    DSharp = 3,
+    E = 4,
+    F = 5,
+    FSharp = 6,
+    G = 7,
+    GSharp = 8,
+    A = 9,
+    ASharp = 10,
+    B = 11,
+}
+
+/// Frequency of a voice.
+///
+/// Internally, this is the phase step added to the voice oscillator at every sample,
+/// i.e. `frequency = step * 48000 / 65536` (resolution of ~0.73Hz, up to 24kHz).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Frequency(u16);
+
+impl Frequency {
+    /// Silence (the oscillator does not move).
+    pub const ZERO: Self = Self(0);
+
+    /// Create a frequency from a value in Hertz.
+    ///
+    /// # Arguments
+    ///
+    /// * `hz` - The frequency in Hertz, saturated to the Nyquist frequency (24kHz).
+    pub const fn from_hz(hz: u32) -> Self {
+        let hz = if hz > SAMPLE_RATE / 2 {
+            SAMPLE_RATE / 2
+        } else {
+            hz
+        };
+        Self((((hz << 16) + SAMPLE_RATE / 2) / SAMPLE_RATE) as u16)
+    }
+
+    /// Create a frequency from a raw phase step.
+    #[inline(always)]
+    pub const fn from_step(step: u16) -> Self {
+        Self(step)
+    }
+
+    /// Get the raw phase step of this frequency.
+    #[inline(always)]
+    pub const fn step(&self) -> u16 {
+        self.0
+    }
+
+    /// Get this frequency in Hertz (rounded down).
+    #[inline(always)]
+    pub const fn hz(&self) -> u32 {
+        (self.0 as u32 * SAMPLE_RATE) >> 16
+    }
+}
+
+/// A note of the equal-tempered scale (A4 = 440Hz).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Note {
+    pitch: Pitch,
+    octave: u8,
+}
+
+impl Note {
+    /// Create a new note.
+    ///
+    /// # Arguments
+    ///
+    /// * `pitch` - The pitch class of the note.
+    /// * `octave` - The octave of the note (scientific pitch notation, C4 is the middle C),
+    ///   saturated to [`MAX_OCTAVE`].
+    pub const fn new(pitch: Pitch, octave: u8) -> Self {
+        let octave = if octave > MAX_OCTAVE {
+            MAX_OCTAVE
+        } else {
+            octave
+        };
+        Self { pitch, octave }
+    }
+
+    /// Get the pitch class of the note.
+    #[inline(always)]
+    pub const fn pitch(&self) -> Pitch {
+        self.pitch
+    }
+
+    /// Get the octave of the note.
+    #[inline(always)]
+    pub const fn octave(&self) -> u8 {
+        self.octave
+    }
+
+    /// Get the frequency of the note.
+    pub const fn frequency(&self) -> Frequency {
+        // Q4 steps of the 8th octave, halved for each octave below (and rounded)
+        let shift = 4 + 8 - self.octave as u32;
+        let step = OCTAVE8_STEPS[self.pitch as usize];
+        Frequency::from_step(((step + (1 << (shift - 1))) >> shift) as u16)
+    }
+}
+
+impl From<Note> for Frequency {
+    fn from(note: Note) -> Self {
+        note.frequency()
+    }
+}
+
+/// ADSR envelope of a voice.
+///
+/// Rates are between 0 (fastest) and 15 (slowest): each rate N moves the level by one step every
+/// 16*N+1 samples, so a full sweep of the level (256 steps) lasts from ~5ms to ~1.3s.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Envelope {
+    /// Attack rate (0-15).
+    pub attack: u8,
+    /// Decay rate (0-15).
+    pub decay: u8,
+    /// Sustain level (0-15), a sustain level of 0 ends the note after the decay.
+    pub sustain: u8,
+    /// Release rate (0-15).
+    pub release: u8,
+}
+
+impl Envelope {
+    /// Full level as long as the gate is open (no attack, no decay, no release).
+    pub const FLAT: Self = Self::new(0, 0, 15, 0);
+    /// Short one-shot sound, ending by itself (UI clicks and beeps).
+    pub const BLIP: Self = Self::new(0, 1, 0, 1);
+    /// Plucked string, ending by itself.
+    pub const PLUCK: Self = Self::new(0, 4, 0, 4);
+    /// Soft attack and release, held while the gate is open.
+    pub const PAD: Self = Self::new(4, 2, 10, 6);
+
+    /// Create a new envelope, values are masked to 4 bits.
+    pub const fn new(attack: u8, decay: u8, sustain: u8, release: u8) -> Self {
+        Self {
+            attack: attack & 0xF,
+            decay: decay & 0xF,
+            sustain: sustain & 0xF,
+            release: release & 0xF,
+        }
+    }
+}
+
+/// Sound Generator peripheral.
+///
+/// The voices can be taken out of it, and used independently.
+pub struct SoundGen {
+    voice0: Option<Voice>,
+    voice1: Option<Voice>,
+    voice2: Option<Voice>,
+}
+
+impl SoundGen {
+    /// Creates a new instance of the Sound Generator peripheral.
+    pub(crate) fn new() -> Self {
+        Self {
+            voice0: Some(Voice::new(0)),
+            voice1: Some(Voice::new(1)),
+            voice2: Some(Voice::new(2)),
+        }
+    }
+
+    /// Enable the Sound Generator (the voices are mixed into the audio output).
+    #[inline(always)]
+    pub fn enable(&mut self) {
+        // Safety: Only the SoundGen owns the CONTROL register.
+        unsafe {
+            let sound = pac::SoundGen::steal();
+            sound.control().write_with_zero(|w| w.enable().set_bit());
+        }
+    }
+
+    /// Disable the Sound Generator (the audio output is left untouched).
+    #[inline(always)]
+    pub fn disable(&mut self) {
+        // Safety: Only the SoundGen owns the CONTROL register.
+        unsafe {
+            let sound = pac::SoundGen::steal();
+            sound.control().write_with_zero(|w| w.enable().clear_bit());
+        }
+    }
+
+    /// Check if any voice is still audible (its envelope is running).
+    #[inline(always)]
+    pub fn is_active(&self) -> bool {
+        // Safety: Only used for reading the voices status.
+        let sound = unsafe { pac::SoundGen::steal() };
+        sound.control().read().active_iter().any(|a| a.bit_is_set())
+    }
+
+    /// Take the first voice (if not already taken).
+    pub fn take_voice0(&mut self) -> Option<Voice> {
+        self.voice0.take()
+    }
+
+    /// Take the second voice (if not already taken).
+    pub fn take_voice1(&mut self) -> Option<Voice> {
+        self.voice1.take()
+    }
+
+    /// Take the third voice (if not already taken).
+    pub fn take_voice2(&mut self) -> Option<Voice> {
+        self.voice2.take()
+    }
+
+    /// Take all the voices (if none of them was already taken).
+    pub fn take_all_voices(&mut self) -> Option<(Voice, Voice, Voice)> {
+        if self.voice0.is_some() && self.voice1.is_some() && self.voice2.is_some() {
+            Some((
+                self.voice0.take()?,
+                self.voice1.take()?,
+                self.voice2.take()?,
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// A voice of the Sound Generator.
+///
+/// The voice registers are write-only, the waveform and volume are kept here so that the gate
+/// can be changed without resetting them.
+pub struct Voice {
+    id: u8,
+    waveform: Waveform,
+    volume: u8,
+    gate: bool,
+}
+
+impl Voice {
+    fn new(id: u8) -> Self {
+        Self {
+            id,
+            waveform: Waveform::Square,
+            volume: 0,
+            gate: false,
+        }
+    }
+
+    /// Index of the voice (0-2).
+    #[inline(always)]
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Get the register block of this voice.
+    #[inline(always)]
+    fn regs(&self) -> &'static pac::sound_gen::Ch {
+        // Safety: Each voice only accesses its own registers.
+        unsafe { (*pac::SoundGen::ptr()).ch(self.id as usize) }
+    }
+
+    /// Write the CONTROL register of the voice.
+    #[inline(always)]
+    fn write_control(&mut self) {
+        let (waveform, volume, gate) = (self.waveform as u8, self.volume, self.gate);
+        // Safety: Each voice only accesses its own registers.
+        unsafe {
+            self.regs().control().write_with_zero(|w| {
+                w.waveform().bits(waveform);
+                w.gate().bit(gate);
+                w.volume().bits(volume)
+            });
+        }
+    }
+
+    /// Set the frequency of the voice, it takes effect immediately (even while a note plays).
+    ///
+    /// # Arguments
+    ///
+    /// * `freq` - The frequency, or a [`Note`].
+    #[inline(always)]
+    pub fn set_frequency(&mut self, freq: impl Into<Frequency>) {
+        let step = freq.into().step();
+        // Safety: Each voice only accesses its own registers.
+        unsafe {
+            self.regs().freq().write_with_zero(|w| w.step().bits(step));
+        }
+    }
+
+    /// Set the waveform of the voice.
+    pub fn set_waveform(&mut self, waveform: Waveform) {
+        self.waveform = waveform;
+        self.write_control();
+    }
+
+    /// Set the volume of the voice (0-255).
+    pub fn set_volume(&mut self, volume: u8) {
+        self.volume = volume;
+        self.write_control();
+    }
+
+    /// Set the ADSR envelope of the voice.
+    pub fn set_envelope(&mut self, envelope: Envelope) {
+        // Safety: Each voice only accesses its own registers.
+        unsafe {
+            self.regs().envelope().write_with_zero(|w| {
+                w.attack().bits(envelope.attack);
+                w.decay().bits(envelope.decay);
+                w.sustain().bits(envelope.sustain);
+                w.release().bits(envelope.release)
+            });
+        }
+    }
+
+    /// Open the gate: (re)start the envelope attack.
+    ///
+    /// If the gate is already open, it is closed first so the envelope restarts.
+    pub fn note_on(&mut self) {
+        if self.gate {
+            self.gate = false;
+            self.write_control();
+        }
+        self.gate = true;
+        self.write_control();
+    }
+
+    /// Close the gate: start the envelope release.
+    pub fn note_off(&mut self) {
+        self.gate = false;
+        self.write_control();
+    }
+
+    /// Play a note: set the frequency and open the gate.
+    ///
+    /// # Arguments
+    ///
+    /// * `freq` - The frequency, or a [`Note`].
+    pub fn play(&mut self, freq: impl Into<Frequency>) {
+        self.set_frequency(freq);
+        self.note_on();
+    }
+
+    /// Check if the voice is still audible (its envelope is running).
+    #[inline(always)]
+    pub fn is_active(&self) -> bool {
+        // Safety: Only used for reading the voices status.
+        let sound = unsafe { pac::SoundGen::steal() };
+        sound.control().read().active(self.id).bit_is_set()
+    }
+}
@@ -1,22 +1,113 @@
+//! SPI module
+//! This module provides the drivers of the SPI masters (SPI0 and SPI1), and a software SPI.
+//!
+//! The SPI masters have TX and RX FIFOs of [`FIFO_SIZE`] bytes: the bytes are queued while the
+//! previous ones are shifted, and sent back-to-back by the hardware. A transfer keeps at most
+//! [`FIFO_SIZE`] bytes in flight, so that no received byte is dropped.
+//!
+//! The SPI clock, mode and bit order of the SPI masters are set at runtime with a [`Config`]
+//! (see [`Spi::reconfigure`]): the clock is divided from the reference clock of the master, e.g.
+//! an SDCard is initialized at 400kHz, then read at full speed.
+//!
+//! The waits for the hardware SPI masters are bounded by [`TIMEOUT`] (measured with Timer0), plus
+//! the time of a full FIFO at the configured clock: a wedged peripheral gives an [`Error`] instead
+//! of hanging the application.
+//!
+//! The software SPI ([`SpiSoft`]) bit-bangs any `embedded-hal` pins with the same [`Config`], and
+//! [`SpiSoftDevice`] adds a chip select to it, e.g. for extra SPI devices on the header pins.
+
 use core::convert::Infallible;
 
-use crate::gpio::{InputCapablePin, OutputCapablePin, SpiPins};
+use crate::executor;
+use crate::gpio::{InputCapablePin, OutputCapablePin, Pin, SpiPins};
 use crate::pac::{self, Spi0 as PacSpi0, Spi1 as PacSpi1};
+use crate::timer::{Duration, Timer};
 use crate::typesafe::Sealed;
 use embedded_hal::delay::DelayNs;
-use embedded_hal::spi::{ErrorType, SpiBus};
+use embedded_hal::digital::{InputPin, OutputPin, PinState};
+use embedded_hal::spi::{ErrorType, Mode, Operation, Phase, Polarity, SpiBus, SpiDevice};
+
+/// Longest wait for the SPI peripheral, plus the time of a full FIFO at the configured clock.
+pub const TIMEOUT: Duration = Duration::millis(10);
+
+/// Size of the TX and RX FIFOs, in bytes.
+pub const FIFO_SIZE: usize = 8;
+
+/// Error of the hardware SPI masters.
+///
+/// A generic driver only sees [`ErrorKind::Other`](embedded_hal::spi::ErrorKind::Other) (see
+/// [`Error::kind`](embedded_hal::spi::Error::kind)): match on this error to handle it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// The peripheral was still busy after its reset: it is not usable.
+    NotReady,
+    /// A transfer did not complete in time: the master is stuck, the bytes in flight are lost.
+    /// Resetting the master ([`Spi::initialize`]) recovers it.
+    Timeout,
+}
+
+impl embedded_hal::spi::Error for Error {
+    /// Both errors are [`ErrorKind::Other`](embedded_hal::spi::ErrorKind::Other): the other
+    /// kinds are errors of the bus (overrun, mode fault, frame format, chip select) that the
+    /// master cannot detect, neither a timeout nor a peripheral not ready is one of them.
+    fn kind(&self) -> embedded_hal::spi::ErrorKind {
+        match self {
+            Error::NotReady | Error::Timeout => embedded_hal::spi::ErrorKind::Other,
+        }
+    }
+}
+
+/// Bit order of the bytes on the SPI bus.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BitOrder {
+    /// Most significant bit first (the usual order)
+    MsbFirst,
+    /// Least significant bit first
+    LsbFirst,
+}
+
+/// Configuration of the hardware SPI masters.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Config {
+    /// Highest SPI clock frequency, in Hz: the closest rate below is used (see
+    /// [`Spi::frequency`]), down to the reference clock of the master / 131072.
+    pub frequency: u32,
+    /// SPI mode (clock polarity and phase).
+    pub mode: Mode,
+    /// Bit order of the bytes.
+    pub bit_order: BitOrder,
+}
+
+impl Config {
+    /// Default configuration: the fastest clock (half the reference clock), mode 0, MSB first.
+    pub const DEFAULT: Self = Self {
+        frequency: u32::MAX,
+        mode: embedded_hal::spi::MODE_0,
+        bit_order: BitOrder::MsbFirst,
+    };
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
 
 pub trait SpiPeripheral: Sealed + 'static {
+    /// Frequency of the reference clock of the SPI master, in Hz (the SPI clock is at most half
+    /// of it).
+    const REFERENCE_CLOCK_HZ: u32;
+
     /// Get the register block for SPI
     fn get_perif() -> &'static pac::spi0::RegisterBlock;
-
-    /// Inter-symbol delay in nanoseconds for SPI operations
-    fn get_inter_symbol_delay(&self) -> u32;
 }
 
 pub struct Spi<P: SpiPeripheral, D: DelayNs> {
     peripheral: P,
     delayer: D,
+    config: Config,
+    /// Longest wait for the peripheral, at the configured clock
+    timeout: Duration,
 }
 
 pub struct Spi0 {
@@ -31,17 +122,15 @@ impl Spi0 {
 
 impl Sealed for Spi0 {}
 impl SpiPeripheral for Spi0 {
+    /// 80MHz (SPI clock up to 40MHz, for the SDCard)
+    const REFERENCE_CLOCK_HZ: u32 = 80_000_000;
+
     #[inline(always)]
     fn get_perif() -> &'static pac::spi0::RegisterBlock {
         // Safety: Only used for reading/writing SPI0 registers
         // Spi0 is a singleton peripheral
         unsafe { &*PacSpi0::ptr() }
     }
-
-    #[inline(always)]
-    fn get_inter_symbol_delay(&self) -> u32 {
-        100 // 100ns inter-symbol delay - suitable for 40MHz SPI clock
-    }
 }
 
 pub struct Spi1 {
@@ -56,17 +145,15 @@ impl Spi1 {
 
 impl Sealed for Spi1 {}
 impl SpiPeripheral for Spi1 {
+    /// 20MHz (SPI clock up to 10MHz, for the OLED display)
+    const REFERENCE_CLOCK_HZ: u32 = 20_000_000;
+
     #[inline(always)]
     fn get_perif() -> &'static pac::spi0::RegisterBlock {
         // Safety: Only used for reading/writing SPI1 registers
         // Spi1 is a singleton peripheral
         unsafe { &*PacSpi1::ptr() }
     }
-
-    #[inline(always)]
-    fn get_inter_symbol_delay(&self) -> u32 {
-        400 // 400ns inter-symbol delay - suitable for 10MHz SPI clock
-    }
 }
 
 impl<P, D> Spi<P, D>
@@ -78,24 +165,208 @@ where
         Self {
             peripheral,
             delayer,
+            config: Config::DEFAULT,
+            timeout: TIMEOUT,
         }
     }
 
-    /// Initialize the SPI peripheral with default settings
-    pub fn initialize(&mut self) {
+    /// Initialize the SPI peripheral, with the current configuration ([`Config::DEFAULT`] unless
+    /// reconfigured)
+    ///
+    /// # Returns
+    ///
+    /// [`Error::NotReady`] if the peripheral is still busy after its reset.
+    pub fn initialize(&mut self) -> Result<(), Error> {
         // The SPI hardware peripheral needs to be hold in reset (for 3 microseconds)
         // before being used. This is what this initialization function does.
         let spi = P::get_perif();
-        // Put SPI peripheral into reset
+        // Put SPI peripheral into reset (this also clears the FIFOs)
         spi.ctrl().write(|w| w.reset().set_bit());
         self.delayer.delay_ms(3);
         // Release SPI peripheral from reset
         spi.ctrl().write(|w| w.reset().clear_bit());
+        self.wait_for(Error::NotReady, Self::poll_idle)?;
+        self.apply_config();
+        Ok(())
+    }
+
+    /// Change the configuration (clock, mode and bit order), once the ongoing transfers are over.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The new configuration.
+    ///
+    /// # Returns
+    ///
+    /// [`Error::Timeout`] if the ongoing transfers did not complete in time (the configuration
+    /// is then unchanged).
+    pub fn reconfigure(&mut self, config: Config) -> Result<(), Error> {
+        self.flush()?; // The configuration must only be changed while idle
+        self.config = config;
+        self.apply_config();
+        Ok(())
+    }
+
+    /// Get the current configuration.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Get the actual SPI clock frequency, in Hz (at most the configured one).
+    pub fn frequency(&self) -> u32 {
+        let divider = Self::divider(self.config.frequency) as u32;
+        P::REFERENCE_CLOCK_HZ / (2 * (divider + 1))
     }
 
     pub fn bring_down(self) -> P {
         self.peripheral
     }
+
+    /// Compute the clock divider of the highest SPI clock at most at a frequency.
+    ///
+    /// SPI_CLK = REFERENCE_CLOCK_HZ / (2 * (divider + 1))
+    #[inline(always)]
+    fn divider(frequency: u32) -> u16 {
+        // Saturating: any frequency above the reference clock gives the fastest clock
+        let half_period = frequency.max(1).saturating_mul(2);
+        let divider = P::REFERENCE_CLOCK_HZ.div_ceil(half_period);
+        divider.saturating_sub(1).min(u16::MAX as u32) as u16
+    }
+
+    /// Write the configuration to the peripheral (it must be idle), and update the timeout.
+    fn apply_config(&mut self) {
+        let divider = Self::divider(self.config.frequency);
+        let Config {
+            mode, bit_order, ..
+        } = self.config;
+        P::get_perif().ctrl().write(|w| unsafe {
+            w.cpol()
+                .bit(mode.polarity == Polarity::IdleHigh)
+                .cpha()
+                .bit(mode.phase == Phase::CaptureOnSecondTransition)
+                .lsb_first()
+                .bit(bit_order == BitOrder::LsbFirst)
+                .divider()
+                .bits(divider)
+        });
+        // A byte takes 16 half-periods of the SPI clock, the peripheral may have a full FIFO
+        // (the reference clocks are whole MHz, this stays in 32 bits)
+        let byte_micros = 16 * (divider as u32 + 1) / (P::REFERENCE_CLOCK_HZ / 1_000_000);
+        self.timeout = TIMEOUT + Duration::micros(((byte_micros + 1) * FIFO_SIZE as u32) as u64);
+    }
+
+    /// Check if the SPI peripheral is idle (no transfer ongoing, and the TX FIFO is empty).
+    #[inline(always)]
+    fn poll_idle() -> Option<()> {
+        P::get_perif()
+            .status()
+            .read()
+            .busy()
+            .bit_is_clear()
+            .then_some(())
+    }
+
+    /// Check if the TX FIFO has free slots.
+    ///
+    /// # Returns
+    ///
+    /// The number of free slots, if any.
+    #[inline(always)]
+    fn poll_tx_space() -> Option<usize> {
+        let level = P::get_perif().fifo_status().read().tx_count().bits() as usize;
+        (level < FIFO_SIZE).then_some(FIFO_SIZE - level)
+    }
+
+    /// Pop a byte from the RX FIFO, if any.
+    #[inline(always)]
+    fn poll_rx() -> Option<u8> {
+        let rx = P::get_perif().rx_data().read();
+        rx.valid().bit_is_set().then(|| rx.data().bits())
+    }
+
+    /// Push a byte to the TX FIFO (the caller checks that it is not full).
+    #[inline(always)]
+    fn push(word: u8) {
+        P::get_perif()
+            .write_data()
+            .write(|w: &mut pac::spi0::write_data::W| unsafe { w.bits(word) });
+    }
+
+    /// Drop the bytes received by the previous writes (the peripheral must be idle).
+    #[inline(always)]
+    fn clear_rx() {
+        // Keep the configuration
+        P::get_perif().ctrl().modify(|_, w| w.rx_clear().set_bit());
+    }
+
+    /// Wait until a condition is met, for at most the timeout ([`TIMEOUT`] plus the time of a
+    /// full FIFO).
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error to return if the condition is still not met after the timeout.
+    /// * `poll` - The condition, giving a value once met.
+    #[inline(always)]
+    fn wait_for<T>(&self, error: Error, mut poll: impl FnMut() -> Option<T>) -> Result<T, Error> {
+        // Fast path: the condition is usually met, do not read the timer
+        if let Some(value) = poll() {
+            return Ok(value);
+        }
+        let timer = Timer::new_timer0();
+        let start = timer.get_counter();
+        loop {
+            if let Some(value) = poll() {
+                return Ok(value);
+            }
+            if timer.elapsed(start) > self.timeout {
+                return Err(error);
+            }
+        }
+    }
+
+    /// Transfer bytes back-to-back, with at most [`FIFO_SIZE`] bytes in flight.
+    ///
+    /// A single copy serves all the transfers of a master (read, transfer and in place).
+    ///
+    /// # Arguments
+    ///
+    /// * `read` - Takes the received bytes (the extra received bytes are dropped).
+    /// * `write` - The bytes to send (zeros once they run out), or `None` to send the bytes of
+    ///   `read` (in place).
+    #[inline(never)]
+    fn transfer_with(&mut self, read: &mut [u8], write: Option<&[u8]>) -> Result<(), Error> {
+        let len = write.map_or(read.len(), |write| write.len().max(read.len()));
+        self.flush()?; // Ensure no other transfers are ongoing
+        Self::clear_rx();
+        let mut sent = 0;
+        for i in 0..len {
+            // Keep the TX FIFO fed, the RX FIFO cannot overflow
+            while sent < len && sent - i < FIFO_SIZE {
+                Self::push(tx_byte(read, write, sent));
+                sent += 1;
+            }
+            rx_byte(read, i, self.wait_for(Error::Timeout, Self::poll_rx)?);
+        }
+        Ok(())
+    }
+}
+
+/// Get the byte to send at an index of a transfer (see [`Spi::transfer_with`]): in place, a byte
+/// is sent before being overwritten by the received one.
+#[inline(always)]
+fn tx_byte(read: &[u8], write: Option<&[u8]>, index: usize) -> u8 {
+    match write {
+        Some(write) => write.get(index).copied().unwrap_or(0),
+        None => read[index],
+    }
+}
+
+/// Store the byte received at an index of a transfer, if there is room for it.
+#[inline(always)]
+fn rx_byte(read: &mut [u8], index: usize, word: u8) {
+    if let Some(slot) = read.get_mut(index) {
+        *slot = word;
+    }
 }
 
 impl<P, D> ErrorType for Spi<P, D>
@@ -103,7 +374,7 @@ where
     P: SpiPeripheral,
     D: DelayNs,
 {
-    type Error = Infallible;
+    type Error = Error;
 }
 
 impl<P, D> SpiBus<u8> for Spi<P, D>
@@ -114,179 +385,435 @@ where
     #[inline(always)]
     fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
         // Unfortunately, SPI peripheral requires writing to initiate reads
-        for word in words.iter_mut() {
-            *word = 0;
-        }
-        // Initiate the read by transferring the dummy data
-        return self.transfer_in_place(words);
+        self.transfer_with(words, Some(&[]))
     }
 
     #[inline(always)]
     fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
-        let spi = P::get_perif();
-        for &word in words {
-            self.flush()?; // Ensure no other transfers are ongoing
-            // Write the Tx byte to send, this will also start the transfer
-            spi.write_data()
-                .write(|w: &mut pac::spi0::write_data::W| unsafe { w.bits(word) });
-            self.delayer
-                .delay_ns(self.peripheral.get_inter_symbol_delay());
+        // The received bytes are dropped (cleared by the next read)
+        let mut words = words;
+        while !words.is_empty() {
+            // Push as many bytes as the TX FIFO can take
+            let space = self.wait_for(Error::Timeout, Self::poll_tx_space)?;
+            let (now, later) = words.split_at(space.min(words.len()));
+            for &word in now {
+                Self::push(word);
+            }
+            words = later;
         }
         Ok(())
     }
 
     #[inline(always)]
     fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
-        let spi = P::get_perif();
-        let len = read.len().min(write.len());
-        self.flush()?; // Ensure no other transfers are ongoing
+        // The missing bytes to send are zeros, the extra received bytes are dropped
+        self.transfer_with(read, Some(write))
+    }
+
+    #[inline(always)]
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.transfer_with(words, None)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // Flush -- ensure the SPI peripheral is idle (the TX FIFO is empty)
+        self.wait_for(Error::Timeout, Self::poll_idle)
+    }
+}
+
+impl<P, D> Spi<P, D>
+where
+    P: SpiPeripheral,
+    D: DelayNs,
+{
+    /// Wait until a condition is met, for at most the timeout (see [`Spi::wait_for`]), yielding
+    /// to the other tasks meanwhile.
+    ///
+    /// # Arguments
+    ///
+    /// * `poll` - The condition, giving a value once met.
+    async fn wait_for_async<T>(&self, mut poll: impl FnMut() -> Option<T>) -> Result<T, Error> {
+        if let Some(value) = poll() {
+            return Ok(value);
+        }
+        let timer = Timer::new_timer0();
+        let start = timer.get_counter();
+        let mut value = None;
+        executor::poll_until(|| {
+            value = poll();
+            value.is_some() || timer.elapsed(start) > self.timeout
+        })
+        .await;
+        // The other tasks may have run for longer than the timeout, check the peripheral again
+        value.or_else(poll).ok_or(Error::Timeout)
+    }
+
+    /// Transfer bytes back-to-back, yielding to the other tasks while waiting for the received
+    /// bytes (see [`Spi::transfer_with`]).
+    async fn transfer_with_async(
+        &mut self,
+        read: &mut [u8],
+        write: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        let len = write.map_or(read.len(), |write| write.len().max(read.len()));
+        self.wait_for_async(Self::poll_idle).await?; // Ensure no other transfers are ongoing
+        Self::clear_rx();
+        let mut sent = 0;
         for i in 0..len {
-            // Write the Tx byte to send, this will also start the transfer
-            spi.write_data()
-                .write(|w: &mut pac::spi0::write_data::W| unsafe { w.bits(write[i]) });
-            self.delayer
-                .delay_ns(self.peripheral.get_inter_symbol_delay());
-            // Wait until data is ready
-            loop {
-                let read_and_status = spi.read_and_status().read();
-                if read_and_status.busy().bit_is_clear() {
-                    // Read the received byte once ready
-                    read[i] = read_and_status.data().bits();
-                    break;
-                }
+            while sent < len && sent - i < FIFO_SIZE {
+                Self::push(tx_byte(read, write, sent));
+                sent += 1;
             }
-        }
-        // Handle remaining bytes if read and write lengths differ
-        if read.len() > len {
-            self.read(&mut read[len..])?;
-        } else {
-            self.write(&write[len..])?;
+            rx_byte(read, i, self.wait_for_async(Self::poll_rx).await?);
         }
         Ok(())
     }
+}
+
+/// Async SPI bus (for the [`crate::executor`]): the task yields while a transfer is ongoing.
+impl<P, D> embedded_hal_async::spi::SpiBus<u8> for Spi<P, D>
+where
+    P: SpiPeripheral,
+    D: DelayNs,
+{
+    async fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        // Unfortunately, SPI peripheral requires writing to initiate reads
+        self.transfer_with_async(words, Some(&[])).await
+    }
 
-    #[inline(always)]
-    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
-        let spi = P::get_perif();
-        self.flush()?; // Ensure no other transfers are ongoing
-        for word in words.iter_mut() {
-            // Write the Tx byte to send, this will also start the transfer
-            spi.write_data()
-                .write(|w: &mut pac::spi0::write_data::W| unsafe { w.bits(*word) });
-            // Wait a bit for the first bits to be sent
-            self.delayer
-                .delay_ns(self.peripheral.get_inter_symbol_delay());
-            // Wait until data is ready
-            loop {
-                let read_and_status = spi.read_and_status().read();
-                if read_and_status.busy().bit_is_clear() {
-                    // Read the received byte once ready
-                    *word = read_and_status.data().bits();
-                    break;
-                }
+    async fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        let mut words = words;
+        while !words.is_empty() {
+            let space = self.wait_for_async(Self::poll_tx_space).await?;
+            let (now, later) = words.split_at(space.min(words.len()));
+            for &word in now {
+                Self::push(word);
             }
+            words = later;
         }
         Ok(())
     }
 
-    #[inline(always)]
-    fn flush(&mut self) -> Result<(), Self::Error> {
-        // Flush -- ensure the SPI peripheral status is not busy
-        let spi = P::get_perif();
-        self.delayer
-            .delay_ns(self.peripheral.get_inter_symbol_delay());
-        while spi.status().read().busy().bit_is_set() {}
-        Ok(())
+    async fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        self.transfer_with_async(read, Some(write)).await
+    }
+
+    async fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.transfer_with_async(words, None).await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        // Flush -- ensure the SPI peripheral is idle (the TX FIFO is empty)
+        self.wait_for_async(Self::poll_idle).await
+    }
+}
+
+/// SPI device over a whole hardware SPI bus, for a device whose chip select is driven by its
+/// driver (e.g. the OLED display): the bus is not shared, there is no chip select to drive.
+pub struct SpiBusDevice<P: SpiPeripheral, D: DelayNs>(Spi<P, D>);
+
+impl<P, D> SpiBusDevice<P, D>
+where
+    P: SpiPeripheral,
+    D: DelayNs,
+{
+    pub fn new(spi: Spi<P, D>) -> Self {
+        Self(spi)
+    }
+
+    pub fn bring_down(self) -> Spi<P, D> {
+        self.0
+    }
+}
+
+impl<P, D> ErrorType for SpiBusDevice<P, D>
+where
+    P: SpiPeripheral,
+    D: DelayNs,
+{
+    type Error = Error;
+}
+
+impl<P, D> SpiDevice<u8> for SpiBusDevice<P, D>
+where
+    P: SpiPeripheral,
+    D: DelayNs,
+{
+    /// Run the operations.
+    ///
+    /// On a [`Error::Timeout`], the master is reset before returning the error, so that the next
+    /// transactions go through (the device driver may ignore the error, e.g. while drawing).
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Error> {
+        let spi = &mut self.0;
+        let result = operations
+            .iter_mut()
+            .try_for_each(|operation| match operation {
+                Operation::Read(words) => spi.read(words),
+                Operation::Write(words) => spi.write(words),
+                Operation::Transfer(read, write) => spi.transfer(read, write),
+                Operation::TransferInPlace(words) => spi.transfer_in_place(words),
+                Operation::DelayNs(ns) => {
+                    spi.flush()?;
+                    spi.delayer.delay_ns(*ns);
+                    Ok(())
+                }
+            });
+        let result = result.and_then(|()| spi.flush());
+        if result == Err(Error::Timeout) {
+            let _ = spi.initialize();
+        }
+        result
+    }
+}
+
+/// Error of the software SPI: an error of one of its pins, each pin with its own error type
+/// (the chip select only for a [`SpiSoftDevice`]).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PinError<C, O, I, S = Infallible> {
+    /// Error of the clock output
+    Clk(C),
+    /// Error of the data output (MOSI)
+    Mosi(O),
+    /// Error of the data input (MISO)
+    Miso(I),
+    /// Error of the chip select output
+    Cs(S),
+}
+
+impl<C, O, I> PinError<C, O, I> {
+    /// Turn an error of the bus into an error of a device (the bus has no chip select).
+    fn into_device<S>(self) -> PinError<C, O, I, S> {
+        match self {
+            PinError::Clk(e) => PinError::Clk(e),
+            PinError::Mosi(e) => PinError::Mosi(e),
+            PinError::Miso(e) => PinError::Miso(e),
+            PinError::Cs(never) => match never {},
+        }
+    }
+}
+
+impl<C, O, I, S> embedded_hal::spi::Error for PinError<C, O, I, S>
+where
+    C: core::fmt::Debug,
+    O: core::fmt::Debug,
+    I: core::fmt::Debug,
+    S: core::fmt::Debug,
+{
+    /// An error of the chip select is a
+    /// [`ChipSelectFault`](embedded_hal::spi::ErrorKind::ChipSelectFault), the errors of the
+    /// other pins are [`Other`](embedded_hal::spi::ErrorKind::Other) errors.
+    fn kind(&self) -> embedded_hal::spi::ErrorKind {
+        match self {
+            PinError::Cs(_) => embedded_hal::spi::ErrorKind::ChipSelectFault,
+            _ => embedded_hal::spi::ErrorKind::Other,
+        }
     }
 }
 
+/// Error of a [`SpiSoft`] bus over its pins.
+pub type BusError<CLK, MOSI, MISO> = PinError<
+    <CLK as embedded_hal::digital::ErrorType>::Error,
+    <MOSI as embedded_hal::digital::ErrorType>::Error,
+    <MISO as embedded_hal::digital::ErrorType>::Error,
+>;
+
+/// Error of a [`SpiSoftDevice`] over its pins.
+pub type DeviceError<CLK, MOSI, MISO, CS> = PinError<
+    <CLK as embedded_hal::digital::ErrorType>::Error,
+    <MOSI as embedded_hal::digital::ErrorType>::Error,
+    <MISO as embedded_hal::digital::ErrorType>::Error,
+    <CS as embedded_hal::digital::ErrorType>::Error,
+>;
+
 /// Software (bit-banged) SPI implementation
 /// Uses embedded-hal traits for GPIO pins and delay
 ///
-/// Assumes Mode 0 (CPOL=0, CPHA=0) operation
-/// SPI frequency assumes to be 400kHz - one symbol is 2.5us
+/// Works with any pins: the clock and MOSI outputs, and the MISO input (e.g. a `Pin<NeverPin>`
+/// for a write-only device). The SPI mode and the bit order are those of the [`Config`], and the
+/// half-periods of the clock are delays derived from its frequency: the frequency is an upper
+/// bound, the time of the pin accesses comes on top.
 pub struct SpiSoft<CLK, MOSI, MISO, DELAYER>
 where
-    CLK: OutputCapablePin,
-    MOSI: OutputCapablePin,
-    MISO: InputCapablePin,
-    DELAYER: embedded_hal::delay::DelayNs,
+    CLK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    DELAYER: DelayNs,
 {
-    spi: SpiPins<MOSI, CLK, MISO>,
+    clk: CLK,
+    mosi: MOSI,
+    miso: MISO,
     delay: DELAYER,
+    config: Config,
+    /// Half-period of the SPI clock, in nanoseconds
+    half_period_ns: u32,
 }
 
 impl<CLK, MOSI, MISO, DELAYER> SpiSoft<CLK, MOSI, MISO, DELAYER>
 where
-    CLK: OutputCapablePin,
-    MOSI: OutputCapablePin,
-    MISO: InputCapablePin,
-    DELAYER: embedded_hal::delay::DelayNs,
+    CLK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    DELAYER: DelayNs,
 {
-    pub fn new(spi: SpiPins<MOSI, CLK, MISO>, delay: DELAYER) -> Self {
-        Self { spi, delay }
+    /// Create a software SPI, and set the clock to its idle level.
+    ///
+    /// # Arguments
+    ///
+    /// * `clk` - The clock output.
+    /// * `mosi` - The data output.
+    /// * `miso` - The data input.
+    /// * `delay` - The delayer, timing the clock.
+    /// * `config` - The configuration (clock frequency, mode and bit order).
+    pub fn new(
+        clk: CLK,
+        mosi: MOSI,
+        miso: MISO,
+        delay: DELAYER,
+        config: Config,
+    ) -> Result<Self, BusError<CLK, MOSI, MISO>> {
+        let mut spi = Self {
+            clk,
+            mosi,
+            miso,
+            delay,
+            config,
+            half_period_ns: 0,
+        };
+        spi.reconfigure(config)?;
+        Ok(spi)
+    }
+
+    /// Change the configuration (clock frequency, mode and bit order), and set the clock to its
+    /// new idle level.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The new configuration.
+    pub fn reconfigure(&mut self, config: Config) -> Result<(), BusError<CLK, MOSI, MISO>> {
+        self.config = config;
+        // Half a period, rounded up (so that the clock is at most at the frequency), in 32 bits:
+        // 1e9 fits, and so does twice any frequency (saturated, the shortest half-period is 1ns)
+        let half_frequency = config.frequency.max(1).saturating_mul(2);
+        self.half_period_ns = 1_000_000_000u32.div_ceil(half_frequency);
+        self.set_clk(false)
+    }
+
+    /// Get the current configuration.
+    pub fn config(&self) -> &Config {
+        &self.config
     }
 
-    /// Consume the software SPI and return the underlying pins
-    pub fn bring_down(self) -> SpiPins<MOSI, CLK, MISO> {
-        self.spi
+    /// Consume the software SPI and return the underlying pins and delayer
+    pub fn bring_down(self) -> (CLK, MOSI, MISO, DELAYER) {
+        (self.clk, self.mosi, self.miso, self.delay)
+    }
+
+    /// Set the clock to its active level, or back to its idle level (the clock polarity).
+    #[inline(always)]
+    fn set_clk(&mut self, active: bool) -> Result<(), BusError<CLK, MOSI, MISO>> {
+        let idle_high = self.config.mode.polarity == Polarity::IdleHigh;
+        self.clk
+            .set_state(PinState::from(active != idle_high))
+            .map_err(PinError::Clk)
     }
 
     /// Transfer a single byte over SPI and return the received byte
     #[inline(always)]
-    fn transfer_byte(&mut self, byte: u8) -> u8 {
-        // 400kHz SPI: 2.5us per bit = 1.25us high, 1.25us low
+    fn transfer_byte(&mut self, byte: u8) -> Result<u8, BusError<CLK, MOSI, MISO>> {
+        let half_period_ns = self.half_period_ns;
+        let sample_first = self.config.mode.phase == Phase::CaptureOnFirstTransition;
         let mut received: u8 = 0;
-        for i in (0..8).rev() {
-            // Set CLK low and
-            self.spi.clk.set_low().unwrap();
-            // Set MOSI based on the current bit to send
-            if (byte & (1 << i)) != 0 {
-                self.spi.mosi.set_high().unwrap();
+        for i in 0..8 {
+            // Mask of the current bit, in the configured order
+            let mask = match self.config.bit_order {
+                BitOrder::MsbFirst => 0x80 >> i,
+                BitOrder::LsbFirst => 1 << i,
+            };
+            let bit = PinState::from(byte & mask != 0);
+            if sample_first {
+                // CPHA=0: set MOSI before the leading edge, sample MISO on it
+                self.mosi.set_state(bit).map_err(PinError::Mosi)?;
+                self.delay.delay_ns(half_period_ns);
+                self.set_clk(true)?;
+                if self.miso.is_high().map_err(PinError::Miso)? {
+                    received |= mask;
+                }
+                self.delay.delay_ns(half_period_ns);
+                self.set_clk(false)?;
             } else {
-                self.spi.mosi.set_low().unwrap();
-            }
-            self.delay.delay_ns(1250);
-            // Set CLK high to sample
-            self.spi.clk.set_high().unwrap();
-            // Read MISO
-            let miso_high = self.spi.miso.is_high().unwrap();
-            if miso_high {
-                received |= 1 << i;
+                // CPHA=1: set MOSI on the leading edge, sample MISO on the trailing edge
+                self.set_clk(true)?;
+                self.mosi.set_state(bit).map_err(PinError::Mosi)?;
+                self.delay.delay_ns(half_period_ns);
+                self.set_clk(false)?;
+                if self.miso.is_high().map_err(PinError::Miso)? {
+                    received |= mask;
+                }
+                self.delay.delay_ns(half_period_ns);
             }
-            self.delay.delay_ns(1250);
         }
-        received
+        Ok(received)
     }
 }
 
-impl<CLK, MOSI, MISO, DELAYER> ErrorType for SpiSoft<CLK, MOSI, MISO, DELAYER>
+impl<CLK, MOSI, MISO, DELAYER> SpiSoft<Pin<CLK>, Pin<MOSI>, Pin<MISO>, DELAYER>
 where
     CLK: OutputCapablePin,
     MOSI: OutputCapablePin,
     MISO: InputCapablePin,
-    DELAYER: embedded_hal::delay::DelayNs,
+    DELAYER: DelayNs,
 {
-    type Error = Infallible;
+    /// Create a software SPI on the SPI pins of the board (e.g. from
+    /// [`crate::gpio::Gpio::take_spi_sd`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `pins` - The SPI pins.
+    /// * `delay` - The delayer, timing the clock.
+    /// * `config` - The configuration (clock frequency, mode and bit order).
+    pub fn from_pins(
+        pins: SpiPins<MOSI, CLK, MISO>,
+        delay: DELAYER,
+        config: Config,
+    ) -> Result<Self, BusError<Pin<CLK>, Pin<MOSI>, Pin<MISO>>> {
+        Self::new(
+            Pin::new_output(pins.clk),
+            Pin::new_output(pins.mosi),
+            Pin::new_input(pins.miso),
+            delay,
+            config,
+        )
+    }
+}
+
+impl<CLK, MOSI, MISO, DELAYER> ErrorType for SpiSoft<CLK, MOSI, MISO, DELAYER>
+where
+    CLK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    DELAYER: DelayNs,
+{
+    type Error = BusError<CLK, MOSI, MISO>;
 }
 
 impl<CLK, MOSI, MISO, DELAYER> SpiBus<u8> for SpiSoft<CLK, MOSI, MISO, DELAYER>
 where
-    CLK: OutputCapablePin,
-    MOSI: OutputCapablePin,
-    MISO: InputCapablePin,
-    DELAYER: embedded_hal::delay::DelayNs,
+    CLK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    DELAYER: DelayNs,
 {
     fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
         for word in words.iter_mut() {
-            *word = self.transfer_byte(0x00);
+            *word = self.transfer_byte(0x00)?;
         }
         Ok(())
     }
 
     fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
         for &word in words {
-            self.transfer_byte(word);
+            self.transfer_byte(word)?;
         }
         Ok(())
     }
@@ -294,7 +821,7 @@ where
     fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
         let len = read.len().min(write.len());
         for i in 0..len {
-            read[i] = self.transfer_byte(write[i]);
+            read[i] = self.transfer_byte(write[i])?;
         }
         // Handle remaining bytes if read and write lengths differ
         if read.len() > len {
@@ -307,7 +834,7 @@ where
 
     fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
         for word in words.iter_mut() {
-            *word = self.transfer_byte(*word);
+            *word = self.transfer_byte(*word)?;
         }
         Ok(())
     }
@@ -317,3 +844,96 @@ where
         Ok(())
     }
 }
+
+/// Software SPI device: a [`SpiSoft`] bus, and the chip select (active low) of its device.
+///
+/// The chip select is asserted for the whole of each transaction.
+pub struct SpiSoftDevice<CLK, MOSI, MISO, CS, DELAYER>
+where
+    CLK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    CS: OutputPin,
+    DELAYER: DelayNs,
+{
+    bus: SpiSoft<CLK, MOSI, MISO, DELAYER>,
+    cs: CS,
+}
+
+impl<CLK, MOSI, MISO, CS, DELAYER> SpiSoftDevice<CLK, MOSI, MISO, CS, DELAYER>
+where
+    CLK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    CS: OutputPin,
+    DELAYER: DelayNs,
+{
+    /// Create a software SPI device, and deselect it.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - The software SPI bus.
+    /// * `cs` - The chip select output (active low).
+    pub fn new(
+        bus: SpiSoft<CLK, MOSI, MISO, DELAYER>,
+        mut cs: CS,
+    ) -> Result<Self, DeviceError<CLK, MOSI, MISO, CS>> {
+        cs.set_high().map_err(PinError::Cs)?;
+        Ok(Self { bus, cs })
+    }
+
+    /// Get the bus.
+    pub fn bus(&self) -> &SpiSoft<CLK, MOSI, MISO, DELAYER> {
+        &self.bus
+    }
+
+    /// Get the bus, e.g. to reconfigure it (see [`SpiSoft::reconfigure`]).
+    pub fn bus_mut(&mut self) -> &mut SpiSoft<CLK, MOSI, MISO, DELAYER> {
+        &mut self.bus
+    }
+
+    /// Consume the device and return the bus and the chip select
+    pub fn bring_down(self) -> (SpiSoft<CLK, MOSI, MISO, DELAYER>, CS) {
+        (self.bus, self.cs)
+    }
+}
+
+impl<CLK, MOSI, MISO, CS, DELAYER> ErrorType for SpiSoftDevice<CLK, MOSI, MISO, CS, DELAYER>
+where
+    CLK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    CS: OutputPin,
+    DELAYER: DelayNs,
+{
+    type Error = DeviceError<CLK, MOSI, MISO, CS>;
+}
+
+impl<CLK, MOSI, MISO, CS, DELAYER> SpiDevice<u8> for SpiSoftDevice<CLK, MOSI, MISO, CS, DELAYER>
+where
+    CLK: OutputPin,
+    MOSI: OutputPin,
+    MISO: InputPin,
+    CS: OutputPin,
+    DELAYER: DelayNs,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        self.cs.set_low().map_err(PinError::Cs)?;
+        let result = operations
+            .iter_mut()
+            .try_for_each(|operation| match operation {
+                Operation::Read(words) => self.bus.read(words),
+                Operation::Write(words) => self.bus.write(words),
+                Operation::Transfer(read, write) => self.bus.transfer(read, write),
+                Operation::TransferInPlace(words) => self.bus.transfer_in_place(words),
+                Operation::DelayNs(ns) => {
+                    self.bus.delay.delay_ns(*ns);
+                    Ok(())
+                }
+            });
+        // Deselect the device, even after an error
+        let deselected = self.cs.set_high();
+        result.map_err(PinError::into_device)?;
+        deselected.map_err(PinError::Cs)
+    }
+}
@@ -0,0 +1,81 @@
+//! Text module
+//! This module provides [`TextBuf`], to build short texts (numbers, times, messages) in a byte
+//! buffer without `core::fmt`.
+//!
+//! The formatting machinery of `core::fmt`, and its instances for each writer and integer type,
+//! take a lot of space in the image: the HAL and the application build their texts with this
+//! instead, and keep `core::fmt` for the optional logs and the panic messages.
+
+/// A text built in a byte buffer.
+///
+/// The text is silently truncated to the size of the buffer (on a character boundary).
+pub struct TextBuf<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> TextBuf<'a> {
+    /// Create an empty text in a buffer.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// The text.
+    pub fn as_str(&self) -> &str {
+        // Safety: Only whole characters are written (see `push_str`)
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    /// Append a string.
+    pub fn push_str(&mut self, s: &str) -> &mut Self {
+        let mut len = s.len().min(self.buf.len() - self.len);
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+        self.buf[self.len..self.len + len].copy_from_slice(&s.as_bytes()[..len]);
+        self.len += len;
+        self
+    }
+
+    /// Append a number in decimal, zero-padded to `width` digits (e.g. `07`).
+    pub fn push_dec(&mut self, mut value: u32, width: usize) -> &mut Self {
+        let mut digits = [b'0'; 10];
+        let first = digits.len() - width.clamp(1, digits.len());
+        let mut start = digits.len();
+        while value > 0 || start > first {
+            start -= 1;
+            digits[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+        }
+        // Safety: ASCII digits only
+        self.push_str(unsafe { core::str::from_utf8_unchecked(&digits[start..]) })
+    }
+
+    /// Append a number in uppercase hexadecimal, on `digits` digits (e.g. `0000002A`).
+    pub fn push_hex(&mut self, value: u32, digits: usize) -> &mut Self {
+        let mut hex = [b'0'; 8];
+        let digits = digits.min(hex.len());
+        for (i, c) in hex[..digits].iter_mut().enumerate() {
+            let nibble = (value >> ((digits - 1 - i) * 4)) as usize & 0xf;
+            *c = b"0123456789ABCDEF"[nibble];
+        }
+        // Safety: ASCII digits only
+        self.push_str(unsafe { core::str::from_utf8_unchecked(&hex[..digits]) })
+    }
+
+    /// Append a time as minutes and seconds (`mm:ss`).
+    pub fn push_time(&mut self, seconds: u32) -> &mut Self {
+        self.push_dec(seconds / 60, 2)
+            .push_str(":")
+            .push_dec(seconds % 60, 2)
+    }
+
+    /// Append spaces, up to `width` bytes in total (to left-align the text, or to right-align
+    /// what is appended next).
+    pub fn pad(&mut self, width: usize) -> &mut Self {
+        while self.len < width.min(self.buf.len()) {
+            self.push_str(" ");
+        }
+        self
+    }
+}
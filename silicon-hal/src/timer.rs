@@ -1,11 +1,34 @@
+//! Timer0 module
+//! This module provides an interface for the hardware Timer0 peripheral.
+//!
+//! Timer0 is a 64-bit counter incremented every microsecond (1MHz). It starts counting at
+//! power-up, and does not wrap around in practice (~585 000 years).
+//!
+//! The [`Timer0`] singleton controls the counter (enable, disable, reset), while [`Timer`] is a
+//! copyable read-only handle, used for timekeeping and delays.
+//!
+//! Timer0 also has 3 compare channels, which can be taken out of the singleton as [`Alarm`]s:
+//! - one-shot alarms at a given instant, or after a given duration (`CountDown`-style),
+//! - periodic ticks with [`Periodic`], without drift (each tick is scheduled from the previous
+//!   one, not from the time it was noticed).
+//!
+//! The alarms are polled: their match flags stay set until the alarm is polled or re-armed.
+//! When an alarm fires, Timer0 also raises the `TIMER0` interrupt (see [`crate::interrupt`]).
+
 use core::marker::PhantomData;
 
-use fugit::TimerInstantU64;
+use fugit::{TimerDurationU64, TimerInstantU64};
 
-use crate::pac::{self, Timer0};
+use crate::pac;
 use crate::typesafe::Sealed;
 
+/// Instant of Timer0 (microseconds since the last reset of the counter).
 pub type Instant = TimerInstantU64<1_000_000>;
+/// Duration measured with Timer0 (in microseconds).
+pub type Duration = TimerDurationU64<1_000_000>;
+
+/// Number of compare channels (alarms) of Timer0.
+pub const ALARMS: usize = 3;
 
 pub trait TimerDevice: Sealed + Clone + Copy + 'static {
     /// Get the register block for Timer0
@@ -13,7 +36,7 @@ pub trait TimerDevice: Sealed + Clone + Copy + 'static {
     /// # Safety
     /// For read-only use, this is perfectly fine to use.
     fn get_perif() -> &'static pac::timer0::RegisterBlock {
-        unsafe { &*Timer0::ptr() }
+        unsafe { &*pac::Timer0::ptr() }
     }
 }
 
@@ -44,6 +67,9 @@ where
     D: TimerDevice,
 {
     /// Get the current counter value.
+    ///
+    /// The two halves of the counter are read separately, the read is retried if the highest
+    /// half changed in between, so the value is always consistent.
     pub fn get_counter(&self) -> Instant {
         // Safety: Only used for reading current timer value
         let timer = D::get_perif();
@@ -59,6 +85,17 @@ where
         TimerInstantU64::from_ticks(timestamp)
     }
 
+    /// Get the time elapsed since an earlier instant.
+    ///
+    /// # Arguments
+    ///
+    /// * `since` - The earlier instant, a later instant (after a reset) gives a zero duration.
+    pub fn elapsed(&self, since: Instant) -> Duration {
+        self.get_counter()
+            .checked_duration_since(since)
+            .unwrap_or(Duration::from_ticks(0))
+    }
+
     /// Get the value of the least significant word of the counter.
     pub fn get_counter_low(&self) -> u32 {
         // Safety: Only used for reading current timer value
@@ -67,6 +104,8 @@ where
     }
 
     /// Pauses execution for at minimum `us` microseconds.
+    ///
+    /// The counter must be enabled, otherwise this never returns.
     pub(crate) fn delay_us(&self, mut us: u32) {
         let mut start = self.get_counter_low();
         loop {
@@ -80,3 +119,308 @@ where
         }
     }
 }
+
+/// Timer0 peripheral.
+///
+/// Only this singleton can control the counter, any number of [`Timer`] handles can read it.
+/// The alarms can be taken out of it, and used independently.
+pub struct Timer0 {
+    alarm0: Option<Alarm>,
+    alarm1: Option<Alarm>,
+    alarm2: Option<Alarm>,
+}
+
+impl Timer0 {
+    /// Creates a new instance of the Timer0 peripheral.
+    pub(crate) fn new() -> Self {
+        Self {
+            alarm0: Some(Alarm::new(0)),
+            alarm1: Some(Alarm::new(1)),
+            alarm2: Some(Alarm::new(2)),
+        }
+    }
+
+    /// Take the first alarm (if not already taken).
+    pub fn take_alarm0(&mut self) -> Option<Alarm> {
+        self.alarm0.take()
+    }
+
+    /// Take the second alarm (if not already taken).
+    pub fn take_alarm1(&mut self) -> Option<Alarm> {
+        self.alarm1.take()
+    }
+
+    /// Take the third alarm (if not already taken).
+    pub fn take_alarm2(&mut self) -> Option<Alarm> {
+        self.alarm2.take()
+    }
+
+    /// Take all the alarms (if none of them was already taken).
+    pub fn take_all_alarms(&mut self) -> Option<(Alarm, Alarm, Alarm)> {
+        if self.alarm0.is_some() && self.alarm1.is_some() && self.alarm2.is_some() {
+            Some((
+                self.alarm0.take()?,
+                self.alarm1.take()?,
+                self.alarm2.take()?,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Give an alarm back (e.g. when leaving a screen), so that it can be taken again.
+    ///
+    /// The alarm is cancelled.
+    pub fn release_alarm(&mut self, mut alarm: Alarm) {
+        alarm.cancel();
+        match alarm.id {
+            0 => self.alarm0 = Some(alarm),
+            1 => self.alarm1 = Some(alarm),
+            _ => self.alarm2 = Some(alarm),
+        }
+    }
+
+    /// Get a copyable read-only handle on the counter.
+    #[inline(always)]
+    pub fn timer(&self) -> Timer<CopyableTimer0> {
+        Timer::new_timer0()
+    }
+
+    /// Start (or resume) counting.
+    #[inline(always)]
+    pub fn enable(&mut self) {
+        // Safety: Only the Timer0 singleton writes the CTRL register.
+        unsafe {
+            let timer = pac::Timer0::steal();
+            timer.ctrl().write_with_zero(|w| w.enable().set_bit());
+        }
+    }
+
+    /// Stop counting, the counter keeps its value.
+    ///
+    /// Warning: [`crate::delay::Timer0Delay`] never returns while the counter is stopped.
+    #[inline(always)]
+    pub fn disable(&mut self) {
+        // Safety: Only the Timer0 singleton writes the CTRL register.
+        unsafe {
+            let timer = pac::Timer0::steal();
+            timer.ctrl().write_with_zero(|w| w.enable().clear_bit());
+        }
+    }
+
+    /// Check if the counter is running.
+    #[inline(always)]
+    pub fn is_enabled(&self) -> bool {
+        // Safety: Only used for reading the control register.
+        let timer = unsafe { pac::Timer0::steal() };
+        timer.ctrl().read().enable().bit_is_set()
+    }
+
+    /// Reset the counter to zero, it keeps running if it was enabled.
+    ///
+    /// Instants taken before the reset are meaningless afterwards, and pending alarms fire later
+    /// than expected.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        let enabled = self.is_enabled();
+        // Safety: Only the Timer0 singleton writes the CTRL register.
+        unsafe {
+            let timer = pac::Timer0::steal();
+            timer.ctrl().write_with_zero(|w| {
+                w.enable().bit(enabled);
+                w.reset().set_bit()
+            });
+        }
+    }
+
+    /// Get the current counter value.
+    #[inline(always)]
+    pub fn now(&self) -> Instant {
+        self.timer().get_counter()
+    }
+
+    /// Get the time elapsed since an earlier instant.
+    #[inline(always)]
+    pub fn elapsed(&self, since: Instant) -> Duration {
+        self.timer().elapsed(since)
+    }
+}
+
+/// A compare channel of Timer0.
+///
+/// An alarm fires once the counter reaches its deadline, then it is disarmed until it is
+/// scheduled again. Deadlines in the past fire immediately.
+pub struct Alarm {
+    id: u8,
+}
+
+impl Alarm {
+    fn new(id: u8) -> Self {
+        Self { id }
+    }
+
+    /// Index of the alarm (0-2).
+    #[inline(always)]
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Get the register block of this compare channel.
+    #[inline(always)]
+    fn regs(&self) -> &'static pac::timer0::Cmp {
+        // Safety: Each alarm only accesses its own registers.
+        unsafe { (*pac::Timer0::ptr()).cmp(self.id as usize) }
+    }
+
+    /// Schedule the alarm at a given instant (a pending alarm is rescheduled).
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - The instant at which the alarm fires.
+    pub fn schedule_at(&mut self, deadline: Instant) {
+        let ticks = deadline.ticks();
+        // Safety: Each alarm only accesses its own registers (and its own match flag).
+        // The channel is disarmed while the 2 halves of the compare value are written.
+        unsafe {
+            let regs = self.regs();
+            regs.ctrl().write_with_zero(|w| w.enable().clear_bit());
+            regs.lo().write_with_zero(|w| w.bits(ticks as u32));
+            regs.hi().write_with_zero(|w| w.bits((ticks >> 32) as u32));
+            let timer = pac::Timer0::steal();
+            timer
+                .status()
+                .write_with_zero(|w| w.match_(self.id).clear_bit_by_one());
+            regs.ctrl().write_with_zero(|w| w.enable().set_bit());
+        }
+    }
+
+    /// Schedule the alarm after a given duration, from now (`CountDown::start`).
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The duration before the alarm fires.
+    pub fn start(&mut self, timeout: Duration) {
+        let now = Timer::new_timer0().get_counter();
+        self.schedule_at(now + timeout);
+    }
+
+    /// Cancel the alarm (and forget it if it already fired).
+    pub fn cancel(&mut self) {
+        // Safety: Each alarm only accesses its own registers (and its own match flag).
+        unsafe {
+            self.regs()
+                .ctrl()
+                .write_with_zero(|w| w.enable().clear_bit());
+            let timer = pac::Timer0::steal();
+            timer
+                .status()
+                .write_with_zero(|w| w.match_(self.id).clear_bit_by_one());
+        }
+    }
+
+    /// Get the deadline of the alarm (the last one it was scheduled at).
+    pub fn deadline(&self) -> Instant {
+        let regs = self.regs();
+        let ticks = (u64::from(regs.hi().read().bits()) << 32) | u64::from(regs.lo().read().bits());
+        Instant::from_ticks(ticks)
+    }
+
+    /// Check if the alarm is scheduled and has not fired yet.
+    #[inline(always)]
+    pub fn is_pending(&self) -> bool {
+        self.regs().ctrl().read().enable().bit_is_set()
+    }
+
+    /// Check if the alarm has fired (without acknowledging it).
+    #[inline(always)]
+    pub fn has_fired(&self) -> bool {
+        // Safety: Only used for reading the match flags.
+        let timer = unsafe { pac::Timer0::steal() };
+        timer.status().read().match_(self.id).bit_is_set()
+    }
+
+    /// Check if the alarm has fired, and acknowledge it.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True once after the alarm fired, false otherwise.
+    pub fn poll(&mut self) -> bool {
+        if !self.has_fired() {
+            return false;
+        }
+        // Safety: Each alarm only clears its own match flag.
+        unsafe {
+            let timer = pac::Timer0::steal();
+            timer
+                .status()
+                .write_with_zero(|w| w.match_(self.id).clear_bit_by_one());
+        }
+        true
+    }
+
+    /// Wait for the alarm to fire, and acknowledge it (`CountDown::wait`, blocking).
+    ///
+    /// Returns immediately if the alarm is not scheduled.
+    pub fn wait(&mut self) {
+        while self.is_pending() {}
+        self.poll();
+    }
+}
+
+/// Periodic ticks, based on an alarm.
+///
+/// Each tick is scheduled one period after the previous one, so the ticks do not drift even if
+/// they are polled late. If more than a period was missed, the missed ticks are skipped.
+pub struct Periodic {
+    alarm: Alarm,
+    period: Duration,
+}
+
+impl Periodic {
+    /// Start periodic ticks, the first one fires one period from now.
+    ///
+    /// # Arguments
+    ///
+    /// * `alarm` - The alarm used for the ticks.
+    /// * `period` - The duration between two ticks.
+    pub fn new(mut alarm: Alarm, period: Duration) -> Self {
+        alarm.start(period);
+        Self { alarm, period }
+    }
+
+    /// Get the duration between two ticks.
+    #[inline(always)]
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Check if a tick is due, and schedule the next one.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True once per tick.
+    pub fn poll(&mut self) -> bool {
+        if !self.alarm.poll() {
+            return false;
+        }
+        let now = Timer::new_timer0().get_counter();
+        let mut next = self.alarm.deadline() + self.period;
+        if next <= now {
+            // Skip the missed ticks
+            next = now + self.period;
+        }
+        self.alarm.schedule_at(next);
+        true
+    }
+
+    /// Restart the ticks, the next one fires one period from now.
+    pub fn restart(&mut self) {
+        self.alarm.start(self.period);
+    }
+
+    /// Stop the ticks and get the alarm back.
+    pub fn into_alarm(mut self) -> Alarm {
+        self.alarm.cancel();
+        self.alarm
+    }
+}
@@ -0,0 +1,285 @@
+//! UART module
+//! This module provides a driver for the UART peripheral, the serial port on the USB-serial link
+//! of the board.
+//!
+//! The UART sends and receives 8N1 frames (8 data bits, no parity, 1 stop bit) through 16-byte
+//! TX and RX FIFOs, at 115200 bauds by default. It implements:
+//! - the `embedded-io` [`Read`](embedded_io::Read) and [`Write`](embedded_io::Write) traits
+//!   (blocking),
+//! - the `embedded-hal-nb` serial [`Read`](embedded_hal_nb::serial::Read) and
+//!   [`Write`](embedded_hal_nb::serial::Write) traits (non-blocking),
+//! - [`core::fmt::Write`], to `write!` formatted text.
+//!
+//! In loopback mode, the UART receives what it sends (the RX pin is ignored), so that it can be
+//! tested without anything connected to the serial link.
+//!
+//! A byte received while the RX FIFO is full (overrun), or with a low stop bit (framing error),
+//! is dropped. The error is reported by the next read, before the bytes still in the FIFO.
+
+use core::convert::Infallible;
+
+use crate::pac;
+
+/// Frequency of the clock of the UART.
+pub const CLOCK_HZ: u32 = 25_000_000;
+
+/// Baud rate at power-up.
+pub const DEFAULT_BAUD_RATE: u32 = 115_200;
+
+/// Size of the TX and RX FIFOs, in bytes.
+pub const FIFO_SIZE: usize = 16;
+
+/// Error of the UART.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// A byte was received while the RX FIFO was full (it was dropped).
+    Overrun,
+    /// A byte was received with a low stop bit, e.g. at a wrong baud rate (it was dropped).
+    Framing,
+}
+
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::Other
+    }
+}
+
+impl embedded_hal_nb::serial::Error for Error {
+    fn kind(&self) -> embedded_hal_nb::serial::ErrorKind {
+        match self {
+            Error::Overrun => embedded_hal_nb::serial::ErrorKind::Overrun,
+            Error::Framing => embedded_hal_nb::serial::ErrorKind::FrameFormat,
+        }
+    }
+}
+
+/// UART peripheral.
+pub struct Uart {
+    _private: (),
+}
+
+impl Uart {
+    /// Creates a new instance of the UART peripheral.
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Get the register block of the UART.
+    #[inline(always)]
+    fn regs() -> &'static pac::uart::RegisterBlock {
+        // Safety: The UART registers are only accessed through the singleton.
+        unsafe { &*pac::Uart::ptr() }
+    }
+
+    /// Set the baud rate.
+    ///
+    /// The bit period is a whole number of clock cycles, the actual baud rate is the closest
+    /// one (see [`Uart::baud_rate`]). The bytes being sent or received are garbled.
+    ///
+    /// # Arguments
+    ///
+    /// * `baud_rate` - The baud rate, from ~382 to 12.5M bauds.
+    pub fn set_baud_rate(&mut self, baud_rate: u32) {
+        let cycles = (CLOCK_HZ + baud_rate / 2) / baud_rate.max(1);
+        let divider = cycles.clamp(2, 1 << 16) - 1;
+        Self::regs()
+            .baud()
+            .write(|w| unsafe { w.divider().bits(divider as u16) });
+    }
+
+    /// Get the actual baud rate.
+    #[inline(always)]
+    pub fn baud_rate(&self) -> u32 {
+        CLOCK_HZ / (Self::regs().baud().read().divider().bits() as u32 + 1)
+    }
+
+    /// Enable or disable the loopback mode: the UART receives what it sends, the RX pin is
+    /// ignored (the TX pin still sends the bytes).
+    #[inline(always)]
+    pub fn set_loopback(&mut self, enabled: bool) {
+        Self::regs().ctrl().write(|w| w.loopback().bit(enabled));
+    }
+
+    /// Check if the loopback mode is enabled.
+    #[inline(always)]
+    pub fn is_loopback(&self) -> bool {
+        Self::regs().ctrl().read().loopback().bit_is_set()
+    }
+
+    /// Drop the bytes waiting in the FIFOs, and the pending errors.
+    ///
+    /// The byte being sent (if any) is not interrupted.
+    pub fn clear(&mut self) {
+        let regs = Self::regs();
+        let loopback = regs.ctrl().read().loopback().bit_is_set();
+        regs.ctrl().write(|w| {
+            w.loopback().bit(loopback);
+            w.clear_tx().set_bit();
+            w.clear_rx().set_bit()
+        });
+        regs.status().write(|w| {
+            w.overrun().clear_bit_by_one();
+            w.frame_error().clear_bit_by_one()
+        });
+    }
+
+    /// Number of bytes waiting in the RX FIFO.
+    #[inline(always)]
+    pub fn rx_count(&self) -> usize {
+        Self::regs().status().read().rx_count().bits() as usize
+    }
+
+    /// Number of free bytes in the TX FIFO.
+    #[inline(always)]
+    pub fn tx_free(&self) -> usize {
+        FIFO_SIZE - Self::regs().status().read().tx_count().bits() as usize
+    }
+
+    /// Check if all the bytes are sent.
+    #[inline(always)]
+    pub fn is_idle(&self) -> bool {
+        Self::regs().status().read().tx_idle().bit_is_set()
+    }
+
+    /// Push a byte to the TX FIFO, without blocking.
+    ///
+    /// # Returns
+    ///
+    /// * `nb::Error::WouldBlock` if the TX FIFO is full.
+    #[inline(always)]
+    pub fn write_byte(&mut self, byte: u8) -> nb::Result<(), Infallible> {
+        let regs = Self::regs();
+        if regs.status().read().tx_full().bit_is_set() {
+            return Err(nb::Error::WouldBlock);
+        }
+        regs.data().write(|w| unsafe { w.byte().bits(byte) });
+        Ok(())
+    }
+
+    /// Pop a byte from the RX FIFO, without blocking.
+    ///
+    /// # Returns
+    ///
+    /// * The pending error, if a byte was dropped (the error is cleared).
+    /// * `nb::Error::WouldBlock` if the RX FIFO is empty.
+    #[inline(always)]
+    pub fn read_byte(&mut self) -> nb::Result<u8, Error> {
+        let regs = Self::regs();
+        let status = regs.status().read();
+        if status.overrun().bit_is_set() {
+            regs.status().write(|w| w.overrun().clear_bit_by_one());
+            return Err(nb::Error::Other(Error::Overrun));
+        }
+        if status.frame_error().bit_is_set() {
+            regs.status().write(|w| w.frame_error().clear_bit_by_one());
+            return Err(nb::Error::Other(Error::Framing));
+        }
+        // Reading DATA pops the RX FIFO
+        let data = regs.data().read();
+        if data.valid().bit_is_clear() {
+            return Err(nb::Error::WouldBlock);
+        }
+        Ok(data.byte().bits())
+    }
+
+    /// Wait until all the bytes are sent.
+    #[inline(always)]
+    pub fn flush(&mut self) {
+        while !self.is_idle() {}
+    }
+}
+
+impl embedded_io::ErrorType for Uart {
+    type Error = Error;
+}
+
+impl embedded_io::Read for Uart {
+    /// Read the received bytes, waiting for at least one.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = nb::block!(self.read_byte())?;
+        let mut read = 1;
+        while read < buf.len() {
+            match self.read_byte() {
+                Ok(byte) => buf[read] = byte,
+                // The error is reported by the next read
+                Err(_) => break,
+            }
+            read += 1;
+        }
+        Ok(read)
+    }
+}
+
+impl embedded_io::ReadReady for Uart {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.rx_count() > 0)
+    }
+}
+
+impl embedded_io::Write for Uart {
+    /// Push bytes to the TX FIFO, waiting for a free slot for the first one.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let mut written = 0;
+        for &byte in buf {
+            if written == 0 {
+                nb::block!(self.write_byte(byte)).ok();
+            } else if self.write_byte(byte).is_err() {
+                break;
+            }
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Uart::flush(self);
+        Ok(())
+    }
+}
+
+impl embedded_io::WriteReady for Uart {
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.tx_free() > 0)
+    }
+}
+
+impl embedded_hal_nb::serial::ErrorType for Uart {
+    type Error = Error;
+}
+
+impl embedded_hal_nb::serial::Read<u8> for Uart {
+    #[inline(always)]
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.read_byte()
+    }
+}
+
+impl embedded_hal_nb::serial::Write<u8> for Uart {
+    #[inline(always)]
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.write_byte(word).map_err(|e| match e {
+            nb::Error::WouldBlock => nb::Error::WouldBlock,
+        })
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        if self.is_idle() {
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+}
+
+impl core::fmt::Write for Uart {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for &byte in s.as_bytes() {
+            nb::block!(self.write_byte(byte)).ok();
+        }
+        Ok(())
+    }
+}
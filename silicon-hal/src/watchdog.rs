@@ -0,0 +1,69 @@
+//! Watchdog module
+//! This module provides an interface for the hardware watchdog timer.
+//!
+//! Once started, the watchdog resets the system (the CPU and the peripherals) if it is not fed
+//! within its timeout, e.g. when the application is stuck in a loop or on a hung SPI transfer.
+//! It cannot be stopped by the software: only the reset stops it.
+//!
+//! The RAM is not cleared by a watchdog reset (the initialized statics are not reloaded either),
+//! and the watchdog remembers the cause of the reset (see [`last_reset_was_watchdog`]).
+//!
+//! The watchdog is a shared resource: it is fed with free functions, from any part of the
+//! application (e.g. from each of its main loops).
+
+use crate::pac;
+use crate::timer::Duration;
+
+/// Longest timeout of the watchdog.
+pub const MAX_TIMEOUT: Duration = Duration::millis(0xffff);
+
+/// Key to write to the FEED register.
+const FEED_KEY: u32 = 0x0000_feed;
+
+/// Get the register block of the watchdog.
+#[inline(always)]
+fn regs() -> &'static pac::watchdog::RegisterBlock {
+    // Safety: The watchdog registers are accessed with single writes, the accesses of the
+    // interrupt handlers cannot corrupt them.
+    unsafe { &*pac::Watchdog::ptr() }
+}
+
+/// Start the watchdog (or change its timeout, if it is already running).
+///
+/// The application must then call [`feed`] at least once per timeout, until the next reset.
+///
+/// # Arguments
+///
+/// * `timeout` - The timeout, from 1ms to [`MAX_TIMEOUT`] (~65s), with a millisecond resolution.
+pub fn start(timeout: Duration) {
+    let millis = timeout.to_millis().clamp(1, 0xffff) as u16;
+    let regs = regs();
+    regs.timeout().write(|w| unsafe { w.millis().bits(millis) });
+    regs.ctrl().write(|w| w.enable().set_bit());
+}
+
+/// Feed the watchdog: the countdown restarts from the timeout.
+///
+/// Does nothing if the watchdog is not running.
+#[inline(always)]
+pub fn feed() {
+    regs().feed().write(|w| unsafe { w.key().bits(FEED_KEY) });
+}
+
+/// Check if the watchdog is running.
+#[inline(always)]
+pub fn is_running() -> bool {
+    regs().ctrl().read().enable().bit_is_set()
+}
+
+/// Time left before the watchdog resets the system (zero if it is not running).
+#[inline(always)]
+pub fn time_left() -> Duration {
+    Duration::millis(regs().status().read().count().bits() as u64)
+}
+
+/// Check if the last reset was caused by the watchdog (and not by a power-up).
+#[inline(always)]
+pub fn last_reset_was_watchdog() -> bool {
+    regs().status().read().wdt_reset().bit_is_set()
+}
@@ -1 +1,5 @@
-
+PROVIDE(TIMER0 = DefaultHandler);
+PROVIDE(BUTTONS = DefaultHandler);
+PROVIDE(AUDIO = DefaultHandler);
+PROVIDE(SPI0 = DefaultHandler);
+PROVIDE(SPI1 = DefaultHandler);
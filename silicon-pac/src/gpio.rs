@@ -10,6 +10,13 @@ pub struct RegisterBlock {
     spi_oled: SpiOled,
     _reserved4: [u8; 0x02],
     audio_viz: AudioViz,
+    _reserved5: [u8; 0x02],
+    btn_rise: BtnRise,
+    _reserved6: [u8; 0x03],
+    btn_fall: BtnFall,
+    _reserved7: [u8; 0x03],
+    btn_count: BtnCount,
+    led_pwm: [LedPwm; 8],
 }
 impl RegisterBlock {
     #[doc = "0x00 - Control of the output of the on-board LEDs."]
@@ -17,7 +24,7 @@ impl RegisterBlock {
     pub const fn led(&self) -> &Led {
         &self.led
     }
-    #[doc = "0x04 - Get state of the on-board BTN inputs."]
+    #[doc = "0x04 - Get state of the on-board BTN inputs (debounced: a new level is taken into account once stable for 5ms)."]
     #[inline(always)]
     pub const fn btn(&self) -> &Btn {
         &self.btn
@@ -37,16 +44,42 @@ impl RegisterBlock {
     pub const fn audio_viz(&self) -> &AudioViz {
         &self.audio_viz
     }
+    #[doc = "0x14 - Sticky press latches of the on-board BTN inputs (write 1 to clear)."]
+    #[inline(always)]
+    pub const fn btn_rise(&self) -> &BtnRise {
+        &self.btn_rise
+    }
+    #[doc = "0x18 - Sticky release latches of the on-board BTN inputs (write 1 to clear)."]
+    #[inline(always)]
+    pub const fn btn_fall(&self) -> &BtnFall {
+        &self.btn_fall
+    }
+    #[doc = "0x1c - Press counters of the on-board BTN inputs (4-bit, wrapping around)."]
+    #[inline(always)]
+    pub const fn btn_count(&self) -> &BtnCount {
+        &self.btn_count
+    }
+    #[doc = "0x20..0x40 - Brightness of the on-board LEDs (PWM duty cycle, applied when the LED is on)."]
+    #[inline(always)]
+    pub const fn led_pwm(&self, n: usize) -> &LedPwm {
+        &self.led_pwm[n]
+    }
+    #[doc = "Iterator for array of:"]
+    #[doc = "0x20..0x40 - Brightness of the on-board LEDs (PWM duty cycle, applied when the LED is on)."]
+    #[inline(always)]
+    pub fn led_pwm_iter(&self) -> impl Iterator<Item = &LedPwm> {
+        self.led_pwm.iter()
+    }
 }
 #[doc = "LED (rw) register accessor: Control of the output of the on-board LEDs.\n\nYou can [`read`](crate::Reg::read) this register and get [`led::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`led::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@led`] module"]
 #[doc(alias = "LED")]
 pub type Led = crate::Reg<led::LedSpec>;
 #[doc = "Control of the output of the on-board LEDs."]
 pub mod led;
-#[doc = "BTN (r) register accessor: Get state of the on-board BTN inputs.\n\nYou can [`read`](crate::Reg::read) this register and get [`btn::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@btn`] module"]
+#[doc = "BTN (r) register accessor: Get state of the on-board BTN inputs (debounced: a new level is taken into account once stable for 5ms).\n\nYou can [`read`](crate::Reg::read) this register and get [`btn::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@btn`] module"]
 #[doc(alias = "BTN")]
 pub type Btn = crate::Reg<btn::BtnSpec>;
-#[doc = "Get state of the on-board BTN inputs."]
+#[doc = "Get state of the on-board BTN inputs (debounced: a new level is taken into account once stable for 5ms)."]
 pub mod btn;
 #[doc = "SPI_SDCARD (rw) register accessor: Control of the input-output pins directly wired to the SD card SPI interface. Some pins are input-only or output-only.\n\nYou can [`read`](crate::Reg::read) this register and get [`spi_sdcard::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`spi_sdcard::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@spi_sdcard`] module"]
 #[doc(alias = "SPI_SDCARD")]
@@ -63,3 +96,23 @@ pub mod spi_oled;
 pub type AudioViz = crate::Reg<audio_viz::AudioVizSpec>;
 #[doc = "Control of the audio viz pins"]
 pub mod audio_viz;
+#[doc = "BTN_RISE (rw) register accessor: Sticky press latches of the on-board BTN inputs (write 1 to clear).\n\nYou can [`read`](crate::Reg::read) this register and get [`btn_rise::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`btn_rise::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@btn_rise`] module"]
+#[doc(alias = "BTN_RISE")]
+pub type BtnRise = crate::Reg<btn_rise::BtnRiseSpec>;
+#[doc = "Sticky press latches of the on-board BTN inputs (write 1 to clear)."]
+pub mod btn_rise;
+#[doc = "BTN_FALL (rw) register accessor: Sticky release latches of the on-board BTN inputs (write 1 to clear).\n\nYou can [`read`](crate::Reg::read) this register and get [`btn_fall::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`btn_fall::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@btn_fall`] module"]
+#[doc(alias = "BTN_FALL")]
+pub type BtnFall = crate::Reg<btn_fall::BtnFallSpec>;
+#[doc = "Sticky release latches of the on-board BTN inputs (write 1 to clear)."]
+pub mod btn_fall;
+#[doc = "BTN_COUNT (r) register accessor: Press counters of the on-board BTN inputs (4-bit, wrapping around).\n\nYou can [`read`](crate::Reg::read) this register and get [`btn_count::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@btn_count`] module"]
+#[doc(alias = "BTN_COUNT")]
+pub type BtnCount = crate::Reg<btn_count::BtnCountSpec>;
+#[doc = "Press counters of the on-board BTN inputs (4-bit, wrapping around)."]
+pub mod btn_count;
+#[doc = "LED_PWM (rw) register accessor: Brightness of the on-board LEDs (PWM duty cycle, applied when the LED is on).\n\nYou can [`read`](crate::Reg::read) this register and get [`led_pwm::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`led_pwm::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@led_pwm`] module"]
+#[doc(alias = "LED_PWM")]
+pub type LedPwm = crate::Reg<led_pwm::LedPwmSpec>;
+#[doc = "Brightness of the on-board LEDs (PWM duty cycle, applied when the LED is on)."]
+pub mod led_pwm;
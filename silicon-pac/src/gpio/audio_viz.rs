@@ -6,12 +6,39 @@ pub type W = crate::W<AudioVizSpec>;
 pub type EnableR = crate::BitReader;
 #[doc = "Field `ENABLE` writer - Read/Set the enable state of the audio visualization. If enabled, the audio visualization is active, and on-board LEDs reflect the audio signal and not the GPIO state."]
 pub type EnableW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `MODE` reader - Read/Set the display mode of the audio visualization (0=BAR, 1=DOT, 2=PEAK (bar with a decaying peak-hold dot), 3=SPLIT (left channel on LEDs 3-0, right channel on LEDs 4-7))."]
+pub type ModeR = crate::FieldReader;
+#[doc = "Field `MODE` writer - Read/Set the display mode of the audio visualization (0=BAR, 1=DOT, 2=PEAK (bar with a decaying peak-hold dot), 3=SPLIT (left channel on LEDs 3-0, right channel on LEDs 4-7))."]
+pub type ModeW<'a, REG> = crate::FieldWriter<'a, REG, 2>;
+#[doc = "Field `SENSITIVITY` reader - Read/Set the sensitivity of the audio visualization. Each step doubles the measured energy (+3dB)."]
+pub type SensitivityR = crate::FieldReader;
+#[doc = "Field `SENSITIVITY` writer - Read/Set the sensitivity of the audio visualization. Each step doubles the measured energy (+3dB)."]
+pub type SensitivityW<'a, REG> = crate::FieldWriter<'a, REG, 4>;
+#[doc = "Field `REFERENCE` reader - Read/Set the reference level of the audio visualization, i.e. the sample value of silence (128 at full volume)."]
+pub type ReferenceR = crate::FieldReader;
+#[doc = "Field `REFERENCE` writer - Read/Set the reference level of the audio visualization, i.e. the sample value of silence (128 at full volume)."]
+pub type ReferenceW<'a, REG> = crate::FieldWriter<'a, REG, 8>;
 impl R {
     #[doc = "Bit 0 - Read/Set the enable state of the audio visualization. If enabled, the audio visualization is active, and on-board LEDs reflect the audio signal and not the GPIO state."]
     #[inline(always)]
     pub fn enable(&self) -> EnableR {
         EnableR::new((self.bits & 1) != 0)
     }
+    #[doc = "Bits 1:2 - Read/Set the display mode of the audio visualization (0=BAR, 1=DOT, 2=PEAK (bar with a decaying peak-hold dot), 3=SPLIT (left channel on LEDs 3-0, right channel on LEDs 4-7))."]
+    #[inline(always)]
+    pub fn mode(&self) -> ModeR {
+        ModeR::new(((self.bits >> 1) & 3) as u8)
+    }
+    #[doc = "Bits 4:7 - Read/Set the sensitivity of the audio visualization. Each step doubles the measured energy (+3dB)."]
+    #[inline(always)]
+    pub fn sensitivity(&self) -> SensitivityR {
+        SensitivityR::new(((self.bits >> 4) & 0x0f) as u8)
+    }
+    #[doc = "Bits 8:15 - Read/Set the reference level of the audio visualization, i.e. the sample value of silence (128 at full volume)."]
+    #[inline(always)]
+    pub fn reference(&self) -> ReferenceR {
+        ReferenceR::new(((self.bits >> 8) & 0xff) as u8)
+    }
 }
 impl W {
     #[doc = "Bit 0 - Read/Set the enable state of the audio visualization. If enabled, the audio visualization is active, and on-board LEDs reflect the audio signal and not the GPIO state."]
@@ -19,11 +46,26 @@ impl W {
     pub fn enable(&mut self) -> EnableW<'_, AudioVizSpec> {
         EnableW::new(self, 0)
     }
+    #[doc = "Bits 1:2 - Read/Set the display mode of the audio visualization (0=BAR, 1=DOT, 2=PEAK (bar with a decaying peak-hold dot), 3=SPLIT (left channel on LEDs 3-0, right channel on LEDs 4-7))."]
+    #[inline(always)]
+    pub fn mode(&mut self) -> ModeW<'_, AudioVizSpec> {
+        ModeW::new(self, 1)
+    }
+    #[doc = "Bits 4:7 - Read/Set the sensitivity of the audio visualization. Each step doubles the measured energy (+3dB)."]
+    #[inline(always)]
+    pub fn sensitivity(&mut self) -> SensitivityW<'_, AudioVizSpec> {
+        SensitivityW::new(self, 4)
+    }
+    #[doc = "Bits 8:15 - Read/Set the reference level of the audio visualization, i.e. the sample value of silence (128 at full volume)."]
+    #[inline(always)]
+    pub fn reference(&mut self) -> ReferenceW<'_, AudioVizSpec> {
+        ReferenceW::new(self, 8)
+    }
 }
 #[doc = "Control of the audio viz pins\n\nYou can [`read`](crate::Reg::read) this register and get [`audio_viz::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`audio_viz::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
 pub struct AudioVizSpec;
 impl crate::RegisterSpec for AudioVizSpec {
-    type Ux = u8;
+    type Ux = u16;
 }
 #[doc = "`read()` method returns [`audio_viz::R`](R) reader structure"]
 impl crate::Readable for AudioVizSpec {}
@@ -31,5 +73,7 @@ impl crate::Readable for AudioVizSpec {}
 impl crate::Writable for AudioVizSpec {
     type Safety = crate::Unsafe;
 }
-#[doc = "`reset()` method sets AUDIO_VIZ to value 0"]
-impl crate::Resettable for AudioVizSpec {}
+#[doc = "`reset()` method sets AUDIO_VIZ to value 0x8000"]
+impl crate::Resettable for AudioVizSpec {
+    const RESET_VALUE: u16 = 0x8000;
+}
@@ -59,7 +59,7 @@ impl R {
         BtnInputR::new(((self.bits >> 7) & 1) != 0)
     }
 }
-#[doc = "Get state of the on-board BTN inputs.\n\nYou can [`read`](crate::Reg::read) this register and get [`btn::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+#[doc = "Get state of the on-board BTN inputs (debounced: a new level is taken into account once stable for 5ms).\n\nYou can [`read`](crate::Reg::read) this register and get [`btn::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
 pub struct BtnSpec;
 impl crate::RegisterSpec for BtnSpec {
     type Ux = u8;
@@ -0,0 +1,65 @@
+#[doc = "Register `BTN_COUNT` reader"]
+pub type R = crate::R<BtnCountSpec>;
+#[doc = "Field `BTN_COUNT(0-6)` reader - Number of presses of the %sth BTN (modulo 16)."]
+pub type BtnCountR = crate::FieldReader;
+impl R {
+    #[doc = "Number of presses of the (0-6)th BTN (modulo 16)."]
+    #[doc = ""]
+    #[doc = "<div class=\"warning\">`n` is number of field in register. `n == 0` corresponds to `BTN_COUNT0` field.</div>"]
+    #[inline(always)]
+    pub fn btn_count(&self, n: u8) -> BtnCountR {
+        #[allow(clippy::no_effect)]
+        [(); 7][n as usize];
+        BtnCountR::new(((self.bits >> (n * 4)) & 0x0f) as u8)
+    }
+    #[doc = "Iterator for array of:"]
+    #[doc = "Number of presses of the (0-6)th BTN (modulo 16)."]
+    #[inline(always)]
+    pub fn btn_count_iter(&self) -> impl Iterator<Item = BtnCountR> + '_ {
+        (0..7).map(move |n| BtnCountR::new(((self.bits >> (n * 4)) & 0x0f) as u8))
+    }
+    #[doc = "Bits 0:3 - Number of presses of the 0th BTN (modulo 16)."]
+    #[inline(always)]
+    pub fn btn_count0(&self) -> BtnCountR {
+        BtnCountR::new((self.bits & 0x0f) as u8)
+    }
+    #[doc = "Bits 4:7 - Number of presses of the 1th BTN (modulo 16)."]
+    #[inline(always)]
+    pub fn btn_count1(&self) -> BtnCountR {
+        BtnCountR::new(((self.bits >> 4) & 0x0f) as u8)
+    }
+    #[doc = "Bits 8:11 - Number of presses of the 2th BTN (modulo 16)."]
+    #[inline(always)]
+    pub fn btn_count2(&self) -> BtnCountR {
+        BtnCountR::new(((self.bits >> 8) & 0x0f) as u8)
+    }
+    #[doc = "Bits 12:15 - Number of presses of the 3th BTN (modulo 16)."]
+    #[inline(always)]
+    pub fn btn_count3(&self) -> BtnCountR {
+        BtnCountR::new(((self.bits >> 12) & 0x0f) as u8)
+    }
+    #[doc = "Bits 16:19 - Number of presses of the 4th BTN (modulo 16)."]
+    #[inline(always)]
+    pub fn btn_count4(&self) -> BtnCountR {
+        BtnCountR::new(((self.bits >> 16) & 0x0f) as u8)
+    }
+    #[doc = "Bits 20:23 - Number of presses of the 5th BTN (modulo 16)."]
+    #[inline(always)]
+    pub fn btn_count5(&self) -> BtnCountR {
+        BtnCountR::new(((self.bits >> 20) & 0x0f) as u8)
+    }
+    #[doc = "Bits 24:27 - Number of presses of the 6th BTN (modulo 16)."]
+    #[inline(always)]
+    pub fn btn_count6(&self) -> BtnCountR {
+        BtnCountR::new(((self.bits >> 24) & 0x0f) as u8)
+    }
+}
+#[doc = "Press counters of the on-board BTN inputs (4-bit, wrapping around).\n\nYou can [`read`](crate::Reg::read) this register and get [`btn_count::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct BtnCountSpec;
+impl crate::RegisterSpec for BtnCountSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`btn_count::R`](R) reader structure"]
+impl crate::Readable for BtnCountSpec {}
+#[doc = "`reset()` method sets BTN_COUNT to value 0"]
+impl crate::Resettable for BtnCountSpec {}
@@ -0,0 +1,120 @@
+#[doc = "Register `BTN_FALL` reader"]
+pub type R = crate::R<BtnFallSpec>;
+#[doc = "Register `BTN_FALL` writer"]
+pub type W = crate::W<BtnFallSpec>;
+#[doc = "Field `BTN_FALL(0-6)` reader - Set when the %sth BTN is released (write 1 to clear)."]
+pub type BtnFallR = crate::BitReader;
+#[doc = "Field `BTN_FALL(0-6)` writer - Set when the %sth BTN is released (write 1 to clear)."]
+pub type BtnFallW<'a, REG> = crate::BitWriter1C<'a, REG>;
+impl R {
+    #[doc = "Set when the (0-6)th BTN is released (write 1 to clear)."]
+    #[doc = ""]
+    #[doc = "<div class=\"warning\">`n` is number of field in register. `n == 0` corresponds to `BTN_FALL0` field.</div>"]
+    #[inline(always)]
+    pub fn btn_fall(&self, n: u8) -> BtnFallR {
+        #[allow(clippy::no_effect)]
+        [(); 7][n as usize];
+        BtnFallR::new(((self.bits >> n) & 1) != 0)
+    }
+    #[doc = "Iterator for array of:"]
+    #[doc = "Set when the (0-6)th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall_iter(&self) -> impl Iterator<Item = BtnFallR> + '_ {
+        (0..7).map(move |n| BtnFallR::new(((self.bits >> n) & 1) != 0))
+    }
+    #[doc = "Bit 0 - Set when the 0th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall0(&self) -> BtnFallR {
+        BtnFallR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - Set when the 1th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall1(&self) -> BtnFallR {
+        BtnFallR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - Set when the 2th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall2(&self) -> BtnFallR {
+        BtnFallR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - Set when the 3th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall3(&self) -> BtnFallR {
+        BtnFallR::new(((self.bits >> 3) & 1) != 0)
+    }
+    #[doc = "Bit 4 - Set when the 4th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall4(&self) -> BtnFallR {
+        BtnFallR::new(((self.bits >> 4) & 1) != 0)
+    }
+    #[doc = "Bit 5 - Set when the 5th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall5(&self) -> BtnFallR {
+        BtnFallR::new(((self.bits >> 5) & 1) != 0)
+    }
+    #[doc = "Bit 6 - Set when the 6th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall6(&self) -> BtnFallR {
+        BtnFallR::new(((self.bits >> 6) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Set when the (0-6)th BTN is released (write 1 to clear)."]
+    #[doc = ""]
+    #[doc = "<div class=\"warning\">`n` is number of field in register. `n == 0` corresponds to `BTN_FALL0` field.</div>"]
+    #[inline(always)]
+    pub fn btn_fall(&mut self, n: u8) -> BtnFallW<'_, BtnFallSpec> {
+        #[allow(clippy::no_effect)]
+        [(); 7][n as usize];
+        BtnFallW::new(self, n)
+    }
+    #[doc = "Bit 0 - Set when the 0th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall0(&mut self) -> BtnFallW<'_, BtnFallSpec> {
+        BtnFallW::new(self, 0)
+    }
+    #[doc = "Bit 1 - Set when the 1th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall1(&mut self) -> BtnFallW<'_, BtnFallSpec> {
+        BtnFallW::new(self, 1)
+    }
+    #[doc = "Bit 2 - Set when the 2th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall2(&mut self) -> BtnFallW<'_, BtnFallSpec> {
+        BtnFallW::new(self, 2)
+    }
+    #[doc = "Bit 3 - Set when the 3th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall3(&mut self) -> BtnFallW<'_, BtnFallSpec> {
+        BtnFallW::new(self, 3)
+    }
+    #[doc = "Bit 4 - Set when the 4th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall4(&mut self) -> BtnFallW<'_, BtnFallSpec> {
+        BtnFallW::new(self, 4)
+    }
+    #[doc = "Bit 5 - Set when the 5th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall5(&mut self) -> BtnFallW<'_, BtnFallSpec> {
+        BtnFallW::new(self, 5)
+    }
+    #[doc = "Bit 6 - Set when the 6th BTN is released (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_fall6(&mut self) -> BtnFallW<'_, BtnFallSpec> {
+        BtnFallW::new(self, 6)
+    }
+}
+#[doc = "Sticky release latches of the on-board BTN inputs (write 1 to clear).\n\nYou can [`read`](crate::Reg::read) this register and get [`btn_fall::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`btn_fall::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct BtnFallSpec;
+impl crate::RegisterSpec for BtnFallSpec {
+    type Ux = u8;
+}
+#[doc = "`read()` method returns [`btn_fall::R`](R) reader structure"]
+impl crate::Readable for BtnFallSpec {}
+#[doc = "`write(|w| ..)` method takes [`btn_fall::W`](W) writer structure"]
+impl crate::Writable for BtnFallSpec {
+    type Safety = crate::Unsafe;
+    const ONE_TO_MODIFY_FIELDS_BITMAP: u8 = 0x7f;
+}
+#[doc = "`reset()` method sets BTN_FALL to value 0"]
+impl crate::Resettable for BtnFallSpec {}
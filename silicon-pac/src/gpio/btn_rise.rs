@@ -0,0 +1,120 @@
+#[doc = "Register `BTN_RISE` reader"]
+pub type R = crate::R<BtnRiseSpec>;
+#[doc = "Register `BTN_RISE` writer"]
+pub type W = crate::W<BtnRiseSpec>;
+#[doc = "Field `BTN_RISE(0-6)` reader - Set when the %sth BTN is pressed (write 1 to clear)."]
+pub type BtnRiseR = crate::BitReader;
+#[doc = "Field `BTN_RISE(0-6)` writer - Set when the %sth BTN is pressed (write 1 to clear)."]
+pub type BtnRiseW<'a, REG> = crate::BitWriter1C<'a, REG>;
+impl R {
+    #[doc = "Set when the (0-6)th BTN is pressed (write 1 to clear)."]
+    #[doc = ""]
+    #[doc = "<div class=\"warning\">`n` is number of field in register. `n == 0` corresponds to `BTN_RISE0` field.</div>"]
+    #[inline(always)]
+    pub fn btn_rise(&self, n: u8) -> BtnRiseR {
+        #[allow(clippy::no_effect)]
+        [(); 7][n as usize];
+        BtnRiseR::new(((self.bits >> n) & 1) != 0)
+    }
+    #[doc = "Iterator for array of:"]
+    #[doc = "Set when the (0-6)th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise_iter(&self) -> impl Iterator<Item = BtnRiseR> + '_ {
+        (0..7).map(move |n| BtnRiseR::new(((self.bits >> n) & 1) != 0))
+    }
+    #[doc = "Bit 0 - Set when the 0th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise0(&self) -> BtnRiseR {
+        BtnRiseR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - Set when the 1th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise1(&self) -> BtnRiseR {
+        BtnRiseR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - Set when the 2th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise2(&self) -> BtnRiseR {
+        BtnRiseR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - Set when the 3th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise3(&self) -> BtnRiseR {
+        BtnRiseR::new(((self.bits >> 3) & 1) != 0)
+    }
+    #[doc = "Bit 4 - Set when the 4th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise4(&self) -> BtnRiseR {
+        BtnRiseR::new(((self.bits >> 4) & 1) != 0)
+    }
+    #[doc = "Bit 5 - Set when the 5th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise5(&self) -> BtnRiseR {
+        BtnRiseR::new(((self.bits >> 5) & 1) != 0)
+    }
+    #[doc = "Bit 6 - Set when the 6th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise6(&self) -> BtnRiseR {
+        BtnRiseR::new(((self.bits >> 6) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Set when the (0-6)th BTN is pressed (write 1 to clear)."]
+    #[doc = ""]
+    #[doc = "<div class=\"warning\">`n` is number of field in register. `n == 0` corresponds to `BTN_RISE0` field.</div>"]
+    #[inline(always)]
+    pub fn btn_rise(&mut self, n: u8) -> BtnRiseW<'_, BtnRiseSpec> {
+        #[allow(clippy::no_effect)]
+        [(); 7][n as usize];
+        BtnRiseW::new(self, n)
+    }
+    #[doc = "Bit 0 - Set when the 0th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise0(&mut self) -> BtnRiseW<'_, BtnRiseSpec> {
+        BtnRiseW::new(self, 0)
+    }
+    #[doc = "Bit 1 - Set when the 1th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise1(&mut self) -> BtnRiseW<'_, BtnRiseSpec> {
+        BtnRiseW::new(self, 1)
+    }
+    #[doc = "Bit 2 - Set when the 2th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise2(&mut self) -> BtnRiseW<'_, BtnRiseSpec> {
+        BtnRiseW::new(self, 2)
+    }
+    #[doc = "Bit 3 - Set when the 3th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise3(&mut self) -> BtnRiseW<'_, BtnRiseSpec> {
+        BtnRiseW::new(self, 3)
+    }
+    #[doc = "Bit 4 - Set when the 4th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise4(&mut self) -> BtnRiseW<'_, BtnRiseSpec> {
+        BtnRiseW::new(self, 4)
+    }
+    #[doc = "Bit 5 - Set when the 5th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise5(&mut self) -> BtnRiseW<'_, BtnRiseSpec> {
+        BtnRiseW::new(self, 5)
+    }
+    #[doc = "Bit 6 - Set when the 6th BTN is pressed (write 1 to clear)."]
+    #[inline(always)]
+    pub fn btn_rise6(&mut self) -> BtnRiseW<'_, BtnRiseSpec> {
+        BtnRiseW::new(self, 6)
+    }
+}
+#[doc = "Sticky press latches of the on-board BTN inputs (write 1 to clear).\n\nYou can [`read`](crate::Reg::read) this register and get [`btn_rise::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`btn_rise::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct BtnRiseSpec;
+impl crate::RegisterSpec for BtnRiseSpec {
+    type Ux = u8;
+}
+#[doc = "`read()` method returns [`btn_rise::R`](R) reader structure"]
+impl crate::Readable for BtnRiseSpec {}
+#[doc = "`write(|w| ..)` method takes [`btn_rise::W`](W) writer structure"]
+impl crate::Writable for BtnRiseSpec {
+    type Safety = crate::Unsafe;
+    const ONE_TO_MODIFY_FIELDS_BITMAP: u8 = 0x7f;
+}
+#[doc = "`reset()` method sets BTN_RISE to value 0"]
+impl crate::Resettable for BtnRiseSpec {}
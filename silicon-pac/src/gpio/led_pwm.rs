@@ -0,0 +1,37 @@
+#[doc = "Register `LED_PWM[%s]` reader"]
+pub type R = crate::R<LedPwmSpec>;
+#[doc = "Register `LED_PWM[%s]` writer"]
+pub type W = crate::W<LedPwmSpec>;
+#[doc = "Field `DUTY` reader - Duty cycle of the LED, from 0 (off) to 255 (fully on)."]
+pub type DutyR = crate::FieldReader;
+#[doc = "Field `DUTY` writer - Duty cycle of the LED, from 0 (off) to 255 (fully on)."]
+pub type DutyW<'a, REG> = crate::FieldWriter<'a, REG, 8>;
+impl R {
+    #[doc = "Bits 0:7 - Duty cycle of the LED, from 0 (off) to 255 (fully on)."]
+    #[inline(always)]
+    pub fn duty(&self) -> DutyR {
+        DutyR::new((self.bits & 0xff) as u8)
+    }
+}
+impl W {
+    #[doc = "Bits 0:7 - Duty cycle of the LED, from 0 (off) to 255 (fully on)."]
+    #[inline(always)]
+    pub fn duty(&mut self) -> DutyW<'_, LedPwmSpec> {
+        DutyW::new(self, 0)
+    }
+}
+#[doc = "Brightness of the on-board LEDs (PWM duty cycle, applied when the LED is on).\n\nYou can [`read`](crate::Reg::read) this register and get [`led_pwm::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`led_pwm::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct LedPwmSpec;
+impl crate::RegisterSpec for LedPwmSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`led_pwm::R`](R) reader structure"]
+impl crate::Readable for LedPwmSpec {}
+#[doc = "`write(|w| ..)` method takes [`led_pwm::W`](W) writer structure"]
+impl crate::Writable for LedPwmSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets LED_PWM[%s] to value 0xff"]
+impl crate::Resettable for LedPwmSpec {
+    const RESET_VALUE: u32 = 0xff;
+}
@@ -0,0 +1,50 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    pending: Pending,
+    _reserved1: [u8; 0x03],
+    enable: Enable,
+    _reserved2: [u8; 0x03],
+    raw: Raw,
+    _reserved3: [u8; 0x03],
+    active: Active,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Pending interrupts, set on a rising edge of the source (write 1 to clear)"]
+    #[inline(always)]
+    pub const fn pending(&self) -> &Pending {
+        &self.pending
+    }
+    #[doc = "0x04 - Enabled interrupts, the CPU is interrupted while an enabled interrupt is pending"]
+    #[inline(always)]
+    pub const fn enable(&self) -> &Enable {
+        &self.enable
+    }
+    #[doc = "0x08 - Current level of the interrupt sources"]
+    #[inline(always)]
+    pub const fn raw(&self) -> &Raw {
+        &self.raw
+    }
+    #[doc = "0x0c - Pending and enabled interrupts (PENDING &amp; ENABLE)"]
+    #[inline(always)]
+    pub const fn active(&self) -> &Active {
+        &self.active
+    }
+}
+#[doc = "PENDING (rw) register accessor: Pending interrupts, set on a rising edge of the source (write 1 to clear)\n\nYou can [`read`](crate::Reg::read) this register and get [`pending::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`pending::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@pending`] module"]
+#[doc(alias = "PENDING")]
+pub type Pending = crate::Reg<pending::PendingSpec>;
+#[doc = "Pending interrupts, set on a rising edge of the source (write 1 to clear)"]
+pub mod pending;
+#[doc = "ENABLE (rw) register accessor: Enabled interrupts, the CPU is interrupted while an enabled interrupt is pending\n\nYou can [`read`](crate::Reg::read) this register and get [`enable::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`enable::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@enable`] module"]
+#[doc(alias = "ENABLE")]
+pub type Enable = crate::Reg<enable::EnableSpec>;
+#[doc = "Enabled interrupts, the CPU is interrupted while an enabled interrupt is pending"]
+pub mod enable;
+#[doc = "RAW (r) register accessor: Current level of the interrupt sources\n\nYou can [`read`](crate::Reg::read) this register and get [`raw::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@raw`] module"]
+#[doc(alias = "RAW")]
+pub type Raw = crate::Reg<raw::RawSpec>;
+#[doc = "Current level of the interrupt sources"]
+pub mod raw;
+pub use Raw as Active;
+pub use raw as active;
@@ -0,0 +1,92 @@
+#[doc = "Register `PENDING` reader"]
+pub type R = crate::R<PendingSpec>;
+#[doc = "Register `PENDING` writer"]
+pub type W = crate::W<PendingSpec>;
+#[doc = "Field `TIMER0` reader - Timer0 compare channels interrupt is pending (write 1 to clear)"]
+pub type Timer0R = crate::BitReader;
+#[doc = "Field `TIMER0` writer - Timer0 compare channels interrupt is pending (write 1 to clear)"]
+pub type Timer0W<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `BUTTONS` reader - Buttons interrupt is pending (write 1 to clear)"]
+pub type ButtonsR = crate::BitReader;
+#[doc = "Field `BUTTONS` writer - Buttons interrupt is pending (write 1 to clear)"]
+pub type ButtonsW<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `AUDIO` reader - Audio Streamer buffer interrupt is pending (write 1 to clear)"]
+pub type AudioR = crate::BitReader;
+#[doc = "Field `AUDIO` writer - Audio Streamer buffer interrupt is pending (write 1 to clear)"]
+pub type AudioW<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `SPI0` reader - SPI0 transfers interrupt is pending (write 1 to clear)"]
+pub type Spi0R = crate::BitReader;
+#[doc = "Field `SPI0` writer - SPI0 transfers interrupt is pending (write 1 to clear)"]
+pub type Spi0W<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `SPI1` reader - SPI1 transfers interrupt is pending (write 1 to clear)"]
+pub type Spi1R = crate::BitReader;
+#[doc = "Field `SPI1` writer - SPI1 transfers interrupt is pending (write 1 to clear)"]
+pub type Spi1W<'a, REG> = crate::BitWriter1C<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - Timer0 compare channels interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn timer0(&self) -> Timer0R {
+        Timer0R::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - Buttons interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn buttons(&self) -> ButtonsR {
+        ButtonsR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - Audio Streamer buffer interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn audio(&self) -> AudioR {
+        AudioR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - SPI0 transfers interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn spi0(&self) -> Spi0R {
+        Spi0R::new(((self.bits >> 3) & 1) != 0)
+    }
+    #[doc = "Bit 4 - SPI1 transfers interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn spi1(&self) -> Spi1R {
+        Spi1R::new(((self.bits >> 4) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - Timer0 compare channels interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn timer0(&mut self) -> Timer0W<'_, PendingSpec> {
+        Timer0W::new(self, 0)
+    }
+    #[doc = "Bit 1 - Buttons interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn buttons(&mut self) -> ButtonsW<'_, PendingSpec> {
+        ButtonsW::new(self, 1)
+    }
+    #[doc = "Bit 2 - Audio Streamer buffer interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn audio(&mut self) -> AudioW<'_, PendingSpec> {
+        AudioW::new(self, 2)
+    }
+    #[doc = "Bit 3 - SPI0 transfers interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn spi0(&mut self) -> Spi0W<'_, PendingSpec> {
+        Spi0W::new(self, 3)
+    }
+    #[doc = "Bit 4 - SPI1 transfers interrupt is pending (write 1 to clear)"]
+    #[inline(always)]
+    pub fn spi1(&mut self) -> Spi1W<'_, PendingSpec> {
+        Spi1W::new(self, 4)
+    }
+}
+#[doc = "Pending interrupts, set on a rising edge of the source (write 1 to clear)\n\nYou can [`read`](crate::Reg::read) this register and get [`pending::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`pending::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct PendingSpec;
+impl crate::RegisterSpec for PendingSpec {
+    type Ux = u8;
+}
+#[doc = "`read()` method returns [`pending::R`](R) reader structure"]
+impl crate::Readable for PendingSpec {}
+#[doc = "`write(|w| ..)` method takes [`pending::W`](W) writer structure"]
+impl crate::Writable for PendingSpec {
+    type Safety = crate::Unsafe;
+    const ONE_TO_MODIFY_FIELDS_BITMAP: u8 = 0x1f;
+}
+#[doc = "`reset()` method sets PENDING to value 0"]
+impl crate::Resettable for PendingSpec {}
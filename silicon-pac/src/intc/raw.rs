@@ -0,0 +1,48 @@
+#[doc = "Register `RAW` reader"]
+pub type R = crate::R<RawSpec>;
+#[doc = "Field `TIMER0` reader - Timer0 compare channels interrupt source"]
+pub type Timer0R = crate::BitReader;
+#[doc = "Field `BUTTONS` reader - Buttons interrupt source"]
+pub type ButtonsR = crate::BitReader;
+#[doc = "Field `AUDIO` reader - Audio Streamer buffer interrupt source"]
+pub type AudioR = crate::BitReader;
+#[doc = "Field `SPI0` reader - SPI0 transfers interrupt source"]
+pub type Spi0R = crate::BitReader;
+#[doc = "Field `SPI1` reader - SPI1 transfers interrupt source"]
+pub type Spi1R = crate::BitReader;
+impl R {
+    #[doc = "Bit 0 - Timer0 compare channels interrupt source"]
+    #[inline(always)]
+    pub fn timer0(&self) -> Timer0R {
+        Timer0R::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - Buttons interrupt source"]
+    #[inline(always)]
+    pub fn buttons(&self) -> ButtonsR {
+        ButtonsR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - Audio Streamer buffer interrupt source"]
+    #[inline(always)]
+    pub fn audio(&self) -> AudioR {
+        AudioR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - SPI0 transfers interrupt source"]
+    #[inline(always)]
+    pub fn spi0(&self) -> Spi0R {
+        Spi0R::new(((self.bits >> 3) & 1) != 0)
+    }
+    #[doc = "Bit 4 - SPI1 transfers interrupt source"]
+    #[inline(always)]
+    pub fn spi1(&self) -> Spi1R {
+        Spi1R::new(((self.bits >> 4) & 1) != 0)
+    }
+}
+#[doc = "Current level of the interrupt sources\n\nYou can [`read`](crate::Reg::read) this register and get [`raw::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct RawSpec;
+impl crate::RegisterSpec for RawSpec {
+    type Ux = u8;
+}
+#[doc = "`read()` method returns [`raw::R`](R) reader structure"]
+impl crate::Readable for RawSpec {}
+#[doc = "`reset()` method sets RAW to value 0"]
+impl crate::Resettable for RawSpec {}
@@ -10,7 +10,13 @@ use generic::*;
 #[doc = r"Common register and bit access and modify traits"]
 pub mod generic;
 #[cfg(feature = "rt")]
-unsafe extern "C" {}
+unsafe extern "C" {
+    fn TIMER0();
+    fn BUTTONS();
+    fn AUDIO();
+    fn SPI0();
+    fn SPI1();
+}
 #[doc(hidden)]
 #[repr(C)]
 pub union Vector {
@@ -20,7 +26,47 @@ pub union Vector {
 #[cfg(feature = "rt")]
 #[doc(hidden)]
 #[unsafe(no_mangle)]
-pub static __EXTERNAL_INTERRUPTS: [Vector; 0] = [];
+pub static __EXTERNAL_INTERRUPTS: [Vector; 5] = [
+    Vector { _handler: TIMER0 },
+    Vector { _handler: BUTTONS },
+    Vector { _handler: AUDIO },
+    Vector { _handler: SPI0 },
+    Vector { _handler: SPI1 },
+];
+#[doc = r"Enumeration of all the interrupts."]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u16)]
+pub enum Interrupt {
+    #[doc = "0 - A compare channel of Timer0 fired"]
+    TIMER0 = 0,
+    #[doc = "1 - The state of a button changed (pressed or released)"]
+    BUTTONS = 1,
+    #[doc = "2 - The Audio Streamer buffer became almost empty (32 samples or less)"]
+    AUDIO = 2,
+    #[doc = "3 - A transfer of SPI0 is complete"]
+    SPI0 = 3,
+    #[doc = "4 - A transfer of SPI1 is complete"]
+    SPI1 = 4,
+}
+unsafe impl riscv::InterruptNumber for Interrupt {
+    const MAX_INTERRUPT_NUMBER: usize = 4;
+    #[inline(always)]
+    fn number(self) -> usize {
+        self as usize
+    }
+    #[inline]
+    fn from_number(number: usize) -> riscv::result::Result<Self> {
+        match number {
+            0 => Ok(Interrupt::TIMER0),
+            1 => Ok(Interrupt::BUTTONS),
+            2 => Ok(Interrupt::AUDIO),
+            3 => Ok(Interrupt::SPI0),
+            4 => Ok(Interrupt::SPI1),
+            _ => Err(riscv::result::Error::InvalidVariant(number)),
+        }
+    }
+}
+unsafe impl riscv::ExternalInterruptNumber for Interrupt {}
 #[doc = "Timer0 peripheral (64-bit counter) at 1MHz."]
 pub type Timer0 = crate::Periph<timer0::RegisterBlock, 0x0002_0000>;
 impl core::fmt::Debug for Timer0 {
@@ -75,6 +121,42 @@ impl core::fmt::Debug for Spi1 {
 }
 #[doc = "On-board SPI1 interface (only SPI Master)."]
 pub use self::spi0 as spi1;
+#[doc = "On-board Sound Generator (3 voices with ADSR envelopes), mixed into the Audio interface after the DAC / Audio Streamer."]
+pub type SoundGen = crate::Periph<sound_gen::RegisterBlock, 0x0002_1800>;
+impl core::fmt::Debug for SoundGen {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("SoundGen").finish()
+    }
+}
+#[doc = "On-board Sound Generator (3 voices with ADSR envelopes), mixed into the Audio interface after the DAC / Audio Streamer."]
+pub mod sound_gen;
+#[doc = "Interrupt Controller, gathers the (edge-triggered) interrupt sources of the peripherals into the machine external interrupt of the CPU."]
+pub type Intc = crate::Periph<intc::RegisterBlock, 0x0002_1c00>;
+impl core::fmt::Debug for Intc {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Intc").finish()
+    }
+}
+#[doc = "Interrupt Controller, gathers the (edge-triggered) interrupt sources of the peripherals into the machine external interrupt of the CPU."]
+pub mod intc;
+#[doc = "Serial port (8N1) wired to the USB-serial link, with 16-byte TX and RX FIFOs."]
+pub type Uart = crate::Periph<uart::RegisterBlock, 0x0002_2000>;
+impl core::fmt::Debug for Uart {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart").finish()
+    }
+}
+#[doc = "Serial port (8N1) wired to the USB-serial link, with 16-byte TX and RX FIFOs."]
+pub mod uart;
+#[doc = "Watchdog timer, resets the system when it is not fed in time."]
+pub type Watchdog = crate::Periph<watchdog::RegisterBlock, 0x0002_2400>;
+impl core::fmt::Debug for Watchdog {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Watchdog").finish()
+    }
+}
+#[doc = "Watchdog timer, resets the system when it is not fed in time."]
+pub mod watchdog;
 #[doc = "On-board Display interface, with hardware Framebuffer (128x128, RGB565)."]
 pub type Display = crate::Periph<display::RegisterBlock, 0x0002_7c00>;
 impl core::fmt::Debug for Display {
@@ -101,6 +183,14 @@ pub struct Peripherals {
     pub spi0: Spi0,
     #[doc = "SPI1"]
     pub spi1: Spi1,
+    #[doc = "SoundGen"]
+    pub sound_gen: SoundGen,
+    #[doc = "INTC"]
+    pub intc: Intc,
+    #[doc = "UART"]
+    pub uart: Uart,
+    #[doc = "WATCHDOG"]
+    pub watchdog: Watchdog,
     #[doc = "Display"]
     pub display: Display,
 }
@@ -131,6 +221,10 @@ impl Peripherals {
             audio_streamer: unsafe { AudioStreamer::steal() },
             spi0: unsafe { Spi0::steal() },
             spi1: unsafe { Spi1::steal() },
+            sound_gen: unsafe { SoundGen::steal() },
+            intc: unsafe { Intc::steal() },
+            uart: unsafe { Uart::steal() },
+            watchdog: unsafe { Watchdog::steal() },
             display: unsafe { Display::steal() },
         }
     }
@@ -0,0 +1,35 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    control: Control,
+    _reserved1: [u8; 0x0f],
+    ch: [Ch; 3],
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Control the Sound Generator."]
+    #[inline(always)]
+    pub const fn control(&self) -> &Control {
+        &self.control
+    }
+    #[doc = "0x10..0x40 - Voice of the Sound Generator."]
+    #[inline(always)]
+    pub const fn ch(&self, n: usize) -> &Ch {
+        &self.ch[n]
+    }
+    #[doc = "Iterator for array of:"]
+    #[doc = "0x10..0x40 - Voice of the Sound Generator."]
+    #[inline(always)]
+    pub fn ch_iter(&self) -> impl Iterator<Item = &Ch> {
+        self.ch.iter()
+    }
+}
+#[doc = "CONTROL (rw) register accessor: Control the Sound Generator.\n\nYou can [`read`](crate::Reg::read) this register and get [`control::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@control`] module"]
+#[doc(alias = "CONTROL")]
+pub type Control = crate::Reg<control::ControlSpec>;
+#[doc = "Control the Sound Generator."]
+pub mod control;
+#[doc = "Voice of the Sound Generator."]
+pub use self::ch::Ch;
+#[doc = r"Cluster"]
+#[doc = "Voice of the Sound Generator."]
+pub mod ch;
@@ -0,0 +1,43 @@
+#[repr(C)]
+#[doc = "Voice of the Sound Generator."]
+#[doc(alias = "CH")]
+pub struct Ch {
+    freq: Freq,
+    _reserved1: [u8; 0x02],
+    control: Control,
+    _reserved2: [u8; 0x02],
+    envelope: Envelope,
+    _reserved_end: [u8; 0x06],
+}
+impl Ch {
+    #[doc = "0x00 - Frequency of the voice."]
+    #[inline(always)]
+    pub const fn freq(&self) -> &Freq {
+        &self.freq
+    }
+    #[doc = "0x04 - Waveform, gate and volume of the voice."]
+    #[inline(always)]
+    pub const fn control(&self) -> &Control {
+        &self.control
+    }
+    #[doc = "0x08 - ADSR envelope of the voice. Each rate N moves the level by one step every 16*N+1 samples."]
+    #[inline(always)]
+    pub const fn envelope(&self) -> &Envelope {
+        &self.envelope
+    }
+}
+#[doc = "FREQ (w) register accessor: Frequency of the voice.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`freq::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@freq`] module"]
+#[doc(alias = "FREQ")]
+pub type Freq = crate::Reg<freq::FreqSpec>;
+#[doc = "Frequency of the voice."]
+pub mod freq;
+#[doc = "CONTROL (w) register accessor: Waveform, gate and volume of the voice.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@control`] module"]
+#[doc(alias = "CONTROL")]
+pub type Control = crate::Reg<control::ControlSpec>;
+#[doc = "Waveform, gate and volume of the voice."]
+pub mod control;
+#[doc = "ENVELOPE (w) register accessor: ADSR envelope of the voice. Each rate N moves the level by one step every 16*N+1 samples.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`envelope::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@envelope`] module"]
+#[doc(alias = "ENVELOPE")]
+pub type Envelope = crate::Reg<envelope::EnvelopeSpec>;
+#[doc = "ADSR envelope of the voice. Each rate N moves the level by one step every 16*N+1 samples."]
+pub mod envelope;
@@ -0,0 +1,36 @@
+#[doc = "Register `CONTROL` writer"]
+pub type W = crate::W<ControlSpec>;
+#[doc = "Field `WAVEFORM` writer - Waveform of the voice (0=square, 1=triangle, 2=noise, 3=sawtooth)."]
+pub type WaveformW<'a, REG> = crate::FieldWriter<'a, REG, 2>;
+#[doc = "Field `GATE` writer - Gate of the voice. A rising edge starts the attack, a falling edge starts the release."]
+pub type GateW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `VOLUME` writer - Volume of the voice."]
+pub type VolumeW<'a, REG> = crate::FieldWriter<'a, REG, 8>;
+impl W {
+    #[doc = "Bits 0:1 - Waveform of the voice (0=square, 1=triangle, 2=noise, 3=sawtooth)."]
+    #[inline(always)]
+    pub fn waveform(&mut self) -> WaveformW<'_, ControlSpec> {
+        WaveformW::new(self, 0)
+    }
+    #[doc = "Bit 2 - Gate of the voice. A rising edge starts the attack, a falling edge starts the release."]
+    #[inline(always)]
+    pub fn gate(&mut self) -> GateW<'_, ControlSpec> {
+        GateW::new(self, 2)
+    }
+    #[doc = "Bits 8:15 - Volume of the voice."]
+    #[inline(always)]
+    pub fn volume(&mut self) -> VolumeW<'_, ControlSpec> {
+        VolumeW::new(self, 8)
+    }
+}
+#[doc = "Waveform, gate and volume of the voice.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ControlSpec;
+impl crate::RegisterSpec for ControlSpec {
+    type Ux = u16;
+}
+#[doc = "`write(|w| ..)` method takes [`control::W`](W) writer structure"]
+impl crate::Writable for ControlSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CONTROL to value 0"]
+impl crate::Resettable for ControlSpec {}
@@ -0,0 +1,43 @@
+#[doc = "Register `ENVELOPE` writer"]
+pub type W = crate::W<EnvelopeSpec>;
+#[doc = "Field `ATTACK` writer - Attack rate (level rising from its current value to the maximum)."]
+pub type AttackW<'a, REG> = crate::FieldWriter<'a, REG, 4>;
+#[doc = "Field `DECAY` writer - Decay rate (level falling from the maximum to the sustain level)."]
+pub type DecayW<'a, REG> = crate::FieldWriter<'a, REG, 4>;
+#[doc = "Field `SUSTAIN` writer - Sustain level (held while the gate is set). A sustain level of 0 ends the note after the decay."]
+pub type SustainW<'a, REG> = crate::FieldWriter<'a, REG, 4>;
+#[doc = "Field `RELEASE` writer - Release rate (level falling to 0 once the gate is cleared)."]
+pub type ReleaseW<'a, REG> = crate::FieldWriter<'a, REG, 4>;
+impl W {
+    #[doc = "Bits 0:3 - Attack rate (level rising from its current value to the maximum)."]
+    #[inline(always)]
+    pub fn attack(&mut self) -> AttackW<'_, EnvelopeSpec> {
+        AttackW::new(self, 0)
+    }
+    #[doc = "Bits 4:7 - Decay rate (level falling from the maximum to the sustain level)."]
+    #[inline(always)]
+    pub fn decay(&mut self) -> DecayW<'_, EnvelopeSpec> {
+        DecayW::new(self, 4)
+    }
+    #[doc = "Bits 8:11 - Sustain level (held while the gate is set). A sustain level of 0 ends the note after the decay."]
+    #[inline(always)]
+    pub fn sustain(&mut self) -> SustainW<'_, EnvelopeSpec> {
+        SustainW::new(self, 8)
+    }
+    #[doc = "Bits 12:15 - Release rate (level falling to 0 once the gate is cleared)."]
+    #[inline(always)]
+    pub fn release(&mut self) -> ReleaseW<'_, EnvelopeSpec> {
+        ReleaseW::new(self, 12)
+    }
+}
+#[doc = "ADSR envelope of the voice. Each rate N moves the level by one step every 16*N+1 samples.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`envelope::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct EnvelopeSpec;
+impl crate::RegisterSpec for EnvelopeSpec {
+    type Ux = u16;
+}
+#[doc = "`write(|w| ..)` method takes [`envelope::W`](W) writer structure"]
+impl crate::Writable for EnvelopeSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets ENVELOPE to value 0"]
+impl crate::Resettable for EnvelopeSpec {}
@@ -0,0 +1,22 @@
+#[doc = "Register `FREQ` writer"]
+pub type W = crate::W<FreqSpec>;
+#[doc = "Field `STEP` writer - Phase step per sample (frequency = STEP * 48000 / 65536 Hz)."]
+pub type StepW<'a, REG> = crate::FieldWriter<'a, REG, 16, u16>;
+impl W {
+    #[doc = "Bits 0:15 - Phase step per sample (frequency = STEP * 48000 / 65536 Hz)."]
+    #[inline(always)]
+    pub fn step(&mut self) -> StepW<'_, FreqSpec> {
+        StepW::new(self, 0)
+    }
+}
+#[doc = "Frequency of the voice.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`freq::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct FreqSpec;
+impl crate::RegisterSpec for FreqSpec {
+    type Ux = u16;
+}
+#[doc = "`write(|w| ..)` method takes [`freq::W`](W) writer structure"]
+impl crate::Writable for FreqSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets FREQ to value 0"]
+impl crate::Resettable for FreqSpec {}
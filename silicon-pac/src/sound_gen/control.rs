@@ -0,0 +1,67 @@
+#[doc = "Register `CONTROL` reader"]
+pub type R = crate::R<ControlSpec>;
+#[doc = "Register `CONTROL` writer"]
+pub type W = crate::W<ControlSpec>;
+#[doc = "Field `ENABLE` reader - Enable the Sound Generator (mix the voices into the audio output)."]
+pub type EnableR = crate::BitReader;
+#[doc = "Field `ENABLE` writer - Enable the Sound Generator (mix the voices into the audio output)."]
+pub type EnableW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `ACTIVE(0-2)` reader - Indicates if the envelope of the voice is running (the voice is audible)."]
+pub type ActiveR = crate::BitReader;
+impl R {
+    #[doc = "Bit 0 - Enable the Sound Generator (mix the voices into the audio output)."]
+    #[inline(always)]
+    pub fn enable(&self) -> EnableR {
+        EnableR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Indicates if the envelope of the voice is running (the voice is audible)."]
+    #[doc = ""]
+    #[doc = "<div class=\"warning\">`n` is number of field in register. `n == 0` corresponds to `ACTIVE0` field.</div>"]
+    #[inline(always)]
+    pub fn active(&self, n: u8) -> ActiveR {
+        #[allow(clippy::no_effect)]
+        [(); 3][n as usize];
+        ActiveR::new(((self.bits >> (n + 4)) & 1) != 0)
+    }
+    #[doc = "Iterator for array of:"]
+    #[doc = "Indicates if the envelope of the voice is running (the voice is audible)."]
+    #[inline(always)]
+    pub fn active_iter(&self) -> impl Iterator<Item = ActiveR> + '_ {
+        (0..3).map(move |n| ActiveR::new(((self.bits >> (n + 4)) & 1) != 0))
+    }
+    #[doc = "Bit 4 - Indicates if the envelope of the voice is running (the voice is audible)."]
+    #[inline(always)]
+    pub fn active0(&self) -> ActiveR {
+        ActiveR::new(((self.bits >> 4) & 1) != 0)
+    }
+    #[doc = "Bit 5 - Indicates if the envelope of the voice is running (the voice is audible)."]
+    #[inline(always)]
+    pub fn active1(&self) -> ActiveR {
+        ActiveR::new(((self.bits >> 5) & 1) != 0)
+    }
+    #[doc = "Bit 6 - Indicates if the envelope of the voice is running (the voice is audible)."]
+    #[inline(always)]
+    pub fn active2(&self) -> ActiveR {
+        ActiveR::new(((self.bits >> 6) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - Enable the Sound Generator (mix the voices into the audio output)."]
+    #[inline(always)]
+    pub fn enable(&mut self) -> EnableW<'_, ControlSpec> {
+        EnableW::new(self, 0)
+    }
+}
+#[doc = "Control the Sound Generator.\n\nYou can [`read`](crate::Reg::read) this register and get [`control::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ControlSpec;
+impl crate::RegisterSpec for ControlSpec {
+    type Ux = u8;
+}
+#[doc = "`read()` method returns [`control::R`](R) reader structure"]
+impl crate::Readable for ControlSpec {}
+#[doc = "`write(|w| ..)` method takes [`control::W`](W) writer structure"]
+impl crate::Writable for ControlSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CONTROL to value 0"]
+impl crate::Resettable for ControlSpec {}
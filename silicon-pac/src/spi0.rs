@@ -2,27 +2,30 @@
 #[doc = "Register block"]
 pub struct RegisterBlock {
     ctrl: Ctrl,
-    _reserved1: [u8; 0x03],
     write_data: WriteData,
-    _reserved2: [u8; 0x03],
+    _reserved1: [u8; 0x03],
     read_data: ReadData,
-    _reserved3: [u8; 0x03],
+    _reserved2: [u8; 0x03],
     status: Status,
-    _reserved4: [u8; 0x03],
+    _reserved3: [u8; 0x03],
     read_and_status: ReadAndStatus,
+    _reserved4: [u8; 0x02],
+    rx_data: RxData,
+    _reserved5: [u8; 0x02],
+    fifo_status: FifoStatus,
 }
 impl RegisterBlock {
-    #[doc = "0x00 - Control of the SPI0 interface."]
+    #[doc = "0x00 - Control and configuration of the SPI0 interface (the configuration must only be changed while idle)."]
     #[inline(always)]
     pub const fn ctrl(&self) -> &Ctrl {
         &self.ctrl
     }
-    #[doc = "0x04 - Write a byte of data to transmit on the SPI interface. Important: This will trigger the SPI transfer."]
+    #[doc = "0x04 - Write a byte of data to transmit on the SPI interface. It is pushed to the TX FIFO (dropped when full), and sent as soon as the previous bytes are."]
     #[inline(always)]
     pub const fn write_data(&self) -> &WriteData {
         &self.write_data
     }
-    #[doc = "0x08 - Read a byte of data from the SPI interface."]
+    #[doc = "0x08 - Read the last byte received from the SPI interface."]
     #[inline(always)]
     pub const fn read_data(&self) -> &ReadData {
         &self.read_data
@@ -37,21 +40,31 @@ impl RegisterBlock {
     pub const fn read_and_status(&self) -> &ReadAndStatus {
         &self.read_and_status
     }
+    #[doc = "0x14 - Pop a byte from the RX FIFO."]
+    #[inline(always)]
+    pub const fn rx_data(&self) -> &RxData {
+        &self.rx_data
+    }
+    #[doc = "0x18 - Flags and levels of the TX and RX FIFOs."]
+    #[inline(always)]
+    pub const fn fifo_status(&self) -> &FifoStatus {
+        &self.fifo_status
+    }
 }
-#[doc = "CTRL (w) register accessor: Control of the SPI0 interface.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ctrl`] module"]
+#[doc = "CTRL (rw) register accessor: Control and configuration of the SPI0 interface (the configuration must only be changed while idle).\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ctrl`] module"]
 #[doc(alias = "CTRL")]
 pub type Ctrl = crate::Reg<ctrl::CtrlSpec>;
-#[doc = "Control of the SPI0 interface."]
+#[doc = "Control and configuration of the SPI0 interface (the configuration must only be changed while idle)."]
 pub mod ctrl;
-#[doc = "WRITE_DATA (w) register accessor: Write a byte of data to transmit on the SPI interface. Important: This will trigger the SPI transfer.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`write_data::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@write_data`] module"]
+#[doc = "WRITE_DATA (w) register accessor: Write a byte of data to transmit on the SPI interface. It is pushed to the TX FIFO (dropped when full), and sent as soon as the previous bytes are.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`write_data::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@write_data`] module"]
 #[doc(alias = "WRITE_DATA")]
 pub type WriteData = crate::Reg<write_data::WriteDataSpec>;
-#[doc = "Write a byte of data to transmit on the SPI interface. Important: This will trigger the SPI transfer."]
+#[doc = "Write a byte of data to transmit on the SPI interface. It is pushed to the TX FIFO (dropped when full), and sent as soon as the previous bytes are."]
 pub mod write_data;
-#[doc = "READ_DATA (r) register accessor: Read a byte of data from the SPI interface.\n\nYou can [`read`](crate::Reg::read) this register and get [`read_data::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@read_data`] module"]
+#[doc = "READ_DATA (r) register accessor: Read the last byte received from the SPI interface.\n\nYou can [`read`](crate::Reg::read) this register and get [`read_data::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@read_data`] module"]
 #[doc(alias = "READ_DATA")]
 pub type ReadData = crate::Reg<read_data::ReadDataSpec>;
-#[doc = "Read a byte of data from the SPI interface."]
+#[doc = "Read the last byte received from the SPI interface."]
 pub mod read_data;
 #[doc = "STATUS (r) register accessor: Read the status of the SPI interface.\n\nYou can [`read`](crate::Reg::read) this register and get [`status::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@status`] module"]
 #[doc(alias = "STATUS")]
@@ -63,3 +76,13 @@ pub mod status;
 pub type ReadAndStatus = crate::Reg<read_and_status::ReadAndStatusSpec>;
 #[doc = "Read the data received and the status of the SPI interface."]
 pub mod read_and_status;
+#[doc = "RX_DATA (r) register accessor: Pop a byte from the RX FIFO.\n\nYou can [`read`](crate::Reg::read) this register and get [`rx_data::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@rx_data`] module"]
+#[doc(alias = "RX_DATA")]
+pub type RxData = crate::Reg<rx_data::RxDataSpec>;
+#[doc = "Pop a byte from the RX FIFO."]
+pub mod rx_data;
+#[doc = "FIFO_STATUS (r) register accessor: Flags and levels of the TX and RX FIFOs.\n\nYou can [`read`](crate::Reg::read) this register and get [`fifo_status::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@fifo_status`] module"]
+#[doc(alias = "FIFO_STATUS")]
+pub type FifoStatus = crate::Reg<fifo_status::FifoStatusSpec>;
+#[doc = "Flags and levels of the TX and RX FIFOs."]
+pub mod fifo_status;
@@ -1,26 +1,95 @@
+#[doc = "Register `CTRL` reader"]
+pub type R = crate::R<CtrlSpec>;
 #[doc = "Register `CTRL` writer"]
 pub type W = crate::W<CtrlSpec>;
-#[doc = "Field `START` writer - Start the SPI transfer."]
+#[doc = "Field `START` writer - Send the last byte written to WRITE_DATA again (pushed to the TX FIFO)."]
 pub type StartW<'a, REG> = crate::BitWriter<'a, REG>;
-#[doc = "Field `RESET` writer - Reset the SPI0 interface."]
+#[doc = "Field `RESET` writer - Reset the SPI0 interface (and clear the FIFOs)."]
 pub type ResetW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `RX_CLEAR` writer - Clear the RX FIFO and the overrun flag."]
+pub type RxClearW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `CPOL` reader - Clock polarity (idle level of SPI_CLK)."]
+pub type CpolR = crate::BitReader;
+#[doc = "Field `CPOL` writer - Clock polarity (idle level of SPI_CLK)."]
+pub type CpolW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `CPHA` reader - Clock phase (0: sample on the first edge, 1: on the second edge)."]
+pub type CphaR = crate::BitReader;
+#[doc = "Field `CPHA` writer - Clock phase (0: sample on the first edge, 1: on the second edge)."]
+pub type CphaW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `LSB_FIRST` reader - Bit order (0: MSB first, 1: LSB first)."]
+pub type LsbFirstR = crate::BitReader;
+#[doc = "Field `LSB_FIRST` writer - Bit order (0: MSB first, 1: LSB first)."]
+pub type LsbFirstW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `DIVIDER` reader - Clock divider (SPI_CLK = reference clock / (2 * (DIVIDER + 1)))."]
+pub type DividerR = crate::FieldReader<u16>;
+#[doc = "Field `DIVIDER` writer - Clock divider (SPI_CLK = reference clock / (2 * (DIVIDER + 1)))."]
+pub type DividerW<'a, REG> = crate::FieldWriter<'a, REG, 16, u16>;
+impl R {
+    #[doc = "Bit 8 - Clock polarity (idle level of SPI_CLK)."]
+    #[inline(always)]
+    pub fn cpol(&self) -> CpolR {
+        CpolR::new(((self.bits >> 8) & 1) != 0)
+    }
+    #[doc = "Bit 9 - Clock phase (0: sample on the first edge, 1: on the second edge)."]
+    #[inline(always)]
+    pub fn cpha(&self) -> CphaR {
+        CphaR::new(((self.bits >> 9) & 1) != 0)
+    }
+    #[doc = "Bit 10 - Bit order (0: MSB first, 1: LSB first)."]
+    #[inline(always)]
+    pub fn lsb_first(&self) -> LsbFirstR {
+        LsbFirstR::new(((self.bits >> 10) & 1) != 0)
+    }
+    #[doc = "Bits 16:31 - Clock divider (SPI_CLK = reference clock / (2 * (DIVIDER + 1)))."]
+    #[inline(always)]
+    pub fn divider(&self) -> DividerR {
+        DividerR::new(((self.bits >> 16) & 0xffff) as u16)
+    }
+}
 impl W {
-    #[doc = "Bit 0 - Start the SPI transfer."]
+    #[doc = "Bit 0 - Send the last byte written to WRITE_DATA again (pushed to the TX FIFO)."]
     #[inline(always)]
     pub fn start(&mut self) -> StartW<'_, CtrlSpec> {
         StartW::new(self, 0)
     }
-    #[doc = "Bit 1 - Reset the SPI0 interface."]
+    #[doc = "Bit 1 - Reset the SPI0 interface (and clear the FIFOs)."]
     #[inline(always)]
     pub fn reset(&mut self) -> ResetW<'_, CtrlSpec> {
         ResetW::new(self, 1)
     }
+    #[doc = "Bit 2 - Clear the RX FIFO and the overrun flag."]
+    #[inline(always)]
+    pub fn rx_clear(&mut self) -> RxClearW<'_, CtrlSpec> {
+        RxClearW::new(self, 2)
+    }
+    #[doc = "Bit 8 - Clock polarity (idle level of SPI_CLK)."]
+    #[inline(always)]
+    pub fn cpol(&mut self) -> CpolW<'_, CtrlSpec> {
+        CpolW::new(self, 8)
+    }
+    #[doc = "Bit 9 - Clock phase (0: sample on the first edge, 1: on the second edge)."]
+    #[inline(always)]
+    pub fn cpha(&mut self) -> CphaW<'_, CtrlSpec> {
+        CphaW::new(self, 9)
+    }
+    #[doc = "Bit 10 - Bit order (0: MSB first, 1: LSB first)."]
+    #[inline(always)]
+    pub fn lsb_first(&mut self) -> LsbFirstW<'_, CtrlSpec> {
+        LsbFirstW::new(self, 10)
+    }
+    #[doc = "Bits 16:31 - Clock divider (SPI_CLK = reference clock / (2 * (DIVIDER + 1)))."]
+    #[inline(always)]
+    pub fn divider(&mut self) -> DividerW<'_, CtrlSpec> {
+        DividerW::new(self, 16)
+    }
 }
-#[doc = "Control of the SPI0 interface.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+#[doc = "Control and configuration of the SPI0 interface (the configuration must only be changed while idle).\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
 pub struct CtrlSpec;
 impl crate::RegisterSpec for CtrlSpec {
-    type Ux = u8;
+    type Ux = u32;
 }
+#[doc = "`read()` method returns [`ctrl::R`](R) reader structure"]
+impl crate::Readable for CtrlSpec {}
 #[doc = "`write(|w| ..)` method takes [`ctrl::W`](W) writer structure"]
 impl crate::Writable for CtrlSpec {
     type Safety = crate::Unsafe;
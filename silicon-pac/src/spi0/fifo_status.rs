@@ -0,0 +1,64 @@
+#[doc = "Register `FIFO_STATUS` reader"]
+pub type R = crate::R<FifoStatusSpec>;
+#[doc = "Field `TX_FULL` reader - The TX FIFO is full."]
+pub type TxFullR = crate::BitReader;
+#[doc = "Field `TX_EMPTY` reader - The TX FIFO is empty."]
+pub type TxEmptyR = crate::BitReader;
+#[doc = "Field `RX_AVAILABLE` reader - The RX FIFO is not empty."]
+pub type RxAvailableR = crate::BitReader;
+#[doc = "Field `RX_FULL` reader - The RX FIFO is full."]
+pub type RxFullR = crate::BitReader;
+#[doc = "Field `OVERRUN` reader - A byte was received while the RX FIFO was full, it was dropped (cleared by RX_CLEAR)."]
+pub type OverrunR = crate::BitReader;
+#[doc = "Field `TX_COUNT` reader - Number of bytes in the TX FIFO (0-8)."]
+pub type TxCountR = crate::FieldReader;
+#[doc = "Field `RX_COUNT` reader - Number of bytes in the RX FIFO (0-8)."]
+pub type RxCountR = crate::FieldReader;
+impl R {
+    #[doc = "Bit 0 - The TX FIFO is full."]
+    #[inline(always)]
+    pub fn tx_full(&self) -> TxFullR {
+        TxFullR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - The TX FIFO is empty."]
+    #[inline(always)]
+    pub fn tx_empty(&self) -> TxEmptyR {
+        TxEmptyR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - The RX FIFO is not empty."]
+    #[inline(always)]
+    pub fn rx_available(&self) -> RxAvailableR {
+        RxAvailableR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - The RX FIFO is full."]
+    #[inline(always)]
+    pub fn rx_full(&self) -> RxFullR {
+        RxFullR::new(((self.bits >> 3) & 1) != 0)
+    }
+    #[doc = "Bit 4 - A byte was received while the RX FIFO was full, it was dropped (cleared by RX_CLEAR)."]
+    #[inline(always)]
+    pub fn overrun(&self) -> OverrunR {
+        OverrunR::new(((self.bits >> 4) & 1) != 0)
+    }
+    #[doc = "Bits 8:15 - Number of bytes in the TX FIFO (0-8)."]
+    #[inline(always)]
+    pub fn tx_count(&self) -> TxCountR {
+        TxCountR::new(((self.bits >> 8) & 0xff) as u8)
+    }
+    #[doc = "Bits 16:23 - Number of bytes in the RX FIFO (0-8)."]
+    #[inline(always)]
+    pub fn rx_count(&self) -> RxCountR {
+        RxCountR::new(((self.bits >> 16) & 0xff) as u8)
+    }
+}
+#[doc = "Flags and levels of the TX and RX FIFOs.\n\nYou can [`read`](crate::Reg::read) this register and get [`fifo_status::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct FifoStatusSpec;
+impl crate::RegisterSpec for FifoStatusSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`fifo_status::R`](R) reader structure"]
+impl crate::Readable for FifoStatusSpec {}
+#[doc = "`reset()` method sets FIFO_STATUS to value 0x02"]
+impl crate::Resettable for FifoStatusSpec {
+    const RESET_VALUE: u32 = 0x02;
+}
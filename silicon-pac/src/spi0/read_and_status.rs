@@ -4,7 +4,7 @@ pub type R = crate::R<ReadAndStatusSpec>;
 pub type DataR = crate::FieldReader;
 #[doc = "Field `READY` reader - Indicates if the SPI interface has data ready to be read."]
 pub type ReadyR = crate::BitReader;
-#[doc = "Field `BUSY` reader - Indicates if the SPI interface is currently busy with a transfer."]
+#[doc = "Field `BUSY` reader - Indicates if the SPI interface is currently busy with a transfer (or the TX FIFO is not empty)."]
 pub type BusyR = crate::BitReader;
 impl R {
     #[doc = "Bits 0:7 - Data to be transmitted on the SPI interface."]
@@ -17,7 +17,7 @@ impl R {
     pub fn ready(&self) -> ReadyR {
         ReadyR::new(((self.bits >> 8) & 1) != 0)
     }
-    #[doc = "Bit 9 - Indicates if the SPI interface is currently busy with a transfer."]
+    #[doc = "Bit 9 - Indicates if the SPI interface is currently busy with a transfer (or the TX FIFO is not empty)."]
     #[inline(always)]
     pub fn busy(&self) -> BusyR {
         BusyR::new(((self.bits >> 9) & 1) != 0)
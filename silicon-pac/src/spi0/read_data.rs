@@ -9,7 +9,7 @@ impl R {
         DataR::new(self.bits)
     }
 }
-#[doc = "Read a byte of data from the SPI interface.\n\nYou can [`read`](crate::Reg::read) this register and get [`read_data::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+#[doc = "Read the last byte received from the SPI interface.\n\nYou can [`read`](crate::Reg::read) this register and get [`read_data::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
 pub struct ReadDataSpec;
 impl crate::RegisterSpec for ReadDataSpec {
     type Ux = u8;
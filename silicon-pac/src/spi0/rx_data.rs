@@ -0,0 +1,27 @@
+#[doc = "Register `RX_DATA` reader"]
+pub type R = crate::R<RxDataSpec>;
+#[doc = "Field `DATA` reader - Received byte (popped from the RX FIFO)."]
+pub type DataR = crate::FieldReader;
+#[doc = "Field `VALID` reader - The received byte is valid (the RX FIFO was not empty)."]
+pub type ValidR = crate::BitReader;
+impl R {
+    #[doc = "Bits 0:7 - Received byte (popped from the RX FIFO)."]
+    #[inline(always)]
+    pub fn data(&self) -> DataR {
+        DataR::new((self.bits & 0xff) as u8)
+    }
+    #[doc = "Bit 8 - The received byte is valid (the RX FIFO was not empty)."]
+    #[inline(always)]
+    pub fn valid(&self) -> ValidR {
+        ValidR::new(((self.bits >> 8) & 1) != 0)
+    }
+}
+#[doc = "Pop a byte from the RX FIFO.\n\nYou can [`read`](crate::Reg::read) this register and get [`rx_data::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct RxDataSpec;
+impl crate::RegisterSpec for RxDataSpec {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [`rx_data::R`](R) reader structure"]
+impl crate::Readable for RxDataSpec {}
+#[doc = "`reset()` method sets RX_DATA to value 0"]
+impl crate::Resettable for RxDataSpec {}
@@ -2,7 +2,7 @@
 pub type R = crate::R<StatusSpec>;
 #[doc = "Field `READY` reader - Indicates if the SPI interface has data ready to be read."]
 pub type ReadyR = crate::BitReader;
-#[doc = "Field `BUSY` reader - Indicates if the SPI interface is currently busy with a transfer."]
+#[doc = "Field `BUSY` reader - Indicates if the SPI interface is currently busy with a transfer (or the TX FIFO is not empty)."]
 pub type BusyR = crate::BitReader;
 impl R {
     #[doc = "Bit 0 - Indicates if the SPI interface has data ready to be read."]
@@ -10,7 +10,7 @@ impl R {
     pub fn ready(&self) -> ReadyR {
         ReadyR::new((self.bits & 1) != 0)
     }
-    #[doc = "Bit 1 - Indicates if the SPI interface is currently busy with a transfer."]
+    #[doc = "Bit 1 - Indicates if the SPI interface is currently busy with a transfer (or the TX FIFO is not empty)."]
     #[inline(always)]
     pub fn busy(&self) -> BusyR {
         BusyR::new(((self.bits >> 1) & 1) != 0)
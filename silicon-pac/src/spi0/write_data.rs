@@ -9,7 +9,7 @@ impl W {
         DataW::new(self, 0)
     }
 }
-#[doc = "Write a byte of data to transmit on the SPI interface. Important: This will trigger the SPI transfer.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`write_data::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+#[doc = "Write a byte of data to transmit on the SPI interface. It is pushed to the TX FIFO (dropped when full), and sent as soon as the previous bytes are.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`write_data::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
 pub struct WriteDataSpec;
 impl crate::RegisterSpec for WriteDataSpec {
     type Ux = u8;
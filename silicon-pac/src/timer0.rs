@@ -3,7 +3,11 @@
 pub struct RegisterBlock {
     ctrl: Ctrl,
     _reserved1: [u8; 0x03],
-    _reserved_1_hi_value: [u8; 0x04],
+    lo_value: LoValue,
+    hi_value: HiValue,
+    status: Status,
+    _reserved4: [u8; 0x03],
+    cmp: [Cmp; 3],
 }
 impl RegisterBlock {
     #[doc = "0x00 - Control Register"]
@@ -11,15 +15,31 @@ impl RegisterBlock {
     pub const fn ctrl(&self) -> &Ctrl {
         &self.ctrl
     }
-    #[doc = "0x04 - 32 highest bits of the Timer Value"]
+    #[doc = "0x04 - 32 lowest bits of the Timer Value"]
+    #[inline(always)]
+    pub const fn lo_value(&self) -> &LoValue {
+        &self.lo_value
+    }
+    #[doc = "0x08 - 32 highest bits of the Timer Value"]
     #[inline(always)]
     pub const fn hi_value(&self) -> &HiValue {
-        unsafe { &*core::ptr::from_ref(self).cast::<u8>().add(4).cast() }
+        &self.hi_value
     }
-    #[doc = "0x04 - 32 lowest bits of the Timer Value"]
+    #[doc = "0x0c - Match flags of the compare channels"]
     #[inline(always)]
-    pub const fn lo_value(&self) -> &LoValue {
-        unsafe { &*core::ptr::from_ref(self).cast::<u8>().add(4).cast() }
+    pub const fn status(&self) -> &Status {
+        &self.status
+    }
+    #[doc = "0x10..0x40 - Compare channel of the Timer"]
+    #[inline(always)]
+    pub const fn cmp(&self, n: usize) -> &Cmp {
+        &self.cmp[n]
+    }
+    #[doc = "Iterator for array of:"]
+    #[doc = "0x10..0x40 - Compare channel of the Timer"]
+    #[inline(always)]
+    pub fn cmp_iter(&self) -> impl Iterator<Item = &Cmp> {
+        self.cmp.iter()
     }
 }
 #[doc = "CTRL (rw) register accessor: Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ctrl`] module"]
@@ -37,3 +57,13 @@ pub mod lo_value;
 pub type HiValue = crate::Reg<hi_value::HiValueSpec>;
 #[doc = "32 highest bits of the Timer Value"]
 pub mod hi_value;
+#[doc = "STATUS (rw) register accessor: Match flags of the compare channels\n\nYou can [`read`](crate::Reg::read) this register and get [`status::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`status::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@status`] module"]
+#[doc(alias = "STATUS")]
+pub type Status = crate::Reg<status::StatusSpec>;
+#[doc = "Match flags of the compare channels"]
+pub mod status;
+#[doc = "Compare channel of the Timer"]
+pub use self::cmp::Cmp;
+#[doc = r"Cluster"]
+#[doc = "Compare channel of the Timer"]
+pub mod cmp;
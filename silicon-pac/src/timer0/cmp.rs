@@ -0,0 +1,41 @@
+#[repr(C)]
+#[doc = "Compare channel of the Timer"]
+#[doc(alias = "CMP")]
+pub struct Cmp {
+    lo: Lo,
+    hi: Hi,
+    ctrl: Ctrl,
+    _reserved_end: [u8; 0x07],
+}
+impl Cmp {
+    #[doc = "0x00 - 32 lowest bits of the compare value"]
+    #[inline(always)]
+    pub const fn lo(&self) -> &Lo {
+        &self.lo
+    }
+    #[doc = "0x04 - 32 highest bits of the compare value"]
+    #[inline(always)]
+    pub const fn hi(&self) -> &Hi {
+        &self.hi
+    }
+    #[doc = "0x08 - Control of the compare channel"]
+    #[inline(always)]
+    pub const fn ctrl(&self) -> &Ctrl {
+        &self.ctrl
+    }
+}
+#[doc = "LO (rw) register accessor: 32 lowest bits of the compare value\n\nYou can [`read`](crate::Reg::read) this register and get [`lo::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`lo::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@lo`] module"]
+#[doc(alias = "LO")]
+pub type Lo = crate::Reg<lo::LoSpec>;
+#[doc = "32 lowest bits of the compare value"]
+pub mod lo;
+#[doc = "HI (rw) register accessor: 32 highest bits of the compare value\n\nYou can [`read`](crate::Reg::read) this register and get [`hi::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`hi::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@hi`] module"]
+#[doc(alias = "HI")]
+pub type Hi = crate::Reg<hi::HiSpec>;
+#[doc = "32 highest bits of the compare value"]
+pub mod hi;
+#[doc = "CTRL (rw) register accessor: Control of the compare channel\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ctrl`] module"]
+#[doc(alias = "CTRL")]
+pub type Ctrl = crate::Reg<ctrl::CtrlSpec>;
+#[doc = "Control of the compare channel"]
+pub mod ctrl;
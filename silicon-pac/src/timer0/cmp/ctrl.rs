@@ -0,0 +1,35 @@
+#[doc = "Register `CTRL` reader"]
+pub type R = crate::R<CtrlSpec>;
+#[doc = "Register `CTRL` writer"]
+pub type W = crate::W<CtrlSpec>;
+#[doc = "Field `ENABLE` reader - Arm the channel, it fires once the Timer Value reaches the compare value (cleared when it fires)"]
+pub type EnableR = crate::BitReader;
+#[doc = "Field `ENABLE` writer - Arm the channel, it fires once the Timer Value reaches the compare value (cleared when it fires)"]
+pub type EnableW<'a, REG> = crate::BitWriter<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - Arm the channel, it fires once the Timer Value reaches the compare value (cleared when it fires)"]
+    #[inline(always)]
+    pub fn enable(&self) -> EnableR {
+        EnableR::new((self.bits & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - Arm the channel, it fires once the Timer Value reaches the compare value (cleared when it fires)"]
+    #[inline(always)]
+    pub fn enable(&mut self) -> EnableW<'_, CtrlSpec> {
+        EnableW::new(self, 0)
+    }
+}
+#[doc = "Control of the compare channel\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct CtrlSpec;
+impl crate::RegisterSpec for CtrlSpec {
+    type Ux = u8;
+}
+#[doc = "`read()` method returns [`ctrl::R`](R) reader structure"]
+impl crate::Readable for CtrlSpec {}
+#[doc = "`write(|w| ..)` method takes [`ctrl::W`](W) writer structure"]
+impl crate::Writable for CtrlSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CTRL to value 0"]
+impl crate::Resettable for CtrlSpec {}
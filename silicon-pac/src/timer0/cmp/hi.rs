@@ -0,0 +1,23 @@
+#[doc = "Register `HI` reader"]
+pub type R = crate::R<HiSpec>;
+#[doc = "Register `HI` writer"]
+pub type W = crate::W<HiSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "32 highest bits of the compare value\n\nYou can [`read`](crate::Reg::read) this register and get [`hi::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`hi::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct HiSpec;
+impl crate::RegisterSpec for HiSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`hi::R`](R) reader structure"]
+impl crate::Readable for HiSpec {}
+#[doc = "`write(|w| ..)` method takes [`hi::W`](W) writer structure"]
+impl crate::Writable for HiSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets HI to value 0"]
+impl crate::Resettable for HiSpec {}
@@ -0,0 +1,23 @@
+#[doc = "Register `LO` reader"]
+pub type R = crate::R<LoSpec>;
+#[doc = "Register `LO` writer"]
+pub type W = crate::W<LoSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "32 lowest bits of the compare value\n\nYou can [`read`](crate::Reg::read) this register and get [`lo::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`lo::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct LoSpec;
+impl crate::RegisterSpec for LoSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`lo::R`](R) reader structure"]
+impl crate::Readable for LoSpec {}
+#[doc = "`write(|w| ..)` method takes [`lo::W`](W) writer structure"]
+impl crate::Writable for LoSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets LO to value 0"]
+impl crate::Resettable for LoSpec {}
@@ -2,26 +2,26 @@
 pub type R = crate::R<CtrlSpec>;
 #[doc = "Register `CTRL` writer"]
 pub type W = crate::W<CtrlSpec>;
-#[doc = "Field `ENABLE` reader - Enable Timer"]
+#[doc = "Field `ENABLE` reader - Enable Timer (enabled at power-up)"]
 pub type EnableR = crate::BitReader;
-#[doc = "Field `ENABLE` writer - Enable Timer"]
+#[doc = "Field `ENABLE` writer - Enable Timer (enabled at power-up)"]
 pub type EnableW<'a, REG> = crate::BitWriter<'a, REG>;
-#[doc = "Field `RESET` writer - Reset Timer to zero"]
+#[doc = "Field `RESET` writer - Reset Timer to zero (self-clearing)"]
 pub type ResetW<'a, REG> = crate::BitWriter<'a, REG>;
 impl R {
-    #[doc = "Bit 0 - Enable Timer"]
+    #[doc = "Bit 0 - Enable Timer (enabled at power-up)"]
     #[inline(always)]
     pub fn enable(&self) -> EnableR {
         EnableR::new((self.bits & 1) != 0)
     }
 }
 impl W {
-    #[doc = "Bit 0 - Enable Timer"]
+    #[doc = "Bit 0 - Enable Timer (enabled at power-up)"]
     #[inline(always)]
     pub fn enable(&mut self) -> EnableW<'_, CtrlSpec> {
         EnableW::new(self, 0)
     }
-    #[doc = "Bit 1 - Reset Timer to zero"]
+    #[doc = "Bit 1 - Reset Timer to zero (self-clearing)"]
     #[inline(always)]
     pub fn reset(&mut self) -> ResetW<'_, CtrlSpec> {
         ResetW::new(self, 1)
@@ -38,5 +38,7 @@ impl crate::Readable for CtrlSpec {}
 impl crate::Writable for CtrlSpec {
     type Safety = crate::Unsafe;
 }
-#[doc = "`reset()` method sets CTRL to value 0"]
-impl crate::Resettable for CtrlSpec {}
+#[doc = "`reset()` method sets CTRL to value 0x01"]
+impl crate::Resettable for CtrlSpec {
+    const RESET_VALUE: u8 = 0x01;
+}
@@ -0,0 +1,80 @@
+#[doc = "Register `STATUS` reader"]
+pub type R = crate::R<StatusSpec>;
+#[doc = "Register `STATUS` writer"]
+pub type W = crate::W<StatusSpec>;
+#[doc = "Field `MATCH(0-2)` reader - Set when the compare channel fires (write 1 to clear)"]
+pub type MatchR = crate::BitReader;
+#[doc = "Field `MATCH(0-2)` writer - Set when the compare channel fires (write 1 to clear)"]
+pub type MatchW<'a, REG> = crate::BitWriter1C<'a, REG>;
+impl R {
+    #[doc = "Set when the compare channel fires (write 1 to clear)"]
+    #[doc = ""]
+    #[doc = "<div class=\"warning\">`n` is number of field in register. `n == 0` corresponds to `MATCH0` field.</div>"]
+    #[inline(always)]
+    pub fn match_(&self, n: u8) -> MatchR {
+        #[allow(clippy::no_effect)]
+        [(); 3][n as usize];
+        MatchR::new(((self.bits >> n) & 1) != 0)
+    }
+    #[doc = "Iterator for array of:"]
+    #[doc = "Set when the compare channel fires (write 1 to clear)"]
+    #[inline(always)]
+    pub fn match__iter(&self) -> impl Iterator<Item = MatchR> + '_ {
+        (0..3).map(move |n| MatchR::new(((self.bits >> n) & 1) != 0))
+    }
+    #[doc = "Bit 0 - Set when the compare channel fires (write 1 to clear)"]
+    #[inline(always)]
+    pub fn match0(&self) -> MatchR {
+        MatchR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - Set when the compare channel fires (write 1 to clear)"]
+    #[inline(always)]
+    pub fn match1(&self) -> MatchR {
+        MatchR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - Set when the compare channel fires (write 1 to clear)"]
+    #[inline(always)]
+    pub fn match2(&self) -> MatchR {
+        MatchR::new(((self.bits >> 2) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Set when the compare channel fires (write 1 to clear)"]
+    #[doc = ""]
+    #[doc = "<div class=\"warning\">`n` is number of field in register. `n == 0` corresponds to `MATCH0` field.</div>"]
+    #[inline(always)]
+    pub fn match_(&mut self, n: u8) -> MatchW<'_, StatusSpec> {
+        #[allow(clippy::no_effect)]
+        [(); 3][n as usize];
+        MatchW::new(self, n)
+    }
+    #[doc = "Bit 0 - Set when the compare channel fires (write 1 to clear)"]
+    #[inline(always)]
+    pub fn match0(&mut self) -> MatchW<'_, StatusSpec> {
+        MatchW::new(self, 0)
+    }
+    #[doc = "Bit 1 - Set when the compare channel fires (write 1 to clear)"]
+    #[inline(always)]
+    pub fn match1(&mut self) -> MatchW<'_, StatusSpec> {
+        MatchW::new(self, 1)
+    }
+    #[doc = "Bit 2 - Set when the compare channel fires (write 1 to clear)"]
+    #[inline(always)]
+    pub fn match2(&mut self) -> MatchW<'_, StatusSpec> {
+        MatchW::new(self, 2)
+    }
+}
+#[doc = "Match flags of the compare channels\n\nYou can [`read`](crate::Reg::read) this register and get [`status::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`status::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct StatusSpec;
+impl crate::RegisterSpec for StatusSpec {
+    type Ux = u8;
+}
+#[doc = "`read()` method returns [`status::R`](R) reader structure"]
+impl crate::Readable for StatusSpec {}
+#[doc = "`write(|w| ..)` method takes [`status::W`](W) writer structure"]
+impl crate::Writable for StatusSpec {
+    type Safety = crate::Unsafe;
+    const ONE_TO_MODIFY_FIELDS_BITMAP: u8 = 0x07;
+}
+#[doc = "`reset()` method sets STATUS to value 0"]
+impl crate::Resettable for StatusSpec {}
@@ -0,0 +1,52 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    data: Data,
+    _reserved1: [u8; 0x02],
+    status: Status,
+    ctrl: Ctrl,
+    _reserved3: [u8; 0x03],
+    baud: Baud,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Write to push a byte to the TX FIFO (dropped when full), read to pop a byte from the RX FIFO."]
+    #[inline(always)]
+    pub const fn data(&self) -> &Data {
+        &self.data
+    }
+    #[doc = "0x04 - FIFO flags and levels, and sticky error flags (write 1 to clear)."]
+    #[inline(always)]
+    pub const fn status(&self) -> &Status {
+        &self.status
+    }
+    #[doc = "0x08 - Control Register"]
+    #[inline(always)]
+    pub const fn ctrl(&self) -> &Ctrl {
+        &self.ctrl
+    }
+    #[doc = "0x0c - Baud rate divider."]
+    #[inline(always)]
+    pub const fn baud(&self) -> &Baud {
+        &self.baud
+    }
+}
+#[doc = "DATA (rw) register accessor: Write to push a byte to the TX FIFO (dropped when full), read to pop a byte from the RX FIFO.\n\nYou can [`read`](crate::Reg::read) this register and get [`data::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`data::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@data`] module"]
+#[doc(alias = "DATA")]
+pub type Data = crate::Reg<data::DataSpec>;
+#[doc = "Write to push a byte to the TX FIFO (dropped when full), read to pop a byte from the RX FIFO."]
+pub mod data;
+#[doc = "STATUS (rw) register accessor: FIFO flags and levels, and sticky error flags (write 1 to clear).\n\nYou can [`read`](crate::Reg::read) this register and get [`status::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`status::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@status`] module"]
+#[doc(alias = "STATUS")]
+pub type Status = crate::Reg<status::StatusSpec>;
+#[doc = "FIFO flags and levels, and sticky error flags (write 1 to clear)."]
+pub mod status;
+#[doc = "CTRL (rw) register accessor: Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ctrl`] module"]
+#[doc(alias = "CTRL")]
+pub type Ctrl = crate::Reg<ctrl::CtrlSpec>;
+#[doc = "Control Register"]
+pub mod ctrl;
+#[doc = "BAUD (rw) register accessor: Baud rate divider.\n\nYou can [`read`](crate::Reg::read) this register and get [`baud::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`baud::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@baud`] module"]
+#[doc(alias = "BAUD")]
+pub type Baud = crate::Reg<baud::BaudSpec>;
+#[doc = "Baud rate divider."]
+pub mod baud;
@@ -0,0 +1,37 @@
+#[doc = "Register `BAUD` reader"]
+pub type R = crate::R<BaudSpec>;
+#[doc = "Register `BAUD` writer"]
+pub type W = crate::W<BaudSpec>;
+#[doc = "Field `DIVIDER` reader - A bit lasts DIVIDER + 1 clock cycles (25MHz), e.g. 216 for 115200 bauds."]
+pub type DividerR = crate::FieldReader<u16>;
+#[doc = "Field `DIVIDER` writer - A bit lasts DIVIDER + 1 clock cycles (25MHz), e.g. 216 for 115200 bauds."]
+pub type DividerW<'a, REG> = crate::FieldWriter<'a, REG, 16, u16>;
+impl R {
+    #[doc = "Bits 0:15 - A bit lasts DIVIDER + 1 clock cycles (25MHz), e.g. 216 for 115200 bauds."]
+    #[inline(always)]
+    pub fn divider(&self) -> DividerR {
+        DividerR::new(self.bits)
+    }
+}
+impl W {
+    #[doc = "Bits 0:15 - A bit lasts DIVIDER + 1 clock cycles (25MHz), e.g. 216 for 115200 bauds."]
+    #[inline(always)]
+    pub fn divider(&mut self) -> DividerW<'_, BaudSpec> {
+        DividerW::new(self, 0)
+    }
+}
+#[doc = "Baud rate divider.\n\nYou can [`read`](crate::Reg::read) this register and get [`baud::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`baud::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct BaudSpec;
+impl crate::RegisterSpec for BaudSpec {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [`baud::R`](R) reader structure"]
+impl crate::Readable for BaudSpec {}
+#[doc = "`write(|w| ..)` method takes [`baud::W`](W) writer structure"]
+impl crate::Writable for BaudSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets BAUD to value 0xd8"]
+impl crate::Resettable for BaudSpec {
+    const RESET_VALUE: u16 = 0xd8;
+}
@@ -0,0 +1,49 @@
+#[doc = "Register `CTRL` reader"]
+pub type R = crate::R<CtrlSpec>;
+#[doc = "Register `CTRL` writer"]
+pub type W = crate::W<CtrlSpec>;
+#[doc = "Field `LOOPBACK` reader - Loopback mode: the TX line is received instead of the RX pin."]
+pub type LoopbackR = crate::BitReader;
+#[doc = "Field `LOOPBACK` writer - Loopback mode: the TX line is received instead of the RX pin."]
+pub type LoopbackW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `CLEAR_TX` writer - Drop the bytes of the TX FIFO (self-clearing, the byte being sent is not interrupted)."]
+pub type ClearTxW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `CLEAR_RX` writer - Drop the bytes of the RX FIFO (self-clearing)."]
+pub type ClearRxW<'a, REG> = crate::BitWriter<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - Loopback mode: the TX line is received instead of the RX pin."]
+    #[inline(always)]
+    pub fn loopback(&self) -> LoopbackR {
+        LoopbackR::new((self.bits & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - Loopback mode: the TX line is received instead of the RX pin."]
+    #[inline(always)]
+    pub fn loopback(&mut self) -> LoopbackW<'_, CtrlSpec> {
+        LoopbackW::new(self, 0)
+    }
+    #[doc = "Bit 1 - Drop the bytes of the TX FIFO (self-clearing, the byte being sent is not interrupted)."]
+    #[inline(always)]
+    pub fn clear_tx(&mut self) -> ClearTxW<'_, CtrlSpec> {
+        ClearTxW::new(self, 1)
+    }
+    #[doc = "Bit 2 - Drop the bytes of the RX FIFO (self-clearing)."]
+    #[inline(always)]
+    pub fn clear_rx(&mut self) -> ClearRxW<'_, CtrlSpec> {
+        ClearRxW::new(self, 2)
+    }
+}
+#[doc = "Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct CtrlSpec;
+impl crate::RegisterSpec for CtrlSpec {
+    type Ux = u8;
+}
+#[doc = "`read()` method returns [`ctrl::R`](R) reader structure"]
+impl crate::Readable for CtrlSpec {}
+#[doc = "`write(|w| ..)` method takes [`ctrl::W`](W) writer structure"]
+impl crate::Writable for CtrlSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CTRL to value 0"]
+impl crate::Resettable for CtrlSpec {}
@@ -0,0 +1,42 @@
+#[doc = "Register `DATA` reader"]
+pub type R = crate::R<DataSpec>;
+#[doc = "Register `DATA` writer"]
+pub type W = crate::W<DataSpec>;
+#[doc = "Field `BYTE` reader - Byte to send (write) / received byte (read, popped from the RX FIFO)."]
+pub type ByteR = crate::FieldReader;
+#[doc = "Field `BYTE` writer - Byte to send (write) / received byte (read, popped from the RX FIFO)."]
+pub type ByteW<'a, REG> = crate::FieldWriter<'a, REG, 8>;
+#[doc = "Field `VALID` reader - The received byte is valid (the RX FIFO was not empty)."]
+pub type ValidR = crate::BitReader;
+impl R {
+    #[doc = "Bits 0:7 - Byte to send (write) / received byte (read, popped from the RX FIFO)."]
+    #[inline(always)]
+    pub fn byte(&self) -> ByteR {
+        ByteR::new((self.bits & 0xff) as u8)
+    }
+    #[doc = "Bit 8 - The received byte is valid (the RX FIFO was not empty)."]
+    #[inline(always)]
+    pub fn valid(&self) -> ValidR {
+        ValidR::new(((self.bits >> 8) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bits 0:7 - Byte to send (write) / received byte (read, popped from the RX FIFO)."]
+    #[inline(always)]
+    pub fn byte(&mut self) -> ByteW<'_, DataSpec> {
+        ByteW::new(self, 0)
+    }
+}
+#[doc = "Write to push a byte to the TX FIFO (dropped when full), read to pop a byte from the RX FIFO.\n\nYou can [`read`](crate::Reg::read) this register and get [`data::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`data::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct DataSpec;
+impl crate::RegisterSpec for DataSpec {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [`data::R`](R) reader structure"]
+impl crate::Readable for DataSpec {}
+#[doc = "`write(|w| ..)` method takes [`data::W`](W) writer structure"]
+impl crate::Writable for DataSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets DATA to value 0"]
+impl crate::Resettable for DataSpec {}
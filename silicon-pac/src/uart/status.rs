@@ -0,0 +1,101 @@
+#[doc = "Register `STATUS` reader"]
+pub type R = crate::R<StatusSpec>;
+#[doc = "Register `STATUS` writer"]
+pub type W = crate::W<StatusSpec>;
+#[doc = "Field `TX_FULL` reader - The TX FIFO is full."]
+pub type TxFullR = crate::BitReader;
+#[doc = "Field `TX_EMPTY` reader - The TX FIFO is empty."]
+pub type TxEmptyR = crate::BitReader;
+#[doc = "Field `TX_IDLE` reader - The TX FIFO is empty and all the bytes are sent."]
+pub type TxIdleR = crate::BitReader;
+#[doc = "Field `RX_AVAILABLE` reader - The RX FIFO is not empty."]
+pub type RxAvailableR = crate::BitReader;
+#[doc = "Field `RX_FULL` reader - The RX FIFO is full."]
+pub type RxFullR = crate::BitReader;
+#[doc = "Field `OVERRUN` reader - A byte was received while the RX FIFO was full, it was dropped (write 1 to clear)."]
+pub type OverrunR = crate::BitReader;
+#[doc = "Field `OVERRUN` writer - A byte was received while the RX FIFO was full, it was dropped (write 1 to clear)."]
+pub type OverrunW<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `FRAME_ERROR` reader - A byte was received with a low stop bit, it was dropped (write 1 to clear)."]
+pub type FrameErrorR = crate::BitReader;
+#[doc = "Field `FRAME_ERROR` writer - A byte was received with a low stop bit, it was dropped (write 1 to clear)."]
+pub type FrameErrorW<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `TX_COUNT` reader - Number of bytes in the TX FIFO (0-16)."]
+pub type TxCountR = crate::FieldReader;
+#[doc = "Field `RX_COUNT` reader - Number of bytes in the RX FIFO (0-16)."]
+pub type RxCountR = crate::FieldReader;
+impl R {
+    #[doc = "Bit 0 - The TX FIFO is full."]
+    #[inline(always)]
+    pub fn tx_full(&self) -> TxFullR {
+        TxFullR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - The TX FIFO is empty."]
+    #[inline(always)]
+    pub fn tx_empty(&self) -> TxEmptyR {
+        TxEmptyR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - The TX FIFO is empty and all the bytes are sent."]
+    #[inline(always)]
+    pub fn tx_idle(&self) -> TxIdleR {
+        TxIdleR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - The RX FIFO is not empty."]
+    #[inline(always)]
+    pub fn rx_available(&self) -> RxAvailableR {
+        RxAvailableR::new(((self.bits >> 3) & 1) != 0)
+    }
+    #[doc = "Bit 4 - The RX FIFO is full."]
+    #[inline(always)]
+    pub fn rx_full(&self) -> RxFullR {
+        RxFullR::new(((self.bits >> 4) & 1) != 0)
+    }
+    #[doc = "Bit 5 - A byte was received while the RX FIFO was full, it was dropped (write 1 to clear)."]
+    #[inline(always)]
+    pub fn overrun(&self) -> OverrunR {
+        OverrunR::new(((self.bits >> 5) & 1) != 0)
+    }
+    #[doc = "Bit 6 - A byte was received with a low stop bit, it was dropped (write 1 to clear)."]
+    #[inline(always)]
+    pub fn frame_error(&self) -> FrameErrorR {
+        FrameErrorR::new(((self.bits >> 6) & 1) != 0)
+    }
+    #[doc = "Bits 8:12 - Number of bytes in the TX FIFO (0-16)."]
+    #[inline(always)]
+    pub fn tx_count(&self) -> TxCountR {
+        TxCountR::new(((self.bits >> 8) & 0x1f) as u8)
+    }
+    #[doc = "Bits 16:20 - Number of bytes in the RX FIFO (0-16)."]
+    #[inline(always)]
+    pub fn rx_count(&self) -> RxCountR {
+        RxCountR::new(((self.bits >> 16) & 0x1f) as u8)
+    }
+}
+impl W {
+    #[doc = "Bit 5 - A byte was received while the RX FIFO was full, it was dropped (write 1 to clear)."]
+    #[inline(always)]
+    pub fn overrun(&mut self) -> OverrunW<'_, StatusSpec> {
+        OverrunW::new(self, 5)
+    }
+    #[doc = "Bit 6 - A byte was received with a low stop bit, it was dropped (write 1 to clear)."]
+    #[inline(always)]
+    pub fn frame_error(&mut self) -> FrameErrorW<'_, StatusSpec> {
+        FrameErrorW::new(self, 6)
+    }
+}
+#[doc = "FIFO flags and levels, and sticky error flags (write 1 to clear).\n\nYou can [`read`](crate::Reg::read) this register and get [`status::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`status::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct StatusSpec;
+impl crate::RegisterSpec for StatusSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`status::R`](R) reader structure"]
+impl crate::Readable for StatusSpec {}
+#[doc = "`write(|w| ..)` method takes [`status::W`](W) writer structure"]
+impl crate::Writable for StatusSpec {
+    type Safety = crate::Unsafe;
+    const ONE_TO_MODIFY_FIELDS_BITMAP: u32 = 0x60;
+}
+#[doc = "`reset()` method sets STATUS to value 0x06"]
+impl crate::Resettable for StatusSpec {
+    const RESET_VALUE: u32 = 0x06;
+}
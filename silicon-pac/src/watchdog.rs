@@ -0,0 +1,50 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    ctrl: Ctrl,
+    timeout: Timeout,
+    feed: Feed,
+    status: Status,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Control Register"]
+    #[inline(always)]
+    pub const fn ctrl(&self) -> &Ctrl {
+        &self.ctrl
+    }
+    #[doc = "0x04 - Timeout, loaded in the counter when the watchdog is started or fed."]
+    #[inline(always)]
+    pub const fn timeout(&self) -> &Timeout {
+        &self.timeout
+    }
+    #[doc = "0x08 - Write the key to reload the counter with the TIMEOUT."]
+    #[inline(always)]
+    pub const fn feed(&self) -> &Feed {
+        &self.feed
+    }
+    #[doc = "0x0c - Reset cause and counter (write 1 to clear the reset flag)."]
+    #[inline(always)]
+    pub const fn status(&self) -> &Status {
+        &self.status
+    }
+}
+#[doc = "CTRL (rw) register accessor: Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ctrl`] module"]
+#[doc(alias = "CTRL")]
+pub type Ctrl = crate::Reg<ctrl::CtrlSpec>;
+#[doc = "Control Register"]
+pub mod ctrl;
+#[doc = "TIMEOUT (rw) register accessor: Timeout, loaded in the counter when the watchdog is started or fed.\n\nYou can [`read`](crate::Reg::read) this register and get [`timeout::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`timeout::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@timeout`] module"]
+#[doc(alias = "TIMEOUT")]
+pub type Timeout = crate::Reg<timeout::TimeoutSpec>;
+#[doc = "Timeout, loaded in the counter when the watchdog is started or fed."]
+pub mod timeout;
+#[doc = "FEED (w) register accessor: Write the key to reload the counter with the TIMEOUT.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`feed::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@feed`] module"]
+#[doc(alias = "FEED")]
+pub type Feed = crate::Reg<feed::FeedSpec>;
+#[doc = "Write the key to reload the counter with the TIMEOUT."]
+pub mod feed;
+#[doc = "STATUS (rw) register accessor: Reset cause and counter (write 1 to clear the reset flag).\n\nYou can [`read`](crate::Reg::read) this register and get [`status::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`status::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@status`] module"]
+#[doc(alias = "STATUS")]
+pub type Status = crate::Reg<status::StatusSpec>;
+#[doc = "Reset cause and counter (write 1 to clear the reset flag)."]
+pub mod status;
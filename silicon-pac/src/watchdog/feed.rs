@@ -0,0 +1,22 @@
+#[doc = "Register `FEED` writer"]
+pub type W = crate::W<FeedSpec>;
+#[doc = "Field `KEY` writer - Feed key, 0x0000FEED (other values are ignored)."]
+pub type KeyW<'a, REG> = crate::FieldWriter<'a, REG, 32, u32>;
+impl W {
+    #[doc = "Bits 0:31 - Feed key, 0x0000FEED (other values are ignored)."]
+    #[inline(always)]
+    pub fn key(&mut self) -> KeyW<'_, FeedSpec> {
+        KeyW::new(self, 0)
+    }
+}
+#[doc = "Write the key to reload the counter with the TIMEOUT.\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`feed::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct FeedSpec;
+impl crate::RegisterSpec for FeedSpec {
+    type Ux = u32;
+}
+#[doc = "`write(|w| ..)` method takes [`feed::W`](W) writer structure"]
+impl crate::Writable for FeedSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets FEED to value 0"]
+impl crate::Resettable for FeedSpec {}
@@ -0,0 +1,43 @@
+#[doc = "Register `STATUS` reader"]
+pub type R = crate::R<StatusSpec>;
+#[doc = "Register `STATUS` writer"]
+pub type W = crate::W<StatusSpec>;
+#[doc = "Field `WDT_RESET` reader - The last reset was caused by the watchdog (write 1 to clear, cleared at power-up)."]
+pub type WdtResetR = crate::BitReader;
+#[doc = "Field `WDT_RESET` writer - The last reset was caused by the watchdog (write 1 to clear, cleared at power-up)."]
+pub type WdtResetW<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `COUNT` reader - Milliseconds left before the bite."]
+pub type CountR = crate::FieldReader<u16>;
+impl R {
+    #[doc = "Bit 0 - The last reset was caused by the watchdog (write 1 to clear, cleared at power-up)."]
+    #[inline(always)]
+    pub fn wdt_reset(&self) -> WdtResetR {
+        WdtResetR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bits 16:31 - Milliseconds left before the bite."]
+    #[inline(always)]
+    pub fn count(&self) -> CountR {
+        CountR::new(((self.bits >> 16) & 0xffff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - The last reset was caused by the watchdog (write 1 to clear, cleared at power-up)."]
+    #[inline(always)]
+    pub fn wdt_reset(&mut self) -> WdtResetW<'_, StatusSpec> {
+        WdtResetW::new(self, 0)
+    }
+}
+#[doc = "Reset cause and counter (write 1 to clear the reset flag).\n\nYou can [`read`](crate::Reg::read) this register and get [`status::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`status::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct StatusSpec;
+impl crate::RegisterSpec for StatusSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`status::R`](R) reader structure"]
+impl crate::Readable for StatusSpec {}
+#[doc = "`write(|w| ..)` method takes [`status::W`](W) writer structure"]
+impl crate::Writable for StatusSpec {
+    type Safety = crate::Unsafe;
+    const ONE_TO_MODIFY_FIELDS_BITMAP: u32 = 0x01;
+}
+#[doc = "`reset()` method sets STATUS to value 0"]
+impl crate::Resettable for StatusSpec {}
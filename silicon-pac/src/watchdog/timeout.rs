@@ -0,0 +1,37 @@
+#[doc = "Register `TIMEOUT` reader"]
+pub type R = crate::R<TimeoutSpec>;
+#[doc = "Register `TIMEOUT` writer"]
+pub type W = crate::W<TimeoutSpec>;
+#[doc = "Field `MILLIS` reader - Timeout in milliseconds (1s by default)."]
+pub type MillisR = crate::FieldReader<u16>;
+#[doc = "Field `MILLIS` writer - Timeout in milliseconds (1s by default)."]
+pub type MillisW<'a, REG> = crate::FieldWriter<'a, REG, 16, u16>;
+impl R {
+    #[doc = "Bits 0:15 - Timeout in milliseconds (1s by default)."]
+    #[inline(always)]
+    pub fn millis(&self) -> MillisR {
+        MillisR::new((self.bits & 0xffff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bits 0:15 - Timeout in milliseconds (1s by default)."]
+    #[inline(always)]
+    pub fn millis(&mut self) -> MillisW<'_, TimeoutSpec> {
+        MillisW::new(self, 0)
+    }
+}
+#[doc = "Timeout, loaded in the counter when the watchdog is started or fed.\n\nYou can [`read`](crate::Reg::read) this register and get [`timeout::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`timeout::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct TimeoutSpec;
+impl crate::RegisterSpec for TimeoutSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`timeout::R`](R) reader structure"]
+impl crate::Readable for TimeoutSpec {}
+#[doc = "`write(|w| ..)` method takes [`timeout::W`](W) writer structure"]
+impl crate::Writable for TimeoutSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets TIMEOUT to value 0x03e8"]
+impl crate::Resettable for TimeoutSpec {
+    const RESET_VALUE: u32 = 0x03e8;
+}
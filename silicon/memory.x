@@ -1,6 +1,7 @@
 MEMORY
 {
-   ram (rwx) : ORIGIN = 0x00000000, LENGTH = 112K /* 114688 bytes */
+   ram (rwx) : ORIGIN = 0x00000000, LENGTH = 0x19f00 /* 106240 bytes, below the no-init RAM and the stack */
+   uninit (rw) : ORIGIN = 0x00019f00, LENGTH = 256 /* kept across the resets (crash record) */
    stack (rwx) : ORIGIN = 0x0001a000, LENGTH = 8K
    peripheral (rw) : ORIGIN = 0x00020000, LENGTH = 64K
 }
@@ -17,3 +18,28 @@ _heap_size = 0;                                 /* Set heap size to 0KB */
 _max_hart_id = 0;                               /* Single-core */
 _hart_stack_size = 8K;                          /* Set stack size per hart to 8kB */
 _stack_start = ORIGIN(stack) + LENGTH(stack);
+
+/* No-init RAM: neither loaded nor cleared at boot, its content survives the resets */
+SECTIONS
+{
+  .uninit (NOLOAD) : ALIGN(4)
+  {
+    __snoinit = .;
+    *(.uninit .uninit.*);
+    __enoinit = .;
+  } > uninit
+}
+INSERT AFTER .bss;
+
+/* The image must end below the no-init RAM, and the no-init RAM below the stack: an overlap
+   would corrupt the crash record, or the stack would corrupt the image */
+ASSERT(ADDR(.text) + SIZEOF(.text) <= ORIGIN(uninit), "
+ERROR(memory.x): .text overlaps the no-init RAM, reduce the code size");
+ASSERT(ADDR(.rodata) + SIZEOF(.rodata) <= ORIGIN(uninit), "
+ERROR(memory.x): .rodata overlaps the no-init RAM, reduce the code size");
+ASSERT(ADDR(.data) + SIZEOF(.data) <= ORIGIN(uninit), "
+ERROR(memory.x): .data overlaps the no-init RAM, reduce the static data");
+ASSERT(ADDR(.bss) + SIZEOF(.bss) <= ORIGIN(uninit), "
+ERROR(memory.x): .bss overlaps the no-init RAM, reduce the static data");
+ASSERT(__snoinit >= ORIGIN(uninit) && __enoinit <= ORIGIN(stack), "
+ERROR(memory.x): .uninit does not fit in the no-init RAM");
@@ -4,15 +4,18 @@
 
 use crate::{
     fs::{RawDirectory, RawVolume, VolumeManager},
-    peripheral::{AudioStreamer, BtnBank, LedBank, OledDisplay, SdCard},
+    peripheral::{AudioStreamer, BtnBank, LedBank, OledDisplay, SdCard, Timer0},
 };
-use silicon_hal::{Peripheral, audio, display};
+use silicon_hal::{Peripheral, audio, crash::CrashRecord, display, timer::Duration};
 
 mod boot;
 mod load;
 mod menu;
 mod play;
 
+/// Timeout of the watchdog: the player is reset if one of its loops is stuck for this long.
+pub const WATCHDOG_TIMEOUT: Duration = Duration::secs(5);
+
 /// Represents the different states of the music player application.
 pub enum AppState {
     Booting(BootingState),
@@ -32,6 +35,9 @@ pub struct LoadingState {
     display: OledDisplay<display::Initialized>,
     sdcard: SdCard,
     audio_streamer: AudioStreamer<audio::Initialized>,
+    timer0: Timer0,
+    /// Record of the crash before this boot, to append to the crash log of the SD card
+    crash: Option<CrashRecord>,
 }
 
 pub struct MenuState {
@@ -40,6 +46,7 @@ pub struct MenuState {
     display: OledDisplay<display::Initialized>,
     audio_streamer: AudioStreamer<audio::Initialized>,
     sd_state: SdDirState,
+    timer0: Timer0,
 }
 
 pub struct PlayingState {
@@ -48,6 +55,7 @@ pub struct PlayingState {
     display: OledDisplay<display::Initialized>,
     audio_streamer: AudioStreamer<audio::Initialized>,
     sd_state: SdDirState,
+    timer0: Timer0,
 }
 
 /// Represents the state of the SD card directory.
@@ -63,6 +71,8 @@ impl AppState {
     /// Returns the new application state after running the logic.
     /// None if you want to stop the application.
     pub fn run(self) -> Option<Self> {
+        silicon_hal::crash::set_app_state(self.name());
+        silicon_hal::watchdog::feed();
         match self {
             AppState::Booting(_) => {
                 return boot::run_booting(self);
@@ -85,6 +95,17 @@ impl AppState {
         }
     }
 
+    /// Get the name of the state, saved in the crash records.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppState::Booting(_) => "booting",
+            AppState::Loading(_) => "loading",
+            AppState::AlbumMenu(_) => "album menu",
+            AppState::TitleMenu(_) => "title menu",
+            AppState::Playing(_) => "playing",
+        }
+    }
+
     /// Create a new AppState in the Booting state.
     pub fn new(peripherals: Peripheral) -> Self {
         AppState::Booting(BootingState { peripherals })
@@ -1,24 +1,35 @@
+use embedded_graphics::mono_font::{self, MonoTextStyle, MonoTextStyleBuilder};
 use embedded_graphics::pixelcolor::Rgb565;
-use embedded_graphics::prelude::{DrawTarget, RgbColor};
-use embedded_hal::digital::{OutputPin, PinState};
+use embedded_graphics::prelude::{DrawTarget, Drawable, Point, RgbColor};
+use embedded_graphics::text::{Baseline, Text};
+use embedded_hal::digital::OutputPin;
 use silicon_hal::delay::INTR_DELAY;
 use silicon_hal::{
+    animation::{Animation, LedAnimator},
     audio,
+    crash::{CrashKind, CrashRecord},
     dac::AudioDac,
-    display::Initialized,
-    gpio::{Gpio, IntoPin as _, Pin, never_bank::NeverPin},
-    spi::{Spi0, Spi1},
+    display::{DisplayError, Initialized},
+    fault::FaultCause,
+    gpio::{Gpio, IntoPin as _},
+    sound::{Envelope, Note, Pitch, SoundGen, Waveform},
+    spi::{self, Spi0, Spi1},
+    text::TextBuf,
+    timer::{Duration, Timer},
 };
 
 use crate::app::LoadingState;
 use crate::peripheral::{
-    AudioStreamer, BtnBank, LedBank, OledDisplay, OledSpi, OledSpiDevice, SdCard, SdCardSpi,
-    SdCardSpiDeviceType,
+    AudioStreamer, BtnBank, LedBank, OledDisplay, OledSpi, OledSpiDevice, SD_INIT_CONFIG, SdCard,
+    SdCardSpi, SdCardSpiDeviceType,
 };
 use crate::{VoidUnwrap, delay_ms};
 
 use super::AppState;
 
+/// Number of boot steps, shown as a progress fill on the LEDs.
+const BOOT_STEPS: u32 = 5;
+
 /// Run the booting state logic.
 ///
 /// This function initializes the necessary peripherals and transitions the application
@@ -34,36 +45,60 @@ use super::AppState;
 pub fn run_booting(state: AppState) -> Option<AppState> {
     if let AppState::Booting(booting_state) = state {
         let mut peripherals = booting_state.peripherals;
+        #[cfg(feature = "log-serial")]
+        silicon_hal::logger::attach_serial(peripherals.uart);
+        info!("booting");
 
-        // Setup the LED bank
-        let mut led_bank = setup_leds(&mut peripherals.gpio);
+        // Check the crash record of the last run, before anything can crash again
+        let crash = silicon_hal::crash::take();
+        if let Some(crash) = &crash {
+            warn!("last crash: {}", crash);
+        }
+        if silicon_hal::watchdog::last_reset_was_watchdog() {
+            warn!("reset by the watchdog");
+        }
 
-        // Reset the LED status
-        led_bank.set_all_low();
-        led_bank.led0.set_high(); // LED ok
+        // Setup the LED bank, and reset the LED status
+        let mut leds = LedAnimator::new(setup_leds(&mut peripherals.gpio));
+        leds.play(Animation::OFF);
+        show_boot_progress(&mut leds, 1); // LED ok
 
         // Setup the button bank
         let btn_bank = setup_btns(&mut peripherals.gpio);
-        led_bank.led1.set_high(); // Buttons ok
+        show_boot_progress(&mut leds, 2); // Buttons ok
 
         // Setup OLED display
-        let oled_display: OledDisplay<Initialized> =
+        let mut oled_display: OledDisplay<Initialized> =
             setup_display(peripherals.spi1, &mut peripherals.gpio);
-        led_bank.led2.set_high(); // Display ok
+        show_boot_progress(&mut leds, 3); // Display ok
+        if let Some(crash) = &crash {
+            show_crash(&mut oled_display, crash);
+            silicon_hal::watchdog::feed();
+        }
 
         // Setup the SDCard
         let sdcard = setup_sdcard(peripherals.spi0, &mut peripherals.gpio);
-        led_bank.led3.set_high(); // SDCard ok
+        show_boot_progress(&mut leds, 4); // SDCard ok
 
         // Setup the audio streamer
         let audio_streamer = setup_audio_streamer(peripherals.dac);
-        led_bank.led4.set_high(); // Audio ok
+        show_boot_progress(&mut leds, 5); // Audio ok
 
-        // Wait 1s before transitioning
-        delay_ms(1000);
-        led_bank.set_all_high();
-        delay_ms(500);
-        led_bank.set_all_low();
+        // Play the boot chime, the LEDs breathe for 1s before transitioning, then fade out
+        play_boot_chime(&mut peripherals.sound);
+        let breathe = Animation::Breathe {
+            leds: 0xff,
+            period: Duration::secs(1),
+        };
+        run_animation(&mut leds, breathe, Duration::secs(1));
+        peripherals.sound.disable();
+        let fade_out = Animation::FadeTo {
+            levels: [0; 8],
+            duration: Duration::millis(500),
+        };
+        run_animation(&mut leds, fade_out, Duration::millis(500));
+        let led_bank = leds.bring_down();
+        info!("boot done");
 
         // Transition to Loading state
         return Some(AppState::Loading(LoadingState {
@@ -72,6 +107,8 @@ pub fn run_booting(state: AppState) -> Option<AppState> {
             display: oled_display,
             sdcard,
             audio_streamer,
+            timer0: peripherals.timer0,
+            crash,
         }));
     }
     None
@@ -87,34 +124,131 @@ fn setup_display(spi: Spi1, gpio: &mut Gpio) -> OledDisplay<Initialized> {
     };
     oled_cs.set_high();
 
-    // Create the SPI interface for the OLED display
-    let oled_spi_cs = Pin::new_output(NeverPin(PinState::Low));
-    let oled_spi = OledSpi::new(spi, INTR_DELAY);
-    let oled_spi_device = OledSpiDevice::new(oled_spi, oled_spi_cs, INTR_DELAY).void_unwrap();
+    // Create the SPI interface for the OLED display, and reset its master (a transfer of the
+    // last run may have been cut by the reset)
+    let mut oled_spi = OledSpi::new(spi, INTR_DELAY);
+    if let Err(err) = oled_spi.initialize() {
+        warn!("display: spi not ready ({:?})", err);
+    }
+    let oled_spi_device = OledSpiDevice::new(oled_spi);
 
     // Create and initialize the OLED display peripheral
     let oled_display: OledDisplay<_> =
         OledDisplay::new(oled_spi_device, oled_cs, oled_dc, oled_rst, INTR_DELAY);
 
-    let mut display = oled_display.initialize().void_unwrap();
+    // Nothing can be shown without the display: stop (with a crash record, and its error ID on
+    // the LEDs) if the master is still stuck after its reset
+    let mut display = match oled_display.initialize() {
+        Ok(display) => display,
+        Err(DisplayError::SpiError(spi::Error::Timeout)) => panic!("display: spi timeout"),
+        Err(DisplayError::SpiError(spi::Error::NotReady)) => panic!("display: spi not ready"),
+        Err(DisplayError::PinError(never)) => match never {},
+    };
 
     // Clear the display
-    display.clear(Rgb565::BLACK);
+    let _ = display.clear(Rgb565::BLACK);
 
     display
 }
 
+/// Show the record of the last crash on the display for a few seconds.
+///
+/// The text is drawn with the font of the player (no text console needed), one field per line
+/// (21 characters each).
+fn show_crash(display: &mut OledDisplay<Initialized>, crash: &CrashRecord) {
+    const TITLE_STYLE: MonoTextStyle<Rgb565> = MonoTextStyleBuilder::new()
+        .font(&mono_font::ascii::FONT_6X10)
+        .text_color(Rgb565::WHITE)
+        .background_color(Rgb565::RED)
+        .build();
+    const TEXT_STYLE: MonoTextStyle<Rgb565> = MonoTextStyleBuilder::new()
+        .font(&mono_font::ascii::FONT_6X10)
+        .text_color(Rgb565::WHITE)
+        .background_color(Rgb565::BLACK)
+        .build();
+
+    let _ = display.clear(Rgb565::BLACK);
+    let title = Text::with_baseline(
+        " LAST CRASH          ",
+        Point::zero(),
+        TITLE_STYLE,
+        Baseline::Top,
+    );
+    let _ = title.draw(display);
+
+    let mut buf = [0; 160];
+    let mut text = TextBuf::new(&mut buf);
+    match crash.kind() {
+        CrashKind::Fault => text
+            .push_str("TRAP ")
+            .push_dec(crash.code as u32, 1)
+            .push_str("\n")
+            .push_str(FaultCause::from_code(crash.code as usize).name())
+            .push_str("\nPC   ")
+            .push_hex(crash.pc, 8)
+            .push_str("\nTVAL ")
+            .push_hex(crash.tval, 8),
+        CrashKind::Panic => text
+            .push_str("PANIC ID ")
+            .push_hex(crash.code as u32, 2)
+            .push_str("\n")
+            .push_str(crash.file())
+            .push_str("\nLINE ")
+            .push_dec(crash.line, 1),
+        CrashKind::Watchdog => text.push_str("WATCHDOG RESET\n(STUCK)"),
+    };
+    text.push_str("\n\nSTATE  ").push_str(crash.app_state());
+    if crash.kind() != CrashKind::Watchdog {
+        text.push_str("\nUPTIME ")
+            .push_dec(crash.uptime_ms / 1000, 1)
+            .push_str(".")
+            .push_dec(crash.uptime_ms % 1000, 3)
+            .push_str("S");
+    }
+    let body = Text::with_baseline(text.as_str(), Point::new(0, 20), TEXT_STYLE, Baseline::Top);
+    let _ = body.draw(display);
+    delay_ms(3000);
+    let _ = display.clear(Rgb565::BLACK);
+}
+
 fn setup_sdcard(spi: Spi0, gpio: &mut Gpio) -> SdCard {
     // Initialize the SDCard here using the provided SPI and GPIO peripherals.
     let mut sd_cs = gpio.take_spi_sd_cs().unwrap().into_pin();
     sd_cs.set_high();
 
-    // Setup the SPI interface for the SDCard
-    let sd_spi = SdCardSpi::new(spi, INTR_DELAY);
+    // Setup the SPI interface for the SDCard, slow for its initialization
+    let mut sd_spi = SdCardSpi::new(spi, INTR_DELAY);
+    if let Err(err) = crate::fs::reconfigure_sd_spi(&mut sd_spi, SD_INIT_CONFIG) {
+        warn!("sdcard: spi not ready ({:?})", err);
+    }
     let sd_spi_device = SdCardSpiDeviceType::new(sd_spi, sd_cs, INTR_DELAY).void_unwrap();
 
-    // Create and return the SDCard peripheral
-    SdCard::new(sd_spi_device, INTR_DELAY)
+    // Create the SDCard peripheral, and initialize the card now (tried again when loading)
+    let sdcard = SdCard::new(sd_spi_device, INTR_DELAY);
+    crate::fs::init_sdcard(&sdcard);
+    sdcard
+}
+
+/// Show a boot step as a progress fill on the LEDs, and wait for the fill to fade in.
+fn show_boot_progress(leds: &mut LedAnimator, step: u32) {
+    leds.play(Animation::Progress {
+        done: step,
+        total: BOOT_STEPS,
+    });
+    while !leds.is_done() {
+        leds.update();
+    }
+}
+
+/// Run an animation of the LEDs for a while (blocking).
+fn run_animation(leds: &mut LedAnimator, animation: Animation, duration: Duration) {
+    let timer = Timer::new_timer0();
+    let end = timer.get_counter() + duration;
+    leds.play(animation);
+    while timer.get_counter() < end {
+        leds.update();
+    }
+    leds.update();
 }
 
 fn setup_leds(gpio: &mut Gpio) -> LedBank {
@@ -147,3 +281,28 @@ fn setup_audio_streamer(dac: AudioDac) -> AudioStreamer<audio::Initialized> {
     let streamer = AudioStreamer::new_mono(dac);
     streamer.initialize()
 }
+
+/// Play the boot chime (a C major arpeggio) on the Sound Generator.
+///
+/// The notes end by themselves, the Sound Generator can be disabled ~1s later.
+fn play_boot_chime(sound: &mut SoundGen) {
+    let Some((mut voice0, mut voice1, mut voice2)) = sound.take_all_voices() else {
+        return;
+    };
+    sound.enable();
+    let notes = [
+        Note::new(Pitch::C, 5),
+        Note::new(Pitch::E, 5),
+        Note::new(Pitch::G, 5),
+    ];
+    for (voice, note) in [&mut voice0, &mut voice1, &mut voice2]
+        .into_iter()
+        .zip(notes)
+    {
+        voice.set_waveform(Waveform::Triangle);
+        voice.set_envelope(Envelope::PLUCK);
+        voice.set_volume(160);
+        voice.play(note);
+        delay_ms(120);
+    }
+}
@@ -1,8 +1,7 @@
 use crate::delay_ms;
 use crate::display::BinWrapDrawTarget;
-use crate::fs::ZeroTimeSource;
+use crate::fs::{ZeroTimeSource, init_sdcard};
 use crate::{
-    VoidUnwrap,
     app::{AppState, SdDirState},
     fs::VolumeManager,
     peripheral::{LedBank, OledDisplay},
@@ -15,7 +14,7 @@ use embedded_graphics::{
 use embedded_hal::digital::OutputPin;
 use embedded_sdmmc::{Mode, VolumeIdx};
 use silicon_hal::delay::{DelayNs, INTR_DELAY};
-use silicon_hal::display;
+use silicon_hal::{crash::CrashRecord, display, text::TextBuf};
 
 /// Run the loading state logic.
 ///
@@ -38,11 +37,19 @@ pub fn run_loading(state: AppState) -> Option<AppState> {
 
         leds.set_all_low();
 
+        // The card may not have been ready at boot: the data transfers must run at full speed
+        init_sdcard(&sdcard);
         let mng = VolumeManager::new(sdcard, ZeroTimeSource);
         leds.led1.set_high();
-        let volume = mng.open_raw_volume(VolumeIdx(0)).void_unwrap();
+        let Ok(volume) = mng.open_raw_volume(VolumeIdx(0)) else {
+            error!("cannot open the SD card volume");
+            return None;
+        };
         leds.led2.set_high();
-        let root = mng.open_root_dir(volume).void_unwrap();
+        let Ok(root) = mng.open_root_dir(volume) else {
+            error!("cannot open the root directory");
+            return None;
+        };
         leds.led3.set_high();
 
         let mut sd_state = SdDirState {
@@ -51,11 +58,22 @@ pub fn run_loading(state: AppState) -> Option<AppState> {
             pwd: root,
         };
 
+        // Keep the crash of the last run in the crash log of the SD card
+        if let Some(crash) = &loading_state.crash
+            && append_crash_log(&mut sd_state, crash).is_err()
+        {
+            warn!("cannot write __SYS__/crash.log");
+        }
+
+        silicon_hal::watchdog::feed();
         delay_ms(1000);
 
         // Display welcome animation
         let mut glyph_data = [0; 512];
-        read_asset_data(&mut sd_state, "hi.raw", 0, &mut glyph_data, &mut leds).unwrap();
+        if read_asset_data(&mut sd_state, "hi.raw", 0, &mut glyph_data, &mut leds).is_err() {
+            error!("cannot read __SYS__/hi.raw");
+            return None;
+        }
         leds.led4.set_high();
 
         welcome_animation(&mut display, &glyph_data, INTR_DELAY);
@@ -70,6 +88,7 @@ pub fn run_loading(state: AppState) -> Option<AppState> {
             display,
             audio_streamer,
             sd_state,
+            timer0: loading_state.timer0,
         }));
     }
     None
@@ -89,11 +108,13 @@ fn welcome_animation(
     let raw_image = ImageRaw::<BinaryColor>::new(glyph_data, 64);
 
     // Clear the display
-    display.clear(Rgb565::BLACK);
+    let _ = display.clear(Rgb565::BLACK);
 
     // Animation loop
     // Total: 2s / 64 "frames" = ~31.25ms per frame (~30 FPS)
     for k in 0..64 {
+        // The animation lasts 2s, keep the watchdog fed
+        silicon_hal::watchdog::feed();
         // Pixel under the k-th column are drawn in WHITE
         {
             let white_pos = Point::new(32, 32);
@@ -105,7 +126,7 @@ fn welcome_animation(
                 BinWrapDrawTarget::new(Rgb565::CSS_WHITE, Rgb565::BLACK, display);
             let sub_image = raw_image.sub_image(&white_area);
             let white_img = Image::new(&sub_image, white_pos);
-            white_img.draw(&mut white_display);
+            let _ = white_img.draw(&mut white_display);
         }
 
         // Pixel above and equal to the k-th column are drawn in PURPLE
@@ -119,13 +140,13 @@ fn welcome_animation(
                 BinWrapDrawTarget::new(Rgb565::CSS_PURPLE, Rgb565::BLACK, display);
             let sub_image = raw_image.sub_image(&purple_area);
             let purple_image = Image::new(&sub_image, purple_pos);
-            purple_image.draw(&mut purple_display);
+            let _ = purple_image.draw(&mut purple_display);
         }
         delay.delay_ms(33);
     }
 
     // Clear the display
-    display.clear(Rgb565::CSS_MAGENTA);
+    let _ = display.clear(Rgb565::CSS_MAGENTA);
 }
 
 /// Read asset data from the SD card.
@@ -168,3 +189,30 @@ pub fn read_asset_data(
     }
     Err(())
 }
+
+/// Append a crash record to the crash log of the SD card (`__SYS__/crash.log`, one line each).
+///
+/// # Arguments
+/// * `root` - The current SD card directory state.
+/// * `crash` - The crash record.
+/// # Returns
+/// * `Result<(), ()>` - An error if the crash log cannot be opened or written.
+fn append_crash_log(root: &mut SdDirState, crash: &CrashRecord) -> Result<(), ()> {
+    let mut buf = [0; silicon_hal::crash::LINE_LEN + 2];
+    let mut line = TextBuf::new(&mut buf);
+    crash.write_line(&mut line);
+    line.push_str("\r\n");
+
+    let mnr: &mut VolumeManager = &mut root.mng;
+    let sys_dir = mnr.open_dir(root.pwd, "__SYS__").map_err(|_| ())?;
+    let written = mnr
+        .open_file_in_dir(sys_dir, "crash.log", Mode::ReadWriteCreateOrAppend)
+        .map_err(|_| ())
+        .and_then(|file| {
+            let written = mnr.write(file, line.as_str().as_bytes()).map_err(|_| ());
+            mnr.close_file(file).map_err(|_| ())?;
+            written
+        });
+    mnr.close_dir(sys_dir).map_err(|_| ())?;
+    written
+}
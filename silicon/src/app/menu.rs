@@ -5,10 +5,13 @@ use embedded_graphics::{
     primitives::{PrimitiveStyleBuilder, Rectangle},
     text::Text,
 };
-use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::digital::OutputPin;
 use embedded_sdmmc::{LfnBuffer, ShortFileName};
 use heapless::{String, Vec};
-use silicon_hal::display;
+use silicon_hal::{
+    display,
+    input::{Button, ButtonEvent, ButtonInput, InputConfig},
+};
 
 use crate::{
     VoidUnwrap,
@@ -16,6 +19,12 @@ use crate::{
     peripheral::OledDisplay,
 };
 
+/// Buttons of the menus.
+const UP: Button = Button::Btn3;
+const DOWN: Button = Button::Btn4;
+const BACK: Button = Button::Btn5;
+const SELECT: Button = Button::Btn6;
+
 /// Run the Album Menu logic.
 ///
 /// This function initializes the necessary peripherals and transitions the application
@@ -29,30 +38,32 @@ use crate::{
 ///
 /// * `Option<AppState>` - The new application state after loading, or None if an error occurred.
 pub fn run_menu(state: AppState) -> Option<AppState> {
-    let (mut display, mut leds, mut btns, mut sd_state, audio_streamer, title_select) = match state
-    {
-        AppState::AlbumMenu(menu_state) => (
-            menu_state.display,
-            menu_state.leds,
-            menu_state.btns,
-            menu_state.sd_state,
-            menu_state.audio_streamer,
-            false,
-        ),
-        AppState::TitleMenu(menu_state) => (
-            menu_state.display,
-            menu_state.leds,
-            menu_state.btns,
-            menu_state.sd_state,
-            menu_state.audio_streamer,
-            true,
-        ),
-        _ => return None, // Invalid state transition
-    };
+    let (mut display, mut leds, btns, mut sd_state, audio_streamer, timer0, title_select) =
+        match state {
+            AppState::AlbumMenu(menu_state) => (
+                menu_state.display,
+                menu_state.leds,
+                menu_state.btns,
+                menu_state.sd_state,
+                menu_state.audio_streamer,
+                menu_state.timer0,
+                false,
+            ),
+            AppState::TitleMenu(menu_state) => (
+                menu_state.display,
+                menu_state.leds,
+                menu_state.btns,
+                menu_state.sd_state,
+                menu_state.audio_streamer,
+                menu_state.timer0,
+                true,
+            ),
+            _ => return None, // Invalid state transition
+        };
 
     // Reset LEDs & Display
     leds.set_all_low();
-    display.clear(Rgb565::BLACK);
+    let _ = display.clear(Rgb565::BLACK);
 
     const FILES_PER_PAGE: usize = 6;
     const MAX_NAME_LENGTH: usize = 20;
@@ -60,11 +71,16 @@ pub fn run_menu(state: AppState) -> Option<AppState> {
     // Load the 6 first file names from the SDCard
     files = match get_file_names(&mut sd_state, 0) {
         Ok(names) => names,
-        Err(_) => return None, // TODO: Handle error appropriately
+        Err(_) => {
+            error!("cannot list the directory");
+            return None;
+        }
     };
     leds.led1.set_high(); // Indicate loading complete
 
     let mut cursor = 0;
+    // Debounced button events, UP and DOWN repeat when held
+    let mut input = ButtonInput::new(btns, InputConfig::DEFAULT);
 
     'select: loop {
         leds.led2.set_low();
@@ -75,7 +91,10 @@ pub fn run_menu(state: AppState) -> Option<AppState> {
             files = match get_file_names(&mut sd_state, (cursor / FILES_PER_PAGE) * FILES_PER_PAGE)
             {
                 Ok(names) => names,
-                Err(_) => return None, // TODO: Handle error appropriately
+                Err(_) => {
+                    error!("cannot list the directory (entry {})", cursor);
+                    return None;
+                }
             };
         }
 
@@ -85,33 +104,36 @@ pub fn run_menu(state: AppState) -> Option<AppState> {
 
         // Handle button inputs
         'inputs: loop {
-            // BTN3 = Up / Prev
-            if btns.btn3.is_high().void_unwrap() {
-                cursor = cursor.saturating_sub(1);
-                break 'inputs;
-            }
-            // BTN4 = Down / Next
-            if btns.btn4.is_high().void_unwrap() {
-                cursor = (cursor + 1).min(files.total - 1);
-                break 'inputs;
-            }
-            // BTN5 = Back
-            if btns.btn5.is_high().void_unwrap() {
+            silicon_hal::watchdog::feed();
+            match input.poll() {
+                // BTN3 = Up / Prev
+                Some(ButtonEvent::Pressed(UP) | ButtonEvent::Repeat { button: UP, .. }) => {
+                    cursor = cursor.saturating_sub(1);
+                    break 'inputs;
+                }
+                // BTN4 = Down / Next
+                Some(ButtonEvent::Pressed(DOWN) | ButtonEvent::Repeat { button: DOWN, .. }) => {
+                    cursor = (cursor + 1).min(files.total - 1);
+                    break 'inputs;
+                }
+                // BTN5 = Back
                 // Normally, go back = select the parent directory
                 // It only works if we are in title select mode, and the parent is then the first entry
-                if title_select {
+                Some(ButtonEvent::Pressed(BACK)) if title_select => {
                     cursor = 0;
                     break 'select;
                 }
-            }
-            // BTN6 = Select / OK
-            if btns.btn6.is_high().void_unwrap() {
-                // Select album
-                break 'select;
+                // BTN6 = Select / OK
+                Some(ButtonEvent::Pressed(SELECT)) => {
+                    // Select album
+                    break 'select;
+                }
+                _ => {}
             }
         }
         leds.led3.set_high(); // Indicate button processed
     }
+    let btns = input.bring_down();
 
     leds.led4.set_high(); // Indicate album selected
 
@@ -124,7 +146,10 @@ pub fn run_menu(state: AppState) -> Option<AppState> {
         .open_dir(sd_state.pwd, selected_file.short_name)
     {
         Ok(dir) => dir,
-        Err(_) => return None, // TODO: Handle error appropriately
+        Err(_) => {
+            error!("cannot open {}", selected_file.display_name.as_str());
+            return None;
+        }
     };
     leds.led5.set_high(); // Indicate directory opened
 
@@ -143,6 +168,7 @@ pub fn run_menu(state: AppState) -> Option<AppState> {
                 volume: sd_state.volume,
                 pwd: new_dir,
             },
+            timer0,
         }))
     } else {
         // Selected a title
@@ -160,10 +186,11 @@ pub fn run_menu(state: AppState) -> Option<AppState> {
                     volume: sd_state.volume,
                     pwd: new_dir,
                 },
+                timer0,
             }));
         }
 
-        display.clear(Rgb565::BLACK);
+        let _ = display.clear(Rgb565::BLACK);
         Some(AppState::Playing(PlayingState {
             leds,
             btns,
@@ -174,6 +201,7 @@ pub fn run_menu(state: AppState) -> Option<AppState> {
                 volume: sd_state.volume,
                 pwd: new_dir,
             },
+            timer0,
         }))
     }
 }
@@ -272,7 +300,7 @@ fn get_file_names<const N: usize, const M: usize>(
             offset,
             total: count,
         }),
-        // TODO: Handle errors appropriately
+        // The caller logs the error
         Err(_) => return Err(()),
     }
 }
@@ -298,7 +326,7 @@ fn render_menu<const N: usize, const M: usize>(
     selected_index: usize,
 ) {
     // Clear the display
-    display.clear(Rgb565::BLACK);
+    let _ = display.clear(Rgb565::BLACK);
 
     // Define text style
     const TEXT_STYLE: MonoTextStyle<Rgb565> = MonoTextStyle::new(
@@ -310,7 +338,7 @@ fn render_menu<const N: usize, const M: usize>(
     {
         let cursor_position = Point::new(0, (selected_index as i32) * 20);
         let cursor_rect = Rectangle::new(cursor_position, Size::new(128, 20));
-        cursor_rect
+        let _ = cursor_rect
             .into_styled(
                 PrimitiveStyleBuilder::new()
                     .fill_color(Rgb565::CSS_PURPLE)
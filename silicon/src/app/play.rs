@@ -1,36 +1,92 @@
+//! Playing state
+//!
+//! The CPU has no hardware multiplier: a multiplication is a software loop, so the per-sample
+//! processing of the player (speed, beats, visualizer) avoids them where it can.
+
 use crate::{
     VoidUnwrap,
     app::{AppState, SdDirState},
-    delay_ms,
     display::BinWrapDrawTarget,
     fs::VolumeManager,
     peripheral::OledDisplay,
 };
+use beat::{Beat, BeatDetector, BeatEffects, BeatFlash, BeatLeds, LedEffect};
+use core::{cell::RefCell, pin::pin};
 use embedded_graphics::{
     image::Image,
     mono_font::{self, MonoTextStyle, MonoTextStyleBuilder},
     pixelcolor::Rgb565,
-    prelude::{Drawable, Point, RgbColor as _},
+    prelude::{Drawable, Point, RgbColor as _, WebColors as _},
     text::Text,
 };
-use embedded_hal::digital::{InputPin, OutputPin};
-use embedded_sdmmc::{Mode, RawDirectory};
-use heapless::format;
+use embedded_hal::digital::OutputPin;
+use embedded_sdmmc::{Mode, RawDirectory, RawFile};
+use heapless::Vec;
+use input::{PlayerAction, PlayerInput};
+use marks::{Bookmarks, LoopRegion};
+use position::{AudioFormat, Track};
 use silicon_hal::{
+    animation::{Animation, LedAnimator},
     display,
+    executor::{self, Executor},
     gpio::{AudioViz, LedBank},
+    text::TextBuf,
+    timer::Duration,
 };
+use speed::{SpeedControl, SpeedMode};
+use visualizer::{View, Visualizer};
+
+mod beat;
+mod input;
+mod marks;
+mod position;
+mod speed;
+mod visualizer;
 
 const AUDIO_SAMPLE_RATE: usize = 48000; // 48kHz
 const AUDIO_CHANNELS: usize = 1; // Mono
 const AUDIO_BIT_DEPTH: usize = 8; // 8-bit
-const AUDIO_DATA_RATE: usize = AUDIO_SAMPLE_RATE * AUDIO_CHANNELS * (AUDIO_BIT_DEPTH / 8); // Bytes per second
+
+/// Format of the music.raw files (raw PCM, no header).
+const MUSIC_FORMAT: AudioFormat = AudioFormat {
+    sample_rate: AUDIO_SAMPLE_RATE as u32,
+    channels: AUDIO_CHANNELS as u32,
+    bit_depth: AUDIO_BIT_DEPTH as u32,
+    data_offset: 0,
+};
+
+/// Duration of a skip (FWD/BWD tapped), in seconds.
+const SKIP_SECS: u32 = 10;
+/// Duration of the first scrub steps (FWD/BWD held), in seconds.
+///
+/// The step doubles every 10 repeats (~1s), up to 32 times this duration.
+const SCRUB_STEP_SECS: u32 = 2;
+
+/// Duration a status message stays on screen.
+const STATUS_DURATION: Duration = Duration::secs(4);
+/// Duration the volume level stays on the LEDs after a change.
+const VOLUME_LEDS_DURATION: Duration = Duration::secs(4);
+/// Period of the track progress updates.
+const PROGRESS_PERIOD: Duration = Duration::millis(500);
+/// Period of the UI task (buttons, LEDs and display), roughly the duration of a block of samples.
+const UI_PERIOD: Duration = Duration::millis(10);
+
+/// Number of samples handed to the speed processor at once.
+///
+/// At the slowest speed, a slice produces at most 3 stretched segments (or 2 resampled samples
+/// per sample), so its output fits in 4 times its length.
+const SPEED_SLICE_LEN: usize = 128;
+
+/// Number of 8-row chunks of the cover art redrawn when leaving the visualizer.
+///
+/// The visualizer only covers the rows above the status and progress lines (0-103).
+const COVER_REDRAW_CHUNKS: u8 = 13;
 
 /// LED can be managed by the software or by the audio visualizer (hardware).
 ///
-/// This enum is a proxy for the LED control, allowing us to switch between software and hardware control of the LEDs.
+/// This enum is a proxy for the LED control, allowing us to switch between software (animated) and hardware control of the LEDs.
 enum LedControl {
-    Software(LedBank),
+    Software(LedAnimator),
     AudioViz(AudioViz),
 }
 
@@ -40,29 +96,35 @@ impl LedControl {
     fn switch_to_software(self) -> Self {
         match self {
             LedControl::Software(_) => self, // Already in software control
-            LedControl::AudioViz(av) => LedControl::Software(av.bring_down()),
+            LedControl::AudioViz(av) => LedControl::Software(LedAnimator::new(av.bring_down())),
         }
     }
 
     /// Switch to audio visualizer control of the LEDs.
+    ///
+    /// The audio visualizer is calibrated for the given volume level (1-8/8).
     #[inline(always)]
-    fn switch_to_audio_viz(self) -> Self {
+    fn switch_to_audio_viz(self, snd_vol: u8) -> Self {
         match self {
             LedControl::AudioViz(_) => self, // Already in audio viz control
-            LedControl::Software(sb) => LedControl::AudioViz(AudioViz::new(sb)),
+            LedControl::Software(sb) => {
+                let mut av = AudioViz::new(sb.bring_down());
+                calibrate_audio_viz(&mut av, snd_vol);
+                LedControl::AudioViz(av)
+            }
         }
     }
 
     /// Apply a function to the LEDs if we are in software control mode.
-    fn software_do(&mut self, f: impl FnOnce(&mut LedBank)) {
+    fn software_do(&mut self, f: impl FnOnce(&mut LedAnimator)) {
         match self {
             LedControl::Software(sb) => f(sb),
             LedControl::AudioViz(_) => { /* Nothing */ }
         }
     }
 
-    /// Get the underlying LedBank if we are in software control mode.
-    fn software_leds(&mut self) -> Option<&mut LedBank> {
+    /// Get the underlying LedAnimator if we are in software control mode.
+    fn software_leds(&mut self) -> Option<&mut LedAnimator> {
         match self {
             LedControl::Software(sb) => Some(sb),
             LedControl::AudioViz(_) => None,
@@ -72,17 +134,55 @@ impl LedControl {
     /// Bring down and get the underlying LedBank
     fn bring_down(self) -> LedBank {
         match self {
-            LedControl::Software(sb) => sb,
+            LedControl::Software(sb) => sb.bring_down(),
             LedControl::AudioViz(av) => av.bring_down(),
         }
     }
 }
 
+/// Calibrate the audio visualizer for a volume level (1-8/8).
+///
+/// The volume scales the samples down (`sample >> (8 - volume)`), which also moves the level of
+/// silence away from 128 and lowers the energy. The reference level follows the volume, and each
+/// halving of the amplitude (energy / 4) is compensated by 2 steps of sensitivity.
+fn calibrate_audio_viz(av: &mut AudioViz, snd_vol: u8) {
+    let shift = 8 - snd_vol;
+    av.set_reference(128 >> shift);
+    av.set_sensitivity(2 * shift);
+}
+
+/// State of the player shared by its tasks (streaming and UI).
+///
+/// The tasks only borrow it between two `await`s.
+struct Player<'a> {
+    mng: &'a mut VolumeManager,
+    track: Track,
+    speed: SpeedControl,
+    loop_region: LoopRegion,
+    visualizer: Visualizer,
+    beat_detector: BeatDetector,
+    /// Beat detected since the last step of the UI task
+    beat: Option<Beat>,
+    /// Volume level 1-8/8
+    snd_vol: u8,
+    paused: bool,
+    /// A block of the track was read since the last step of the UI task
+    io_read: bool,
+    /// The streaming task is feeding the audio streamer
+    io_stream: bool,
+    /// The playback is over (end of the track, read error or BACK)
+    stopped: bool,
+}
+
 /// Run the Playing logic.
 ///
 /// This function initializes the necessary peripherals and transitions the application
 /// from the Loading state to the Menu state.
 ///
+/// The player runs two tasks on the executor of the HAL: the streaming task reads the track
+/// and feeds the audio streamer, the UI task handles the buttons, the LEDs and the display
+/// while the streaming task waits for room in the audio FIFO.
+///
 //// # Arguments
 ///
 /// * `state` - The current application state, expected to be in the Loading state.
@@ -94,9 +194,13 @@ pub fn run_playing(state: AppState) -> Option<AppState> {
     if let AppState::Playing(playing_state) = state {
         let mut display = playing_state.display;
         let mut leds = playing_state.leds;
-        let mut btns = playing_state.btns;
         let mut sd_state = playing_state.sd_state;
         let mut audio_streamer = playing_state.audio_streamer;
+        let mut timer0 = playing_state.timer0;
+        let timer = timer0.timer();
+        let pwd = sd_state.pwd;
+        // Timers of the executor, the volume LEDs and the status message
+        let (executor_alarm, mut volume_alarm, mut status_alarm) = timer0.take_all_alarms()?;
 
         leds.set_all_low();
 
@@ -107,146 +211,420 @@ pub fn run_playing(state: AppState) -> Option<AppState> {
         let mng = &mut sd_state.mng;
         let audio_file = match mng.open_file_in_dir(sd_state.pwd, "music.raw", Mode::ReadOnly) {
             Ok(f) => f,
-            Err(_) => return None, // TODO: Handle error appropriately
+            Err(_) => {
+                error!("cannot open music.raw");
+                return None;
+            }
         };
         // Get the size of the audio file
-        let size = mng.file_length(audio_file).void_unwrap();
-        let duration = size / (AUDIO_DATA_RATE as u32); // Duration in seconds
+        let track = Track::new(mng, audio_file, MUSIC_FORMAT).void_unwrap();
+        // Load the bookmarks of this title (if any)
+        let mut bookmarks = Bookmarks::load(mng, sd_state.pwd);
         leds.led1.set_high();
 
         // Start streaming audio
-        let mut snd_vol = 8; // Volume level 1-8/8
-        let mut led_vol_timeout = 0; // Timeout counter for volume LED indication
-        let mut paused = false;
-        let mut buffer = [0u8; 512];
-        let mut cycle = 0; // Count loop iterations (it allows us to reevaluate the progress every ~500ms / 47 iters)
-        let mut led_control = LedControl::AudioViz(AudioViz::new(leds));
-        loop {
-            // Handle inputs
-            {
-                let pause_btn = btns.btn4.is_high().unwrap_or(false);
-                let back_btn = btns.btn3.is_high().unwrap_or(false);
-                let forward_btn = btns.btn6.is_high().unwrap_or(false);
-                let backward_btn = btns.btn5.is_high().unwrap_or(false);
-                let vol_up_btn = btns.btn2.is_high().unwrap_or(false);
-                let vol_down_btn = btns.btn1.is_high().unwrap_or(false);
-                // Check if the pause button is pressed
-                if pause_btn {
-                    paused = !paused;
-                    // Simple debounce - wait 300ms
-                    delay_ms(300);
-                    cycle = 0; // Force immediate progress update after unpausing
-                }
-                // Check if the back button is pressed
-                if back_btn {
-                    // Stop playback and go back to title menu
-                    break;
-                }
-                // Check if the forward button is pressed
-                if forward_btn {
-                    // TODO: Skip will fail if we are near EOF - handle that case (https://github.com/fusetim/rusty-soc/issues/1)
-                    let _ = mng.file_seek_from_current(audio_file, 938 * 512); // Skip forward ~10s (assuming 48kHz mono 8-bit)
-                    // Simple debounce - wait 300ms
-                    delay_ms(300);
-                    cycle = 0; // Force immediate progress update after unpausing
-                } else if backward_btn {
-                    // Check if the backward button is pressed
-                    // TODO: Going backward will fail if we are near the start of the file - handle that case (https://github.com/fusetim/rusty-soc/issues/1)
-                    let _ = mng.file_seek_from_current(audio_file, -(938 * 512)); // Skip backward ~10s
-                    // Simple debounce - wait 300ms
-                    delay_ms(300);
-                    cycle = 0; // Force immediate progress update after unpausing
-                }
-                // Check volume up button
-                if led_vol_timeout >= 24 * 7 {
-                    // Debouncing for volume buttons (ignore if < 500ms since last volume change)
-                } else if vol_up_btn {
-                    if snd_vol < 8 {
-                        snd_vol += 1;
-                    }
-                    led_vol_timeout = 48 * 4; // Show volume level for 4 seconds (assuming 48kHz sample rate and 512-byte reads)
-                } else if vol_down_btn {
-                    // Check volume down button
-                    if snd_vol > 1 {
-                        snd_vol -= 1;
+        let player = RefCell::new(Player {
+            mng,
+            track,
+            speed: SpeedControl::new(),
+            loop_region: LoopRegion::None,
+            visualizer: Visualizer::new(),
+            beat_detector: BeatDetector::new(),
+            beat: None,
+            snd_vol: 8,
+            paused: false,
+            io_read: false,
+            io_stream: false,
+            stopped: false,
+        });
+        let mut led_control = LedControl::Software(LedAnimator::new(leds)).switch_to_audio_viz(8);
+        let mut input = PlayerInput::new(playing_state.btns);
+        let mut cover_redraw = None; // Cover art file (if any) and next chunk, while redrawing it
+        let mut led_bank = None; // LED bank given back by the UI task
+
+        let mut executor = Executor::new(executor_alarm);
+        {
+            // Streaming task: read the track, apply the volume and the speed, and feed the audio streamer
+            let streaming = pin!(async {
+                let mut buffer = [0u8; 512];
+                let mut output: Vec<u8, { 4 * SPEED_SLICE_LEN }> = Vec::new();
+                loop {
+                    let bytes_read = {
+                        let mut guard = player.borrow_mut();
+                        let Player {
+                            mng,
+                            track,
+                            loop_region,
+                            visualizer,
+                            beat_detector,
+                            beat,
+                            snd_vol,
+                            paused,
+                            io_read,
+                            stopped,
+                            ..
+                        } = &mut *guard;
+                        if *stopped {
+                            break;
+                        }
+                        if *paused {
+                            0
+                        } else {
+                            // Read audio data from the file (never past the end of the A-B loop, if any)
+                            let Ok(bytes_read) = track.read(mng, &mut buffer, loop_region.end())
+                            else {
+                                // Error reading file - stop playback
+                                break;
+                            };
+                            if bytes_read == 0 {
+                                if let Some(a) = loop_region.start() {
+                                    // End of the A-B loop, go back to A.
+                                    // The speed processor is not reset, so the samples are spliced without a gap.
+                                    if track.seek(mng, a).is_ok() {
+                                        continue;
+                                    }
+                                }
+                                break; // End of file
+                            }
+                            *io_read = true;
+                            // Show the samples as they are in the file (before the volume scaling)
+                            visualizer.feed(&buffer[..bytes_read]);
+                            if let Some(detected) = beat_detector.process(&buffer[..bytes_read]) {
+                                *beat = Some(detected);
+                            }
+                            // Apply volume adjustment (simple scaling)
+                            for sample in buffer[..bytes_read].iter_mut() {
+                                let scaled = *sample >> (8 - *snd_vol); // Scale down to 0-8/8 volume
+                                *sample = scaled as u8;
+                            }
+                            bytes_read
+                        }
+                    };
+                    if bytes_read == 0 {
+                        // Paused, let the UI task run
+                        executor::sleep(UI_PERIOD).await;
+                        continue;
                     }
-                    led_vol_timeout = 48 * 4; // Show volume level for 4 seconds (assuming 48kHz sample rate and 512-byte reads)
-                }
-            }
 
-            // Display LED volume level indication (if needed)
-            if led_vol_timeout > 0 {
-                led_vol_timeout -= 1;
-                if led_vol_timeout == 0 {
-                    led_control = led_control.switch_to_audio_viz();
-                } else {
-                    led_control = led_control.switch_to_software(); // Switch to software control to show volume level
-                    if let Some(leds) = led_control.software_leds() {
-                        leds.set_all_states([
-                            snd_vol >= 1,
-                            snd_vol >= 2,
-                            snd_vol >= 3,
-                            snd_vol >= 4,
-                            snd_vol >= 5,
-                            snd_vol >= 6,
-                            snd_vol >= 7,
-                            snd_vol >= 8,
-                        ]);
+                    // Change the playback speed and stream the resulting samples
+                    player.borrow_mut().io_stream = true;
+                    for slice in buffer[..bytes_read].chunks(SPEED_SLICE_LEN) {
+                        output.clear();
+                        player.borrow_mut().speed.process(slice, |samples| {
+                            output.extend_from_slice(samples).unwrap();
+                        });
+                        audio_streamer.write_all(&output).await;
                     }
+                    player.borrow_mut().io_stream = false;
                 }
-            }
-
-            // Read audio data from the file
-            if !paused {
-                if led_vol_timeout == 0 {
-                    led_control.software_do(|leds| leds.led2.set_low().void_unwrap());
-                }
-                if let Ok(bytes_read) = mng.read(audio_file, &mut buffer) {
-                    if led_vol_timeout == 0 {
-                        led_control.software_do(|leds| leds.led2.set_high().void_unwrap());
-                    }
-                    if bytes_read == 0 {
-                        break; // End of file
-                    }
-                    if led_vol_timeout == 0 {
-                        led_control.software_do(|leds| leds.led3.set_high().void_unwrap());
-                    }
-                    // Apply volume adjustment (simple scaling)
-                    for sample in buffer[..bytes_read].iter_mut() {
-                        let scaled = *sample >> (8 - snd_vol); // Scale down to 0-8/8 volume
-                        *sample = scaled as u8;
+                player.borrow_mut().stopped = true;
+            });
+
+            // UI task: handle the buttons, the LEDs and the display
+            let ui = pin!(async {
+                let mut next_progress = timer.get_counter();
+                let mut redraw_progress = true; // Force a progress update, without waiting for the next period
+                let mut scrub = None; // Target position while scrubbing
+                let mut beat_effects = BeatEffects::METER;
+                let mut beat_leds = BeatLeds::new();
+                let mut beat_flash = BeatFlash::new();
+                let mut shown_bpm = None; // Tempo shown in the status line
+                loop {
+                    silicon_hal::watchdog::feed();
+                    {
+                        let mut guard = player.borrow_mut();
+                        let Player {
+                            mng,
+                            track,
+                            speed,
+                            loop_region,
+                            visualizer,
+                            beat_detector,
+                            beat,
+                            snd_vol,
+                            paused,
+                            io_read,
+                            io_stream,
+                            stopped,
+                        } = &mut *guard;
+                        if *stopped {
+                            break;
+                        }
+                        let beat = beat.take(); // Beat detected since the last step
+
+                        // Handle inputs
+                        match input.poll() {
+                            Some(PlayerAction::TogglePause) => {
+                                *paused = !*paused;
+                                redraw_progress = true; // Force immediate progress update after unpausing
+                            }
+                            Some(PlayerAction::Back) => {
+                                // Stop playback and go back to title menu
+                                break;
+                            }
+                            Some(PlayerAction::SkipForward) => {
+                                // Skip forward 10s (clamped to the end of the track)
+                                let _ = track.skip(mng, track.seconds_to_samples(SKIP_SECS) as i32);
+                                speed.reset();
+                                beat_detector.reset();
+                                redraw_progress = true; // Force immediate progress update
+                            }
+                            Some(PlayerAction::SkipBackward) => {
+                                // Skip backward 10s (clamped to the start of the track)
+                                let _ =
+                                    track.skip(mng, -(track.seconds_to_samples(SKIP_SECS) as i32));
+                                speed.reset();
+                                beat_detector.reset();
+                                redraw_progress = true; // Force immediate progress update
+                            }
+                            Some(PlayerAction::Scrub { forward, repeats }) => {
+                                // Move the target, faster and faster, playback goes on until released
+                                let step = track
+                                    .seconds_to_samples(SCRUB_STEP_SECS << (repeats / 10).min(5))
+                                    as i32;
+                                let step = if forward { step } else { -step };
+                                scrub = Some(
+                                    track.clamp_offset(scrub.unwrap_or(track.position()), step),
+                                );
+                                redraw_progress = true; // Force immediate progress update to show the target
+                            }
+                            Some(PlayerAction::ScrubEnd) => {
+                                if let Some(target) = scrub.take() {
+                                    let _ = track.seek(mng, target);
+                                    speed.reset();
+                                    beat_detector.reset();
+                                }
+                                redraw_progress = true;
+                            }
+                            Some(PlayerAction::VolumeUp) => {
+                                if *snd_vol < 8 {
+                                    *snd_vol += 1;
+                                }
+                                volume_alarm.start(VOLUME_LEDS_DURATION); // Show volume level for a while
+                            }
+                            Some(PlayerAction::VolumeDown) => {
+                                if *snd_vol > 1 {
+                                    *snd_vol -= 1;
+                                }
+                                volume_alarm.start(VOLUME_LEDS_DURATION); // Show volume level for a while
+                            }
+                            Some(PlayerAction::SpeedUp) => {
+                                speed.faster();
+                                redraw_progress = true; // Force immediate progress update (the times depend on the speed)
+                            }
+                            Some(PlayerAction::SpeedDown) => {
+                                speed.slower();
+                                redraw_progress = true;
+                            }
+                            Some(PlayerAction::TogglePitchMode) => {
+                                speed.toggle_mode();
+                                redraw_progress = true;
+                            }
+                            Some(PlayerAction::CycleLoop) => {
+                                *loop_region = loop_region.cycle(track.position());
+                                let mut buf = [0; 16];
+                                let mut status = TextBuf::new(&mut buf);
+                                match *loop_region {
+                                    LoopRegion::None => status.push_str("Loop off"),
+                                    LoopRegion::Start(a) => status
+                                        .push_str("A   ")
+                                        .push_time(track.samples_to_seconds(a)),
+                                    LoopRegion::Active(a, b) => status
+                                        .push_str("A-B ")
+                                        .push_time(track.samples_to_seconds(a))
+                                        .push_str("-")
+                                        .push_time(track.samples_to_seconds(b)),
+                                };
+                                display_status(&mut display, status.as_str());
+                                status_alarm.start(STATUS_DURATION);
+                                redraw_progress = true;
+                            }
+                            Some(PlayerAction::AddBookmark) => {
+                                let mut buf = [0; 16];
+                                let mut status = TextBuf::new(&mut buf);
+                                match bookmarks.add(track) {
+                                    Ok(mark) => status.push_str("+ ").push_str(&mark.name),
+                                    Err(_) => status.push_str("Marks full"),
+                                };
+                                display_status(&mut display, status.as_str());
+                                if bookmarks.save(mng, pwd).is_err() {
+                                    display_status(&mut display, "Save failed");
+                                }
+                                status_alarm.start(STATUS_DURATION);
+                            }
+                            Some(PlayerAction::DeleteBookmark) => {
+                                match bookmarks.remove_before(track.position()) {
+                                    Some(mark) => {
+                                        let mut buf = [0; 16];
+                                        let mut status = TextBuf::new(&mut buf);
+                                        status.push_str("- ").push_str(&mark.name);
+                                        display_status(&mut display, status.as_str());
+                                        if bookmarks.save(mng, pwd).is_err() {
+                                            display_status(&mut display, "Save failed");
+                                        }
+                                    }
+                                    None => display_status(&mut display, "No mark"),
+                                }
+                                status_alarm.start(STATUS_DURATION);
+                            }
+                            Some(
+                                action @ (PlayerAction::NextBookmark
+                                | PlayerAction::PreviousBookmark),
+                            ) => {
+                                let (mark, arrow) = if action == PlayerAction::NextBookmark {
+                                    (bookmarks.next(track.position()), ">")
+                                } else {
+                                    (bookmarks.previous(track), "<")
+                                };
+                                match mark {
+                                    Some(mark) => {
+                                        let _ = track.seek(mng, mark.position);
+                                        speed.reset();
+                                        beat_detector.reset();
+                                        let mut buf = [0; 16];
+                                        let mut status = TextBuf::new(&mut buf);
+                                        status.push_str(arrow).push_str(" ").push_str(&mark.name);
+                                        display_status(&mut display, status.as_str());
+                                    }
+                                    None => display_status(&mut display, "No mark"),
+                                }
+                                status_alarm.start(STATUS_DURATION);
+                                redraw_progress = true;
+                            }
+                            Some(PlayerAction::CycleView) => {
+                                if let Some((Some(art_file), _)) = cover_redraw.take() {
+                                    let _ = mng.close_file(art_file);
+                                }
+                                let view = visualizer.view().next();
+                                visualizer.set_view(view);
+                                if view == View::Cover {
+                                    // Redraw the cover art progressively, to keep the audio FIFO fed
+                                    cover_redraw = Some((open_cover_art(mng, pwd), 0));
+                                }
+                            }
+                            Some(PlayerAction::CycleBeatEffects) => {
+                                beat_effects = beat_effects.next();
+                                if beat_effects.leds == LedEffect::Meter
+                                    && !volume_alarm.is_pending()
+                                {
+                                    led_control = led_control.switch_to_audio_viz(*snd_vol);
+                                }
+                                display_status(&mut display, beat_effects.label());
+                                status_alarm.start(STATUS_DURATION);
+                            }
+                            None => {}
+                        }
+
+                        // Clear the status message after a while
+                        if status_alarm.poll() {
+                            display_status(&mut display, "");
+                            shown_bpm = None;
+                        }
+
+                        // Display LED volume level indication (if needed)
+                        if volume_alarm.poll() {
+                            if beat_effects.leds == LedEffect::Meter {
+                                led_control = led_control.switch_to_audio_viz(*snd_vol);
+                            }
+                        } else if volume_alarm.is_pending() {
+                            led_control = led_control.switch_to_software(); // Switch to software control to show volume level
+                            if let Some(leds) = led_control.software_leds() {
+                                // The fill fades to the new level
+                                leds.play(Animation::Progress {
+                                    done: *snd_vol as u32,
+                                    total: 8,
+                                });
+                            }
+                        }
+                        led_control.software_do(|leds| leds.update());
+
+                        // The I/O indicators only show while the LEDs are free (hardware meter):
+                        // LED2 goes off when a block is read, LED3 is on while the audio streamer is fed
+                        if !volume_alarm.is_pending() && beat_effects.leds == LedEffect::Meter {
+                            let (read, stream) = (core::mem::take(io_read), *io_stream);
+                            led_control.software_do(|leds| {
+                                let leds = leds.leds_mut();
+                                if read {
+                                    leds.led2.set_low().void_unwrap();
+                                } else {
+                                    leds.led2.set_high().void_unwrap();
+                                }
+                                if stream {
+                                    leds.led3.set_high().void_unwrap();
+                                } else {
+                                    leds.led3.set_low().void_unwrap();
+                                }
+                            });
+                        }
+
+                        // Beat effects
+                        if beat_effects.leds != LedEffect::Meter && !volume_alarm.is_pending() {
+                            led_control = led_control.switch_to_software();
+                            let states = beat_leds.render(beat_effects.leds, beat);
+                            led_control.software_do(|leds| leds.play(Animation::states(states)));
+                        }
+                        beat_flash.step(&mut display, beat_effects.flash, beat);
+
+                        // Draw a bit of the visualizer or of the cover art
+                        if let Some((art_file, chunk)) = cover_redraw {
+                            cover_redraw =
+                                redraw_cover_art_chunk(&mut display, mng, art_file, chunk);
+                        } else {
+                            visualizer.step(&mut display);
+                        }
+
+                        let now = timer.get_counter();
+                        if now >= next_progress {
+                            next_progress = now + PROGRESS_PERIOD;
+                            redraw_progress = true;
+                        }
+                        if redraw_progress {
+                            redraw_progress = false;
+                            // Update track progress display
+                            display_track_progress(
+                                &mut display,
+                                track,
+                                *loop_region,
+                                scrub,
+                                !*paused,
+                                speed,
+                            );
+                            // Show the tempo in the status line (unless a status message is shown)
+                            let bpm = beat_detector.bpm();
+                            if !status_alarm.is_pending() && bpm != shown_bpm {
+                                let mut buf = [0; 15];
+                                let mut bpm_str = TextBuf::new(&mut buf);
+                                if let Some(bpm) = bpm {
+                                    bpm_str.push_dec(bpm as u32, 1).push_str(" BPM");
+                                }
+                                display_status(&mut display, bpm_str.as_str());
+                                shown_bpm = bpm;
+                            }
+                        }
                     }
 
-                    let mut written = 0;
-                    while written < bytes_read {
-                        written += audio_streamer.write_samples(&buffer[written..bytes_read]);
-                    }
-                    if led_vol_timeout == 0 {
-                        led_control.software_do(|leds| leds.led3.set_low().void_unwrap());
-                    }
-                } else {
-                    // Error reading file - stop playback
-                    break;
+                    // Let the streaming task run until the next step
+                    executor::sleep(UI_PERIOD).await;
                 }
-            }
+                player.borrow_mut().stopped = true;
+                // Ensure we are back in software control and get the LedBank back
+                led_bank = Some(led_control.bring_down());
+            });
 
-            if cycle == 0 {
-                // Update track progress display
-                if let Ok(current_pos) = mng.file_offset(audio_file) {
-                    let elapsed = current_pos / (AUDIO_DATA_RATE as u32); // Elapsed time in seconds
-                    display_track_progress(&mut display, elapsed, duration, !paused);
-                }
-            }
-            cycle += 1;
-            if cycle >= 47 {
-                // ~500ms at 48kHz mono 8-bit with 512-byte reads
-                cycle = 0;
-            }
+            executor.run(&mut [streaming, ui]);
         }
+        timer0.release_alarm(executor.into_alarm());
+        timer0.release_alarm(volume_alarm);
+        timer0.release_alarm(status_alarm);
 
         // EOF reached, stop audio streamer
-        mng.close_file(audio_file).void_unwrap();
-        let mut leds = led_control.bring_down(); // Ensure we are back in software control and get the LedBank back
+        let Player { mng, track, .. } = player.into_inner();
+        mng.close_file(track.file()).void_unwrap();
+        if let Some((Some(art_file), _)) = cover_redraw {
+            let _ = mng.close_file(art_file);
+        }
+        let mut leds = led_bank.unwrap();
+        let btns = input.bring_down();
         leds.led4.set_high();
 
         // Go back to the title menu
@@ -270,6 +648,7 @@ pub fn run_playing(state: AppState) -> Option<AppState> {
             display,
             audio_streamer,
             sd_state,
+            timer0,
         }));
     }
     None
@@ -280,61 +659,104 @@ pub fn run_playing(state: AppState) -> Option<AppState> {
 /// This function attempts to open and display a "art.raw" file from the given title directory.
 /// It should be encoded as a 128x128 RGB565 row-major raw image.
 ///
-/// If the file is not found, ... TODO
+/// If the file is missing or too small, a warning is logged and the display is left as is.
 pub fn display_cover_art(
     display: &mut OledDisplay<display::Initialized>,
     mng: &mut VolumeManager,
     title_dir: RawDirectory,
 ) {
-    const X_START: u8 = 0;
-    const Y_START: u8 = 0;
-
     // Try to open the art.raw file
-    let art_file = match mng.open_file_in_dir(title_dir, "art.raw", Mode::ReadOnly) {
-        Ok(f) => f,
-        Err(_) => return, // TODO: Handle error appropriately
+    let Some(art_file) = open_cover_art(mng, title_dir) else {
+        warn!("no cover art (art.raw)");
+        return;
     };
 
-    //debug_leds.led1.set_high();
+    // Our stack is very small, so we have to read and draw the image in chunks
+    // We do so by reading 2048 bytes at a time (128 pixels * 8 rows * 2 bytes per pixel)
+    // Note: PCCM(128,512) = 2048 bytes
+    for chunk in 0..16 {
+        if draw_cover_art_chunk(display, mng, art_file, chunk).is_err() {
+            warn!("cover art truncated at row {}", chunk as u32 * 8);
+            break; // Read error or unexpected EOF
+        }
+    }
+
+    // Close the art file
+    mng.close_file(art_file).void_unwrap();
+}
+
+/// Open the cover art ("art.raw") of a title.
+///
+/// # Returns
+///
+/// * `Option<RawFile>` - The art file, or None if it is missing or too small.
+fn open_cover_art(mng: &mut VolumeManager, title_dir: RawDirectory) -> Option<RawFile> {
+    let art_file = mng
+        .open_file_in_dir(title_dir, "art.raw", Mode::ReadOnly)
+        .ok()?;
 
     // Ensure the file is the expected size (128*128*2 = 32768 bytes)
-    if let Ok(file_size) = mng.file_length(art_file) {
-        //debug_leds.led2.set_high();
-        if file_size < 32768 {
+    match mng.file_length(art_file) {
+        Ok(file_size) if file_size >= 32768 => Some(art_file),
+        _ => {
+            // Invalid file size, or unable to get it (probably an SD card error)
             let _ = mng.close_file(art_file);
-            return; // Invalid file size
+            None
         }
-    } else {
-        let _ = mng.close_file(art_file);
-        return; // Unable to get file size (probably an SD card error)
     }
+}
 
-    //debug_leds.led3.set_high();
-
-    // Our stack is very small, so we have to read and draw the image in chunks
-    // We do so by reading 2048 bytes at a time (128 pixels * 8 rows * 2 bytes per pixel)
-    // Note: PCCM(128,512) = 2048 bytes
+/// Read the next 8 rows of the cover art and draw them at the given chunk (rows `8*chunk` to
+/// `8*chunk + 7`).
+fn draw_cover_art_chunk(
+    display: &mut OledDisplay<display::Initialized>,
+    mng: &mut VolumeManager,
+    art_file: RawFile,
+    chunk: u8,
+) -> Result<(), ()> {
     let mut img_buffer = [0u8; 2048];
-    for row in 0..16 {
-        if let Ok(bytes_read) = mng.read(art_file, &mut img_buffer) {
-            if bytes_read != 2048 {
-                break; // Unexpected EOF
-            }
-            // Draw the 8 rows we just read
-            let x0 = X_START;
-            let y0 = Y_START + row * 8;
-            let x1 = x0 + 128 - 1;
-            let y1 = y0 + 8 - 1;
-            display.draw_area_from_slice(x0, y0, x1, y1, &img_buffer);
-        } else {
-            break; // Read error
-        }
+    let bytes_read = mng.read(art_file, &mut img_buffer).map_err(|_| ())?;
+    if bytes_read != 2048 {
+        return Err(()); // Unexpected EOF
     }
+    let y0 = chunk * 8;
+    let _ = display.draw_area_from_slice(0, y0, 127, y0 + 7, &img_buffer);
+    Ok(())
+}
 
-    //debug_leds.led4.set_high();
+/// Redraw a chunk (8 rows) of the cover art above the status and progress lines.
+///
+/// Without cover art (or on a read error), the chunk is cleared instead.
+///
+/// # Returns
+///
+/// * `Option<(Option<RawFile>, u8)>` - The art file and the next chunk to draw, or None once the
+///   redraw is done (the art file is then closed).
+fn redraw_cover_art_chunk(
+    display: &mut OledDisplay<display::Initialized>,
+    mng: &mut VolumeManager,
+    mut art_file: Option<RawFile>,
+    chunk: u8,
+) -> Option<(Option<RawFile>, u8)> {
+    if let Some(file) = art_file
+        && draw_cover_art_chunk(display, mng, file, chunk).is_err()
+    {
+        let _ = mng.close_file(file);
+        art_file = None;
+    }
+    if art_file.is_none() {
+        let y0 = chunk * 8;
+        let _ = display.fill_rectangle(0, y0, 127, y0 + 7, Rgb565::BLACK);
+    }
 
-    // Close the art file
-    mng.close_file(art_file).void_unwrap();
+    if chunk + 1 < COVER_REDRAW_CHUNKS {
+        Some((art_file, chunk + 1))
+    } else {
+        if let Some(file) = art_file {
+            mng.close_file(file).void_unwrap();
+        }
+        None
+    }
 }
 
 /// Display the track progress on the OLED display.
@@ -342,32 +764,30 @@ pub fn display_cover_art(
 /// # Arguments
 ///
 /// * `display` - The OLED display to draw on.
-/// * `elapsed` - The elapsed time in seconds.
-/// * `total` - The total duration of the track in seconds.
+/// * `track` - The track being played.
+/// * `loop_region` - The A-B loop, drawn on the progress bar.
+/// * `scrub` - The target position while scrubbing, drawn on the progress bar.
 /// * `is_playing` - Whether the track is currently playing or paused.
+/// * `speed` - The playback speed, the displayed times are scaled accordingly.
 pub fn display_track_progress(
     display: &mut OledDisplay<display::Initialized>,
-    elapsed: u32,
-    total: u32,
+    track: &Track,
+    loop_region: LoopRegion,
+    scrub: Option<u32>,
     is_playing: bool,
+    speed: &SpeedControl,
 ) {
-    // TODO: Implement a progress bar on the OLED display
-    // For now, we just print the elapsed time in seconds
-    let progress = elapsed * 8 / total;
-    let elapsed_str = format!(5; "{:02}:{:02}", elapsed / 60, elapsed % 60).void_unwrap();
-    let total_str = format!(5; "{:02}:{:02}", total / 60, total % 60).void_unwrap();
-    let bar_str = match progress {
-        0 => "*-------",
-        1 => "-*------",
-        2 => "--*-----",
-        3 => "---*----",
-        4 => "----*---",
-        5 => "-----*--",
-        6 => "------*-",
-        7 => "-------*",
-        _ => "********",
-    };
-    let progress_str = format!(20; "{} {} {}", elapsed_str, bar_str, total_str).void_unwrap();
+    // While scrubbing, show the target time instead of the elapsed time
+    let position = scrub.unwrap_or(track.position());
+    // Show the times as they will actually be experienced at the current speed
+    let elapsed = speed.playback_duration(track.samples_to_seconds(position));
+    let total = speed.playback_duration(track.samples_to_seconds(track.length()));
+    let mut elapsed_buf = [0; 5];
+    let mut elapsed_str = TextBuf::new(&mut elapsed_buf);
+    elapsed_str.push_time(elapsed);
+    let mut total_buf = [0; 5];
+    let mut total_str = TextBuf::new(&mut total_buf);
+    total_str.push_time(total);
 
     const CHARACTER_STYLE: MonoTextStyle<Rgb565> = MonoTextStyleBuilder::new()
         .font(&mono_font::ascii::FONT_6X10)
@@ -375,7 +795,29 @@ pub fn display_track_progress(
         .background_color(Rgb565::BLACK)
         .build();
     let pos = Point::new(8, 128 - 2); // Bottom-left corner
-    let text = Text::new(&progress_str, pos, CHARACTER_STYLE);
+    let text = Text::new(elapsed_str.as_str(), pos, CHARACTER_STYLE);
+    let _ = text.draw(display);
+    let pos = Point::new(128 - 5 * 6, 128 - 2); // Bottom-right corner
+    let text = Text::new(total_str.as_str(), pos, CHARACTER_STYLE);
+    let _ = text.draw(display);
+
+    // Draw the progress bar between the two times
+    draw_progress_bar(display, track, loop_region, scrub);
+
+    // Draw the speed tag above the progress bar ("p" when the pitch is preserved)
+    let mode_str = match speed.mode() {
+        SpeedMode::Resample => " ",
+        SpeedMode::Stretch => "p",
+    };
+    let mut speed_buf = [0; 6];
+    let mut speed_str = TextBuf::new(&mut speed_buf);
+    let label = speed.label();
+    speed_str
+        .pad(5usize.saturating_sub(label.len()))
+        .push_str(label)
+        .push_str(mode_str);
+    let pos = Point::new(128 - 6 * 6, 128 - 12);
+    let text = Text::new(speed_str.as_str(), pos, CHARACTER_STYLE);
     let _ = text.draw(display);
 
     // Draw play/pause icon
@@ -390,6 +832,89 @@ pub fn display_track_progress(
     let _ = icon_image.draw(&mut white_display);
 }
 
+/// Draw the graphical progress bar of the track.
+///
+/// From top to bottom, the bar is made of:
+/// - the A-B loop region (yellow),
+/// - the track itself, played part in white and remaining part in gray,
+/// - the scrub target marker (orange), only while scrubbing.
+fn draw_progress_bar(
+    display: &mut OledDisplay<display::Initialized>,
+    track: &Track,
+    loop_region: LoopRegion,
+    scrub: Option<u32>,
+) {
+    const X0: u8 = 8 + 6 * 6; // After the elapsed time
+    const X1: u8 = 128 - 6 * 6 - 1; // Before the total time
+    const Y0: u8 = 128 - 10; // Same height as the text
+    const LOOP_Y: u8 = Y0;
+    const TRACK_Y: u8 = Y0 + 3;
+    const MARKER_Y: u8 = Y0 + 7;
+    const Y1: u8 = 127;
+
+    // Scale in 32 bits: the track length is reduced to 24 bits, so that the sample position
+    // (reduced alike) times the bar width (< 256) cannot overflow
+    let shift = (32 - track.length().leading_zeros()).saturating_sub(24);
+    let length = (track.length() >> shift).max(1);
+    let x_of = |sample: u32| {
+        X0 + ((sample.min(track.length()) >> shift) * (X1 - X0) as u32 / length) as u8
+    };
+
+    // Loop region
+    let _ = display.fill_rectangle(X0, LOOP_Y, X1, TRACK_Y - 1, Rgb565::BLACK);
+    match loop_region {
+        LoopRegion::None => {}
+        LoopRegion::Start(a) => {
+            let _ = display.fill_rectangle(x_of(a), LOOP_Y, x_of(a), TRACK_Y - 2, Rgb565::YELLOW);
+        }
+        LoopRegion::Active(a, b) => {
+            let _ = display.fill_rectangle(x_of(a), LOOP_Y, x_of(b), TRACK_Y - 2, Rgb565::YELLOW);
+        }
+    }
+
+    // Track
+    let x = x_of(track.position());
+    let _ = display.fill_rectangle(X0, TRACK_Y, x, MARKER_Y - 1, Rgb565::WHITE);
+    if x < X1 {
+        let _ = display.fill_rectangle(x + 1, TRACK_Y, X1, MARKER_Y - 1, Rgb565::CSS_DIM_GRAY);
+    }
+
+    // Scrub target
+    let _ = display.fill_rectangle(X0, MARKER_Y, X1, Y1, Rgb565::BLACK);
+    if let Some(target) = scrub {
+        let x = x_of(target);
+        let _ = display.fill_rectangle(x, TRACK_Y - 1, x, Y1, Rgb565::CSS_ORANGE);
+        let _ = display.fill_rectangle(
+            x.saturating_sub(1).max(X0),
+            MARKER_Y + 1,
+            (x + 1).min(X1),
+            Y1,
+            Rgb565::CSS_ORANGE,
+        );
+    }
+}
+
+/// Display a short status message (loop, bookmarks, ...) above the track progress.
+///
+/// # Arguments
+///
+/// * `display` - The OLED display to draw on.
+/// * `status` - The message to display (at most 15 characters), an empty message clears it.
+pub fn display_status(display: &mut OledDisplay<display::Initialized>, status: &str) {
+    const CHARACTER_STYLE: MonoTextStyle<Rgb565> = MonoTextStyleBuilder::new()
+        .font(&mono_font::ascii::FONT_6X10)
+        .text_color(Rgb565::WHITE)
+        .background_color(Rgb565::BLACK)
+        .build();
+    // Pad the message so that it always covers the previous one
+    let mut buf = [0; 15];
+    let mut status_str = TextBuf::new(&mut buf);
+    status_str.push_str(status).pad(15);
+    let pos = Point::new(0, 128 - 12);
+    let text = Text::new(status_str.as_str(), pos, CHARACTER_STYLE);
+    let _ = text.draw(display);
+}
+
 mod glyph {
     //! Additional 8x8 glyphs for the Playing screen.
     use embedded_graphics::{image::ImageRaw, pixelcolor::BinaryColor};
@@ -0,0 +1,333 @@
+//! Beat detection and beat effects
+//!
+//! The beat detector looks for onsets in the sample stream: the energy of each read (512 samples,
+//! ~10.7ms) is compared to the energy of the previous one, and a sudden rise of energy (energy
+//! flux) well above its recent average is a beat. The interval between beats gives an estimate
+//! of the tempo.
+//!
+//! The beats drive software LED patterns (instead of the hardware energy meter) and optional
+//! flashes of a thin strip of the screen, right above the status line.
+//!
+//! Note: The "energy" is the sum of the absolute values of the samples (mean amplitude) rather
+//! than the sum of their squares, to save a multiplication per sample.
+
+use embedded_graphics::{pixelcolor::Rgb565, prelude::RgbColor as _};
+use silicon_hal::{display, timer::Duration};
+
+use super::UI_PERIOD;
+use crate::peripheral::OledDisplay;
+
+/// Sampling rate of the analyzed samples.
+const SAMPLE_RATE: u32 = 48_000;
+/// Minimum interval between two beats, in samples (~300ms, i.e. 200 BPM).
+const MIN_BEAT_INTERVAL: u32 = SAMPLE_RATE * 3 / 10;
+/// Tempo range of the estimate, the beat intervals are folded (doubled or halved) into it.
+const MIN_BPM: u32 = 80;
+const MAX_BPM: u32 = 180;
+/// Without a beat for this long (in samples, ~3s), the tempo estimate is dropped.
+const BPM_TIMEOUT: u32 = SAMPLE_RATE * 3;
+/// Number of consistent beat intervals needed before the tempo is shown.
+const BPM_CONFIDENCE: u8 = 3;
+/// Minimum energy flux of a beat (a rise of the mean amplitude of 1 over 512 samples).
+const MIN_FLUX: u32 = 512;
+
+/// A beat detected in the sample stream.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Beat {
+    /// Strength of the beat, from 1 (flux twice its recent average) to 3 (8 times or more).
+    pub strength: u8,
+}
+
+/// Onset (beat) detector and tempo estimator.
+pub struct BeatDetector {
+    /// Energy of the previous window.
+    energy: u32,
+    /// Recent average of the energy flux (leaky integrator over ~64 windows, ~0.7s).
+    avg_flux: u32,
+    /// Number of samples since the last beat.
+    since_beat: u32,
+    /// Whether the last beat was detected in the current stretch of samples (no seek since).
+    synced: bool,
+    /// Estimated beat period, in samples (0 if unknown).
+    period: u32,
+    /// Number of consecutive beat intervals matching the estimated period.
+    confidence: u8,
+}
+
+impl BeatDetector {
+    /// Create a new beat detector.
+    pub fn new() -> Self {
+        Self {
+            energy: 0,
+            avg_flux: 0,
+            since_beat: 0,
+            synced: false,
+            period: 0,
+            confidence: 0,
+        }
+    }
+
+    /// Forget the last window and beat (after a seek), the tempo estimate is kept.
+    pub fn reset(&mut self) {
+        self.energy = 0;
+        self.avg_flux = 0;
+        self.since_beat = 0;
+        self.synced = false;
+    }
+
+    /// Analyze a window of samples (8-bit unsigned, centered on 128).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Beat>` - The beat detected at the start of this window, if any.
+    pub fn process(&mut self, samples: &[u8]) -> Option<Beat> {
+        let energy: u32 = samples.iter().map(|&s| s.abs_diff(128) as u32).sum();
+        let flux = energy.saturating_sub(self.energy);
+        self.energy = energy;
+        self.since_beat = self.since_beat.saturating_add(samples.len() as u32);
+
+        // Compare the flux to its recent average (before including it)
+        let threshold = (self.avg_flux << 1).max(MIN_FLUX);
+        let beat = if flux > threshold && self.since_beat >= MIN_BEAT_INTERVAL {
+            let ratio = flux / self.avg_flux.max(1);
+            let strength = match ratio {
+                0..4 => 1,
+                4..8 => 2,
+                _ => 3,
+            };
+            self.update_tempo(self.since_beat);
+            self.since_beat = 0;
+            Some(Beat { strength })
+        } else {
+            if self.since_beat >= BPM_TIMEOUT {
+                self.period = 0;
+                self.confidence = 0;
+            }
+            None
+        };
+        self.avg_flux = self.avg_flux - (self.avg_flux >> 6) + (flux >> 6);
+        beat
+    }
+
+    /// Update the tempo estimate with the interval between two beats.
+    fn update_tempo(&mut self, mut interval: u32) {
+        if !self.synced || interval >= BPM_TIMEOUT {
+            // First beat after a seek or a silence
+            self.synced = true;
+            return;
+        }
+        // Fold the interval into the tempo range (a beat may be missed or doubled)
+        while interval < SAMPLE_RATE * 60 / MAX_BPM {
+            interval <<= 1;
+        }
+        while interval > SAMPLE_RATE * 60 / MIN_BPM {
+            interval >>= 1;
+        }
+
+        if self.period == 0 {
+            self.period = interval;
+            return;
+        }
+        // Consistent intervals (within 1/8 of the period) build up the confidence
+        if interval.abs_diff(self.period) <= self.period >> 3 {
+            self.confidence = (self.confidence + 1).min(BPM_CONFIDENCE);
+            self.period = self.period - (self.period >> 2) + (interval >> 2);
+        } else if self.confidence > 0 {
+            self.confidence -= 1;
+        } else {
+            self.period = interval;
+        }
+    }
+
+    /// Get the estimated tempo of the track, in beats per minute.
+    ///
+    /// The tempo is measured on the samples of the file, i.e. at normal speed.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u16>` - The tempo, or None until enough regular beats were detected.
+    pub fn bpm(&self) -> Option<u16> {
+        if self.period == 0 || self.confidence < BPM_CONFIDENCE {
+            return None;
+        }
+        Some(((SAMPLE_RATE * 60 + self.period / 2) / self.period) as u16)
+    }
+}
+
+/// LED pattern driven by the beats.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LedEffect {
+    /// Hardware energy meter (AudioViz), the beats are not used.
+    Meter,
+    /// All LEDs light up on a beat, then fall back like a VU-meter.
+    Pulse,
+    /// A single LED moves to the next position on each beat, bouncing back and forth.
+    Chase,
+}
+
+/// Effects driven by the beats, chosen by the user.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct BeatEffects {
+    pub leds: LedEffect,
+    /// Flash a strip of the screen on each beat.
+    pub flash: bool,
+}
+
+impl BeatEffects {
+    /// The hardware energy meter, without flashes.
+    pub const METER: Self = Self {
+        leds: LedEffect::Meter,
+        flash: false,
+    };
+
+    /// The effects chosen after these ones.
+    ///
+    /// Meter, Pulse, Chase, then Pulse and Chase with flashes.
+    pub fn next(self) -> Self {
+        let (leds, flash) = match (self.leds, self.flash) {
+            (LedEffect::Meter, _) => (LedEffect::Pulse, false),
+            (LedEffect::Pulse, false) => (LedEffect::Chase, false),
+            (LedEffect::Chase, false) => (LedEffect::Pulse, true),
+            (LedEffect::Pulse, true) => (LedEffect::Chase, true),
+            (LedEffect::Chase, true) => (LedEffect::Meter, false),
+        };
+        Self { leds, flash }
+    }
+
+    /// Short name of the effects, shown in the status line.
+    pub fn label(&self) -> &'static str {
+        match (self.leds, self.flash) {
+            (LedEffect::Meter, _) => "LEDs: meter",
+            (LedEffect::Pulse, false) => "LEDs: pulse",
+            (LedEffect::Chase, false) => "LEDs: chase",
+            (LedEffect::Pulse, true) => "Pulse + flash",
+            (LedEffect::Chase, true) => "Chase + flash",
+        }
+    }
+}
+
+/// Duration between two steps of the Pulse fall.
+const PULSE_FALL_STEP: Duration = Duration::millis(30);
+/// Number of UI steps ([`UI_PERIOD`], 10ms) between two steps of the Pulse fall.
+const PULSE_FALL_PERIOD: u8 = (PULSE_FALL_STEP.ticks() / UI_PERIOD.ticks()) as u8;
+
+/// Software LED patterns driven by the beats.
+pub struct BeatLeds {
+    /// Number of LEDs lit by the Pulse effect.
+    level: u8,
+    /// UI steps left before the Pulse level falls.
+    fall_timer: u8,
+    /// Position of the Chase LED.
+    position: u8,
+    /// Direction of the Chase LED.
+    rising: bool,
+}
+
+impl BeatLeds {
+    /// Create new LED patterns (all LEDs off).
+    pub fn new() -> Self {
+        Self {
+            level: 0,
+            fall_timer: 0,
+            position: 0,
+            rising: true,
+        }
+    }
+
+    /// Advance the pattern by one UI step ([`UI_PERIOD`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `effect` - The LED effect to render.
+    /// * `beat` - The beat detected since the previous UI step, if any.
+    ///
+    /// # Returns
+    ///
+    /// * `[bool; 8]` - The states of the LEDs.
+    pub fn render(&mut self, effect: LedEffect, beat: Option<Beat>) -> [bool; 8] {
+        match effect {
+            LedEffect::Meter => [false; 8],
+            LedEffect::Pulse => {
+                if let Some(beat) = beat {
+                    // Stronger beats light up more LEDs
+                    self.level = self.level.max(5 + beat.strength);
+                    self.fall_timer = PULSE_FALL_PERIOD;
+                } else if self.fall_timer > 1 {
+                    self.fall_timer -= 1;
+                } else if self.level > 0 {
+                    self.level -= 1;
+                    self.fall_timer = PULSE_FALL_PERIOD;
+                }
+                core::array::from_fn(|i| (i as u8) < self.level)
+            }
+            LedEffect::Chase => {
+                if beat.is_some() {
+                    if self.rising && self.position == 7 || !self.rising && self.position == 0 {
+                        self.rising = !self.rising;
+                    }
+                    self.position = if self.rising {
+                        self.position + 1
+                    } else {
+                        self.position - 1
+                    };
+                }
+                core::array::from_fn(|i| i as u8 == self.position)
+            }
+        }
+    }
+}
+
+/// Rows of the flashing strip (between the visualizer area and the status line).
+const FLASH_Y0: u8 = 105;
+const FLASH_Y1: u8 = 106;
+/// Duration a flash stays on screen.
+const FLASH_DURATION: Duration = Duration::millis(40);
+/// Number of UI steps ([`UI_PERIOD`], 10ms) a flash stays on screen.
+const FLASH_FRAMES: u8 = (FLASH_DURATION.ticks() / UI_PERIOD.ticks()) as u8;
+
+/// Flashes of a strip of the screen on the beats.
+pub struct BeatFlash {
+    /// UI steps left before the flash is cleared (0 if not shown).
+    frames_left: u8,
+}
+
+impl BeatFlash {
+    /// Create a new (hidden) flash.
+    pub fn new() -> Self {
+        Self { frames_left: 0 }
+    }
+
+    /// Advance the flash by one UI step ([`UI_PERIOD`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `display` - The OLED display to draw on.
+    /// * `enabled` - Whether flashes are enabled, a flash being shown is still cleared.
+    /// * `beat` - The beat detected since the previous UI step, if any.
+    pub fn step(
+        &mut self,
+        display: &mut OledDisplay<display::Initialized>,
+        enabled: bool,
+        beat: Option<Beat>,
+    ) {
+        match beat {
+            Some(beat) if enabled => {
+                let color = match beat.strength {
+                    1 => Rgb565::BLUE,
+                    2 => Rgb565::MAGENTA,
+                    _ => Rgb565::WHITE,
+                };
+                let _ = display.fill_rectangle(0, FLASH_Y0, 127, FLASH_Y1, color);
+                self.frames_left = FLASH_FRAMES;
+            }
+            _ if self.frames_left > 0 => {
+                self.frames_left -= 1;
+                if self.frames_left == 0 || !enabled {
+                    let _ = display.fill_rectangle(0, FLASH_Y0, 127, FLASH_Y1, Rgb565::BLACK);
+                    self.frames_left = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Button handling for the Playing screen
+//!
+//! The button events come from the HAL event layer ([`ButtonInput`]), which debounces the
+//! buttons against Timer0, and makes some buttons auto-repeat when held.
+//!
+//! The PAUSE and BACK buttons double as modifiers: while one of them is held, the other buttons
+//! trigger alternative actions (speed control, A-B loop, bookmarks, ...). Buttons used in such a
+//! chord do not trigger their own action when released.
+//!
+//! FWD and BWD skip 10s when tapped. When held, they scrub through the track instead: the target
+//! position moves faster and faster, and playback jumps there once the button is released.
+
+use silicon_hal::{
+    gpio::BtnBank,
+    input::{BUTTONS, Button, ButtonEvent, ButtonInput, InputConfig},
+    timer::Duration,
+};
+
+/// Buttons of the Playing screen.
+const VOL_DOWN: Button = Button::Btn1;
+const VOL_UP: Button = Button::Btn2;
+const BACK: Button = Button::Btn3;
+const PAUSE: Button = Button::Btn4;
+const BACKWARD: Button = Button::Btn5;
+const FORWARD: Button = Button::Btn6;
+
+/// Timings of the buttons: held buttons repeat after ~300ms, volume every ~250ms,
+/// scrubbing (FWD/BWD) every ~100ms.
+const INPUT_CONFIG: InputConfig = InputConfig {
+    repeat_delay: Duration::millis(300),
+    repeat_periods: {
+        let mut periods = [Duration::millis(250); BUTTONS];
+        periods[BACKWARD.index()] = Duration::millis(100);
+        periods[FORWARD.index()] = Duration::millis(100);
+        periods
+    },
+    modifiers: BACK.mask() | PAUSE.mask(),
+    ..InputConfig::DEFAULT
+};
+
+/// Action requested by the user on the Playing screen.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PlayerAction {
+    /// Play or pause the track (PAUSE).
+    TogglePause,
+    /// Stop the playback and go back to the title menu (BACK).
+    Back,
+    /// Skip forward (FWD, tapped).
+    SkipForward,
+    /// Skip backward (BWD, tapped).
+    SkipBackward,
+    /// Move the scrub target (FWD/BWD, held).
+    ///
+    /// `repeats` is the number of repeats since the button was pressed, used to accelerate.
+    Scrub { forward: bool, repeats: u16 },
+    /// Jump to the scrub target (FWD/BWD released after scrubbing).
+    ScrubEnd,
+    /// Increase the volume (VOL+).
+    VolumeUp,
+    /// Decrease the volume (VOL-).
+    VolumeDown,
+    /// Play faster (PAUSE + VOL+).
+    SpeedUp,
+    /// Play slower (PAUSE + VOL-).
+    SpeedDown,
+    /// Switch between resampling and pitch-preserving speed change (PAUSE + FWD).
+    TogglePitchMode,
+    /// Mark the start of the A-B loop, then its end, then clear it (PAUSE + BWD).
+    CycleLoop,
+    /// Drop a bookmark at the current position (PAUSE + BACK).
+    AddBookmark,
+    /// Delete the closest bookmark before the current position (BACK + PAUSE).
+    DeleteBookmark,
+    /// Go to the next bookmark (BACK + FWD).
+    NextBookmark,
+    /// Go to the previous bookmark (BACK + BWD).
+    PreviousBookmark,
+    /// Switch between the cover art, the spectrum analyzer and the oscilloscope (BACK + VOL+).
+    CycleView,
+    /// Switch between the LED and screen effects driven by the beats (BACK + VOL-).
+    CycleBeatEffects,
+}
+
+/// Input handler for the Playing screen.
+pub struct PlayerInput {
+    buttons: ButtonInput,
+}
+
+impl PlayerInput {
+    /// Create a new input handler.
+    ///
+    /// Buttons already held when the handler is created are ignored until released.
+    pub fn new(btns: BtnBank) -> Self {
+        Self {
+            buttons: ButtonInput::new(btns, INPUT_CONFIG),
+        }
+    }
+
+    /// Bring down the input handler and get the buttons back.
+    pub fn bring_down(self) -> BtnBank {
+        self.buttons.bring_down()
+    }
+
+    /// Poll the buttons and return the requested action (if any).
+    ///
+    /// The events without action are skipped, the next ones stay queued until the next poll.
+    pub fn poll(&mut self) -> Option<PlayerAction> {
+        self.buttons.sample();
+        while let Some(event) = self.buttons.next_event() {
+            if let Some(action) = Self::action(event) {
+                return Some(action);
+            }
+        }
+        None
+    }
+
+    /// Get the action of a button event (if any).
+    fn action(event: ButtonEvent) -> Option<PlayerAction> {
+        match event {
+            // Chords with PAUSE held
+            ButtonEvent::Chord {
+                modifier: PAUSE,
+                button,
+            } => match button {
+                VOL_UP => Some(PlayerAction::SpeedUp),
+                VOL_DOWN => Some(PlayerAction::SpeedDown),
+                FORWARD => Some(PlayerAction::TogglePitchMode),
+                BACKWARD => Some(PlayerAction::CycleLoop),
+                BACK => Some(PlayerAction::AddBookmark),
+                _ => None,
+            },
+            ButtonEvent::Repeat {
+                button: VOL_UP,
+                modifier: Some(PAUSE),
+                ..
+            } => Some(PlayerAction::SpeedUp),
+            ButtonEvent::Repeat {
+                button: VOL_DOWN,
+                modifier: Some(PAUSE),
+                ..
+            } => Some(PlayerAction::SpeedDown),
+
+            // Chords with BACK held
+            ButtonEvent::Chord {
+                modifier: BACK,
+                button,
+            } => match button {
+                PAUSE => Some(PlayerAction::DeleteBookmark),
+                FORWARD => Some(PlayerAction::NextBookmark),
+                BACKWARD => Some(PlayerAction::PreviousBookmark),
+                VOL_UP => Some(PlayerAction::CycleView),
+                VOL_DOWN => Some(PlayerAction::CycleBeatEffects),
+                _ => None,
+            },
+
+            // Single buttons, the buttons used in a chord are ignored until released
+            ButtonEvent::Repeat {
+                button: button @ (FORWARD | BACKWARD),
+                count,
+                modifier: None,
+            } => Some(PlayerAction::Scrub {
+                forward: button == FORWARD,
+                repeats: count,
+            }),
+            ButtonEvent::Released {
+                button: FORWARD,
+                repeats: 0,
+                consumed: false,
+            } => Some(PlayerAction::SkipForward),
+            ButtonEvent::Released {
+                button: BACKWARD,
+                repeats: 0,
+                consumed: false,
+            } => Some(PlayerAction::SkipBackward),
+            ButtonEvent::Released {
+                button: FORWARD | BACKWARD,
+                consumed: false,
+                ..
+            } => Some(PlayerAction::ScrubEnd),
+            ButtonEvent::Released {
+                button: PAUSE,
+                consumed: false,
+                ..
+            } => Some(PlayerAction::TogglePause),
+            ButtonEvent::Released {
+                button: BACK,
+                consumed: false,
+                ..
+            } => Some(PlayerAction::Back),
+            ButtonEvent::Pressed(VOL_UP)
+            | ButtonEvent::Repeat {
+                button: VOL_UP,
+                modifier: None,
+                ..
+            } => Some(PlayerAction::VolumeUp),
+            ButtonEvent::Pressed(VOL_DOWN)
+            | ButtonEvent::Repeat {
+                button: VOL_DOWN,
+                modifier: None,
+                ..
+            } => Some(PlayerAction::VolumeDown),
+            _ => None,
+        }
+    }
+}
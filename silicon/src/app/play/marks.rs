@@ -0,0 +1,220 @@
+//! A-B repeat loop and bookmarks
+//!
+//! The A-B loop is set during playback by marking the start (A) and then the end (B) of the
+//! region to repeat. Bookmarks are named positions in the track, stored in a `marks.txt` file in
+//! the title directory so they survive a reboot. Each line of this file is a bookmark:
+//!
+//! ```text
+//! <sample position> <name>
+//! ```
+//!
+//! New bookmarks are named after their position (`mm:ss`), but the file can be edited on a
+//! computer to give them better names.
+
+use embedded_sdmmc::{Mode, RawDirectory};
+use heapless::{String, Vec};
+use silicon_hal::text::TextBuf;
+
+use super::position::Track;
+use crate::fs::VolumeManager;
+
+/// Name of the bookmark file, in the title directory.
+const MARKS_FILE: &str = "marks.txt";
+/// Maximum number of bookmarks per track.
+pub const MAX_BOOKMARKS: usize = 16;
+/// Maximum length of a bookmark name.
+pub const MAX_NAME_LENGTH: usize = 14;
+/// Size of the bookmark file buffer (enough for [`MAX_BOOKMARKS`] full lines).
+const FILE_BUFFER_LEN: usize = MAX_BOOKMARKS * (10 + 1 + MAX_NAME_LENGTH + 1);
+
+/// Going to the previous bookmark ignores the bookmarks less than 2 seconds before the current
+/// position, so that it can be pressed repeatedly (like the "previous track" of a player).
+const PREVIOUS_MARGIN_SECS: u32 = 2;
+
+/// State of the A-B repeat loop.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LoopRegion {
+    /// No loop.
+    None,
+    /// The start of the loop has been marked, waiting for the end.
+    Start(u32),
+    /// Looping between the two sample positions.
+    Active(u32, u32),
+}
+
+impl LoopRegion {
+    /// Advance the A-B loop state: mark A, then mark B, then clear the loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The current sample position.
+    pub fn cycle(self, position: u32) -> Self {
+        match self {
+            LoopRegion::None => LoopRegion::Start(position),
+            LoopRegion::Start(a) if a == position => self,
+            LoopRegion::Start(a) => LoopRegion::Active(a.min(position), a.max(position)),
+            LoopRegion::Active(_, _) => LoopRegion::None,
+        }
+    }
+
+    /// The sample position playback must not go past, if the loop is active.
+    #[inline(always)]
+    pub fn end(&self) -> Option<u32> {
+        match self {
+            LoopRegion::Active(_, b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// The sample position to go back to once the end of the loop is reached, if active.
+    #[inline(always)]
+    pub fn start(&self) -> Option<u32> {
+        match self {
+            LoopRegion::Active(a, _) => Some(*a),
+            _ => None,
+        }
+    }
+}
+
+/// A named position in a track.
+pub struct Bookmark {
+    /// Sample position of the bookmark.
+    pub position: u32,
+    /// Name of the bookmark.
+    pub name: String<MAX_NAME_LENGTH>,
+}
+
+/// Bookmarks of the track being played.
+pub struct Bookmarks {
+    /// Bookmarks, sorted by position.
+    marks: Vec<Bookmark, MAX_BOOKMARKS>,
+}
+
+impl Bookmarks {
+    /// Load the bookmarks of a title.
+    ///
+    /// Missing or invalid bookmark files are treated as empty, and invalid lines are skipped.
+    pub fn load(mng: &mut VolumeManager, title_dir: RawDirectory) -> Self {
+        let mut bookmarks = Self { marks: Vec::new() };
+        let file = match mng.open_file_in_dir(title_dir, MARKS_FILE, Mode::ReadOnly) {
+            Ok(f) => f,
+            Err(_) => return bookmarks,
+        };
+        let mut buffer = [0u8; FILE_BUFFER_LEN];
+        let len = mng.read(file, &mut buffer).unwrap_or(0);
+        let _ = mng.close_file(file);
+
+        // Parsed as bytes: only the names need to be valid UTF-8
+        for line in buffer[..len].split(|&c| c == b'\n') {
+            let line = line.trim_ascii();
+            let split = line.iter().position(|&c| c == b' ').unwrap_or(line.len());
+            let (position, name) = line.split_at(split);
+            if let Some(position) = parse_position(position) {
+                // Truncate the name (on a char boundary) if needed
+                let name = name.trim_ascii();
+                let name = &name[..name.len().min(MAX_NAME_LENGTH)];
+                let name = match core::str::from_utf8(name) {
+                    Ok(name) => name,
+                    Err(e) => core::str::from_utf8(&name[..e.valid_up_to()]).unwrap_or(""),
+                };
+                let _ = bookmarks.insert(position, name);
+            }
+        }
+        bookmarks
+    }
+
+    /// Save the bookmarks of a title (overwriting the bookmark file).
+    pub fn save(&self, mng: &mut VolumeManager, title_dir: RawDirectory) -> Result<(), ()> {
+        let file = mng
+            .open_file_in_dir(title_dir, MARKS_FILE, Mode::ReadWriteCreateOrTruncate)
+            .map_err(|_| ())?;
+        let mut result = Ok(());
+        for mark in self.marks.iter() {
+            let mut buf = [0; 10 + 1 + MAX_NAME_LENGTH + 1];
+            let mut line = TextBuf::new(&mut buf);
+            line.push_dec(mark.position, 1)
+                .push_str(" ")
+                .push_str(&mark.name)
+                .push_str("\n");
+            if mng.write(file, line.as_str().as_bytes()).is_err() {
+                result = Err(());
+                break;
+            }
+        }
+        mng.close_file(file).map_err(|_| ())?;
+        result
+    }
+
+    /// Add a bookmark at the current position of the track, named after its time in the track.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<&Bookmark, ()>` - The new bookmark, or an error if there is no space left.
+    pub fn add(&mut self, track: &Track) -> Result<&Bookmark, ()> {
+        let position = track.position();
+        let seconds = track.samples_to_seconds(position);
+        let mut buf = [0; MAX_NAME_LENGTH];
+        let mut name = TextBuf::new(&mut buf);
+        name.push_time(seconds);
+        self.insert(position, name.as_str())
+    }
+
+    /// Remove the closest bookmark before the given position (or at it).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Bookmark>` - The removed bookmark, if any.
+    pub fn remove_before(&mut self, position: u32) -> Option<Bookmark> {
+        let index = self.marks.iter().rposition(|m| m.position <= position)?;
+        Some(self.marks.remove(index))
+    }
+
+    /// Find the first bookmark after the given position.
+    pub fn next(&self, position: u32) -> Option<&Bookmark> {
+        self.marks.iter().find(|m| m.position > position)
+    }
+
+    /// Find the last bookmark before the current position of the track.
+    ///
+    /// Bookmarks just before the position are skipped, so that going to the previous bookmark
+    /// repeatedly goes further back instead of staying on the same bookmark.
+    pub fn previous(&self, track: &Track) -> Option<&Bookmark> {
+        let position = track
+            .position()
+            .saturating_sub(track.seconds_to_samples(PREVIOUS_MARGIN_SECS));
+        self.marks.iter().rev().find(|m| m.position < position)
+    }
+
+    /// Insert a bookmark, keeping the list sorted (an existing bookmark at the same position is
+    /// renamed).
+    fn insert(&mut self, position: u32, name: &str) -> Result<&Bookmark, ()> {
+        let name = String::try_from(name).map_err(|_| ())?;
+        let index = match self.marks.binary_search_by_key(&position, |m| m.position) {
+            Ok(index) => {
+                self.marks[index].name = name;
+                index
+            }
+            Err(index) => {
+                self.marks
+                    .insert(index, Bookmark { position, name })
+                    .map_err(|_| ())?;
+                index
+            }
+        };
+        Ok(&self.marks[index])
+    }
+}
+
+/// Parse a sample position (decimal digits only, at most `u32::MAX`).
+fn parse_position(digits: &[u8]) -> Option<u32> {
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u32, |value, &c| {
+        let digit = c.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        value.checked_mul(10)?.checked_add(digit as u32)
+    })
+}
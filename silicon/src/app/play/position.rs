@@ -0,0 +1,165 @@
+//! Sample-position model for the track being played
+//!
+//! The playing loop used to work directly with byte offsets in the audio file and relative
+//! seeks, which silently failed near the start or the end of the file (issue #1). [`Track`]
+//! keeps track of the current position in samples, and every seek is done from the start of the
+//! file to a clamped, exact sample position.
+//!
+//! Positions are always converted to byte offsets through the [`AudioFormat`] of the file, so
+//! seeks land on a frame boundary (a sample for every channel) whatever the format is.
+
+use embedded_sdmmc::RawFile;
+
+use crate::fs::VolumeManager;
+
+/// Layout of the audio samples in a file.
+#[derive(Clone, Copy)]
+pub struct AudioFormat {
+    /// Number of samples per second (for each channel).
+    pub sample_rate: u32,
+    /// Number of interleaved channels.
+    pub channels: u32,
+    /// Number of bits per sample, each sample is stored on a whole number of bytes.
+    pub bit_depth: u32,
+    /// Offset of the first sample in the file (size of the header, if any).
+    pub data_offset: u32,
+}
+
+impl AudioFormat {
+    /// Size of one frame (one sample for every channel) in bytes.
+    #[inline(always)]
+    pub const fn frame_size(&self) -> u32 {
+        self.channels * self.bit_depth.div_ceil(8)
+    }
+}
+
+/// Audio track opened for playback.
+pub struct Track {
+    file: RawFile,
+    format: AudioFormat,
+    /// Length of the track in samples.
+    length: u32,
+    /// Position of the next sample to be read.
+    position: u32,
+}
+
+impl Track {
+    /// Open a track from an already opened audio file.
+    ///
+    /// # Arguments
+    ///
+    /// * `mng` - The volume manager the file was opened with.
+    /// * `file` - The audio file.
+    /// * `format` - The layout of the samples in the file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Track, ()>` - The track positioned on its first sample, or an error if the file
+    ///   cannot be read.
+    pub fn new(mng: &mut VolumeManager, file: RawFile, format: AudioFormat) -> Result<Self, ()> {
+        let size = mng.file_length(file).map_err(|_| ())?;
+        let mut track = Self {
+            file,
+            format,
+            length: size.saturating_sub(format.data_offset) / format.frame_size(),
+            position: 0,
+        };
+        track.seek(mng, 0)?;
+        Ok(track)
+    }
+
+    /// The underlying audio file.
+    #[inline(always)]
+    pub fn file(&self) -> RawFile {
+        self.file
+    }
+
+    /// Length of the track in samples.
+    #[inline(always)]
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    /// Position of the next sample to be read.
+    #[inline(always)]
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+
+    /// Convert a number of samples to seconds.
+    #[inline(always)]
+    pub fn samples_to_seconds(&self, samples: u32) -> u32 {
+        samples / self.format.sample_rate
+    }
+
+    /// Convert a number of seconds to samples (saturating).
+    #[inline(always)]
+    pub fn seconds_to_samples(&self, seconds: u32) -> u32 {
+        seconds.saturating_mul(self.format.sample_rate)
+    }
+
+    /// Read samples from the current position.
+    ///
+    /// Only whole frames are read, so the file always stays aligned on a frame boundary.
+    ///
+    /// # Arguments
+    ///
+    /// * `mng` - The volume manager the file was opened with.
+    /// * `buf` - The buffer to read the samples into.
+    /// * `end` - Optional sample position the read must not go past.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, ()>` - The number of bytes read (0 at the end of the track or at `end`).
+    pub fn read(
+        &mut self,
+        mng: &mut VolumeManager,
+        buf: &mut [u8],
+        end: Option<u32>,
+    ) -> Result<usize, ()> {
+        let frame_size = self.format.frame_size() as usize;
+        let end = end.unwrap_or(self.length).min(self.length);
+        let remaining = end.saturating_sub(self.position) as usize * frame_size;
+        let len = buf.len().min(remaining);
+        let len = len - len % frame_size;
+        if len == 0 {
+            return Ok(0);
+        }
+        let bytes_read = mng.read(self.file, &mut buf[..len]).map_err(|_| ())?;
+        self.position += (bytes_read / frame_size) as u32;
+        if bytes_read % frame_size != 0 {
+            // Partial frame (truncated file?), drop it and realign the file
+            self.seek(mng, self.position)?;
+        }
+        Ok(bytes_read - bytes_read % frame_size)
+    }
+
+    /// Seek to the given sample position, clamped to the track.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, ()>` - The new position (in samples).
+    pub fn seek(&mut self, mng: &mut VolumeManager, sample: u32) -> Result<u32, ()> {
+        let sample = sample.min(self.length);
+        let offset = self.format.data_offset + sample * self.format.frame_size();
+        mng.file_seek_from_start(self.file, offset)
+            .map_err(|_| ())?;
+        self.position = sample;
+        Ok(sample)
+    }
+
+    /// Compute the position `delta` samples away from `from`, clamped to the track.
+    #[inline(always)]
+    pub fn clamp_offset(&self, from: u32, delta: i32) -> u32 {
+        from.saturating_add_signed(delta).min(self.length)
+    }
+
+    /// Seek relatively to the current position, clamped to the track.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, ()>` - The new position (in samples).
+    pub fn skip(&mut self, mng: &mut VolumeManager, delta: i32) -> Result<u32, ()> {
+        self.seek(mng, self.clamp_offset(self.position, delta))
+    }
+}
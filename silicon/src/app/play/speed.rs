@@ -0,0 +1,302 @@
+//! Playback speed control
+//!
+//! The audio streamer always consumes samples at 48kHz, so playing faster or slower means
+//! producing fewer or more samples than we read from the file. Two strategies are provided:
+//!
+//! - [`SpeedMode::Resample`] - plain linear-interpolation resampling. Cheap, but the pitch
+//!   follows the speed (like a tape). Good enough for music.
+//! - [`SpeedMode::Stretch`] - time-domain stretching (WSOLA). Overlapping segments of the input
+//!   are picked so that they line up with what was previously played and cross-faded together.
+//!   The pitch is preserved, which is what you want for speech (audiobooks, language practice).
+//!
+//! Both processors keep the number of multiplications per sample as low as possible (see the
+//! `play` module).
+
+/// Available speed factors, in Q8 fixed-point (256 = 1x).
+const SPEEDS: [u32; 6] = [128, 192, 256, 320, 384, 512];
+/// Labels for the available speed factors (same order as [`SPEEDS`]).
+const SPEED_LABELS: [&str; 6] = ["0.5x", "0.75x", "1x", "1.25x", "1.5x", "2x"];
+/// Index of the normal (1x) speed in [`SPEEDS`].
+const NORMAL_SPEED: usize = 2;
+
+/// Length of the cross-fade between two consecutive segments (also the synthesis hop).
+const OVERLAP: usize = 128;
+/// Maximum shift (in samples) of a segment around its nominal position while searching for the
+/// best alignment.
+const TOLERANCE: usize = 32;
+/// Step between two candidate positions during the alignment search.
+const SEARCH_STEP: usize = 4;
+/// Step between two compared samples during the alignment search.
+const COMPARE_STEP: usize = 4;
+/// Number of input samples required to produce one stretched segment.
+const WINDOW: usize = 2 * TOLERANCE + 2 * OVERLAP;
+
+/// Size of the output buffer, samples are handed to the sink in chunks of at most this size.
+const OUTPUT_LEN: usize = 256;
+
+/// Strategy used to change the playback speed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SpeedMode {
+    /// Linear resampling, the pitch changes with the speed.
+    Resample,
+    /// Time-domain stretching (WSOLA), the pitch is preserved.
+    Stretch,
+}
+
+/// Playback speed processor.
+///
+/// Takes blocks of 8-bit unsigned samples read from the file and produces blocks of samples
+/// to be streamed, played at the selected speed.
+pub struct SpeedControl {
+    /// Index of the current speed in [`SPEEDS`].
+    speed: usize,
+    /// Current speed strategy.
+    mode: SpeedMode,
+    /// Last sample produced, used to start the next processor without a click.
+    last_out: u8,
+    /// Output buffer.
+    out: [u8; OUTPUT_LEN],
+    out_len: usize,
+
+    // Resampler state
+    /// Previous input sample (left side of the interpolation).
+    prev: u8,
+    /// Fractional position between `prev` and the next input sample (Q8).
+    phase: u32,
+
+    // Stretcher state
+    /// Pending input samples. `fifo[TOLERANCE]` is the nominal position of the next segment.
+    fifo: [u8; WINDOW],
+    fifo_len: usize,
+    /// Natural continuation of the last played segment, faded out under the next segment.
+    tail: [u8; OVERLAP],
+    /// Input samples still to be dropped before filling the fifo (analysis hop larger than
+    /// the window).
+    skip: usize,
+}
+
+impl SpeedControl {
+    /// Create a new speed processor, at normal speed and in resampling mode.
+    pub fn new() -> Self {
+        Self {
+            speed: NORMAL_SPEED,
+            mode: SpeedMode::Resample,
+            last_out: 128,
+            out: [0; OUTPUT_LEN],
+            out_len: 0,
+            prev: 128,
+            phase: 0,
+            fifo: [128; WINDOW],
+            fifo_len: 0,
+            tail: [128; OVERLAP],
+            skip: 0,
+        }
+    }
+
+    /// Current speed factor in Q8 fixed-point (256 = 1x).
+    #[inline(always)]
+    pub fn factor(&self) -> u32 {
+        SPEEDS[self.speed]
+    }
+
+    /// Human readable label for the current speed (e.g. "1.25x").
+    #[inline(always)]
+    pub fn label(&self) -> &'static str {
+        SPEED_LABELS[self.speed]
+    }
+
+    /// Current speed strategy.
+    #[inline(always)]
+    pub fn mode(&self) -> SpeedMode {
+        self.mode
+    }
+
+    /// Select the next faster speed (if any).
+    ///
+    /// The change takes effect on the next sample, without resetting the processor.
+    pub fn faster(&mut self) {
+        if self.speed + 1 < SPEEDS.len() {
+            self.speed += 1;
+        }
+    }
+
+    /// Select the next slower speed (if any).
+    ///
+    /// The change takes effect on the next sample, without resetting the processor.
+    pub fn slower(&mut self) {
+        if self.speed > 0 {
+            self.speed -= 1;
+        }
+    }
+
+    /// Switch between resampling and pitch-preserving mode.
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            SpeedMode::Resample => SpeedMode::Stretch,
+            SpeedMode::Stretch => SpeedMode::Resample,
+        };
+        self.reset();
+    }
+
+    /// Convert a duration of the file content into a playback duration at the current speed.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - A duration of audio content (in any unit, usually seconds), computed in 32
+    ///   bits: saturated above 2^24 units (194 days in seconds).
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The time it takes to play this content at the current speed (same unit).
+    #[inline(always)]
+    pub fn playback_duration(&self, content: u32) -> u32 {
+        content.saturating_mul(256) / self.factor()
+    }
+
+    /// Drop the pending samples, e.g. after a seek.
+    ///
+    /// The next processed samples start from the last produced sample, to avoid clicks.
+    pub fn reset(&mut self) {
+        self.prev = self.last_out;
+        self.phase = 0;
+        self.fifo_len = 0;
+        self.skip = 0;
+        self.tail = [self.last_out; OVERLAP];
+    }
+
+    /// Process a block of input samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Samples read from the file.
+    /// * `sink` - Called with each block of samples ready to be played.
+    pub fn process(&mut self, input: &[u8], mut sink: impl FnMut(&[u8])) {
+        if self.speed == NORMAL_SPEED && self.mode == SpeedMode::Resample && self.phase == 0 {
+            // Fast path: nothing to do
+            if let Some(&last) = input.last() {
+                self.prev = last;
+                self.last_out = last;
+            }
+            sink(input);
+            return;
+        }
+        match self.mode {
+            SpeedMode::Resample => self.resample(input, &mut sink),
+            SpeedMode::Stretch => self.stretch(input, &mut sink),
+        }
+        self.flush(&mut sink);
+    }
+
+    /// Queue one output sample, handing the output buffer to the sink when full.
+    #[inline(always)]
+    fn push(&mut self, sample: u8, sink: &mut impl FnMut(&[u8])) {
+        self.out[self.out_len] = sample;
+        self.out_len += 1;
+        if self.out_len == OUTPUT_LEN {
+            self.flush(sink);
+        }
+    }
+
+    /// Hand the pending output samples to the sink.
+    fn flush(&mut self, sink: &mut impl FnMut(&[u8])) {
+        if self.out_len > 0 {
+            self.last_out = self.out[self.out_len - 1];
+            sink(&self.out[..self.out_len]);
+            self.out_len = 0;
+        }
+    }
+
+    /// Linear resampling.
+    ///
+    /// For each input sample, output samples are interpolated between the previous and the
+    /// current input sample while the phase stays below 1.
+    fn resample(&mut self, input: &[u8], sink: &mut impl FnMut(&[u8])) {
+        let step = self.factor();
+        for &sample in input {
+            while self.phase < 256 {
+                let a = self.prev as i32;
+                let b = sample as i32;
+                let out = a + (((b - a) * self.phase as i32) >> 8);
+                self.push(out as u8, sink);
+                self.phase += step;
+            }
+            self.phase -= 256;
+            self.prev = sample;
+        }
+    }
+
+    /// Time-domain stretching (WSOLA).
+    ///
+    /// Segments of `2 * OVERLAP` samples are taken every `OVERLAP * speed` input samples,
+    /// shifted by up to `TOLERANCE` samples to best match the natural continuation of the
+    /// previous segment, and cross-faded with it. Each segment produces `OVERLAP` samples.
+    fn stretch(&mut self, mut input: &[u8], sink: &mut impl FnMut(&[u8])) {
+        while !input.is_empty() {
+            // Drop the input samples skipped by a large analysis hop
+            if self.skip > 0 {
+                let n = self.skip.min(input.len());
+                self.skip -= n;
+                input = &input[n..];
+                continue;
+            }
+
+            // Fill the analysis window
+            let n = (WINDOW - self.fifo_len).min(input.len());
+            self.fifo[self.fifo_len..self.fifo_len + n].copy_from_slice(&input[..n]);
+            self.fifo_len += n;
+            input = &input[n..];
+            if self.fifo_len < WINDOW {
+                break;
+            }
+
+            // Find the best aligned segment around the nominal position
+            let offset = self.best_offset();
+            let segment = &self.fifo[offset..offset + 2 * OVERLAP];
+
+            // Cross-fade the previous tail into the new segment
+            let mut faded = [0u8; OVERLAP];
+            for (i, out) in faded.iter_mut().enumerate() {
+                let a = self.tail[i] as i32;
+                let b = segment[i] as i32;
+                *out = (a + (((b - a) * i as i32) >> 7)) as u8;
+            }
+            self.tail.copy_from_slice(&segment[OVERLAP..]);
+            for sample in faded {
+                self.push(sample, sink);
+            }
+
+            // Advance by the analysis hop
+            let hop = ((OVERLAP as u32 * self.factor()) >> 8) as usize;
+            if hop < self.fifo_len {
+                self.fifo.copy_within(hop..self.fifo_len, 0);
+                self.fifo_len -= hop;
+            } else {
+                self.skip = hop - self.fifo_len;
+                self.fifo_len = 0;
+            }
+        }
+    }
+
+    /// Find the offset (in the fifo) of the segment that best continues the current tail.
+    ///
+    /// The candidates are compared with the sum of absolute differences on a subset of the
+    /// samples, which does not require any multiplication.
+    fn best_offset(&self) -> usize {
+        let mut best = TOLERANCE;
+        let mut best_score = u32::MAX;
+        for offset in (0..=2 * TOLERANCE).step_by(SEARCH_STEP) {
+            let mut score = 0u32;
+            for i in (0..OVERLAP).step_by(COMPARE_STEP) {
+                score += self.fifo[offset + i].abs_diff(self.tail[i]) as u32;
+                if score >= best_score {
+                    break;
+                }
+            }
+            if score < best_score {
+                best_score = score;
+                best = offset;
+            }
+        }
+        best
+    }
+}
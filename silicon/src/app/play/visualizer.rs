@@ -0,0 +1,454 @@
+//! Spectrum analyzer and oscilloscope views
+//!
+//! While a track plays, the top of the screen can show the cover art, a 16-band spectrum
+//! analyzer (with peak caps) or an oscilloscope of the samples being played.
+//!
+//! The playing loop only has ~10ms per iteration to read and stream 512 samples, and the audio
+//! FIFO only holds ~20ms of audio. The visualizer is therefore a small state machine which does
+//! a bounded amount of work per iteration ([`Visualizer::step`]): one stage of the FFT, or a few
+//! `fill_rectangle` calls. Only what changed since the last frame is redrawn.
+//!
+//! The spectrum is computed with a fixed-point radix-2 FFT over 128 samples, decimated by 4 from
+//! the 512 samples of a read (12kHz, ~94Hz per bin, up to 6kHz).
+
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::{RgbColor as _, WebColors as _},
+};
+use silicon_hal::display;
+
+use crate::peripheral::OledDisplay;
+
+/// Number of points of the FFT.
+const FFT_LEN: usize = 128;
+/// Number of stages of the FFT (log2 of [`FFT_LEN`]).
+const FFT_STAGES: usize = 7;
+/// Number of input samples averaged into one FFT point.
+const DECIMATION: usize = 4;
+/// Cosine of the FFT twiddle factors (`cos(2*pi*k/128)` for k in 0..64), in Q12 fixed-point.
+const TWIDDLE_COS: [i16; FFT_LEN / 2] = [
+    4096, 4091, 4076, 4052, 4017, 3973, 3920, 3857, 3784, 3703, 3612, 3513, 3406, 3290, 3166, 3035,
+    2896, 2751, 2598, 2440, 2276, 2106, 1931, 1751, 1567, 1380, 1189, 995, 799, 601, 401, 201, 0,
+    -201, -401, -601, -799, -995, -1189, -1380, -1567, -1751, -1931, -2106, -2276, -2440, -2598,
+    -2751, -2896, -3035, -3166, -3290, -3406, -3513, -3612, -3703, -3784, -3857, -3920, -3973,
+    -4017, -4052, -4076, -4091,
+];
+/// First half of the Hann window applied before the FFT (symmetric), in Q8 fixed-point.
+const HANN: [u8; FFT_LEN / 2] = [
+    0, 0, 1, 1, 2, 4, 6, 8, 10, 12, 15, 18, 22, 25, 29, 34, 38, 42, 47, 52, 57, 63, 68, 74, 80, 86,
+    92, 98, 104, 110, 116, 123, 129, 135, 142, 148, 154, 160, 166, 172, 178, 184, 189, 195, 200,
+    205, 210, 215, 219, 224, 228, 231, 235, 238, 241, 244, 246, 248, 250, 252, 253, 254, 255, 255,
+];
+
+/// Number of bands of the spectrum analyzer.
+const BANDS: usize = 16;
+/// FFT bins of each band (band `i` covers bins `BAND_EDGES[i]..BAND_EDGES[i + 1]`).
+///
+/// The bands are roughly logarithmic, from ~94Hz to 6kHz (the DC bin is ignored).
+const BAND_EDGES: [usize; BANDS + 1] =
+    [1, 2, 3, 4, 5, 6, 8, 10, 12, 15, 19, 24, 30, 37, 46, 56, 64];
+
+/// Bottom row of the visualizer area (the progress and status lines are below).
+const Y_BOTTOM: u8 = 103;
+/// Maximum height of a spectrum bar.
+const MAX_HEIGHT: u8 = 96;
+/// Width of a spectrum bar (plus a 1px gap).
+const BAR_WIDTH: u8 = 7;
+/// Height lost by a bar at each frame when the level drops.
+const BAR_FALL: u8 = 6;
+/// Number of frames a peak cap stays in place before falling.
+const PEAK_HOLD_FRAMES: u8 = 8;
+/// Height lost by a peak cap at each frame once it falls.
+const PEAK_FALL: u8 = 2;
+const BAR_COLOR: Rgb565 = Rgb565::CSS_DEEP_SKY_BLUE;
+const PEAK_COLOR: Rgb565 = Rgb565::WHITE;
+
+/// Number of samples (columns) shown by the oscilloscope.
+const SCOPE_LEN: usize = 128;
+/// Number of oscilloscope columns drawn per step.
+const SCOPE_COLUMNS_PER_STEP: usize = 32;
+/// Row of the oscilloscope zero line.
+const SCOPE_CENTER: i32 = 52;
+const SCOPE_COLOR: Rgb565 = Rgb565::CSS_LIME;
+/// Segment of an oscilloscope column with nothing drawn.
+const SCOPE_EMPTY: (u8, u8) = (1, 0);
+
+/// Number of rows cleared per step when the visualizer is shown.
+const CLEAR_ROWS_PER_STEP: u8 = 26;
+
+/// What is shown at the top of the Playing screen.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    /// The cover art of the title (drawn by the caller).
+    Cover,
+    /// The spectrum analyzer.
+    Spectrum,
+    /// The oscilloscope.
+    Scope,
+}
+
+impl View {
+    /// The view shown after this one.
+    pub fn next(self) -> Self {
+        match self {
+            View::Cover => View::Spectrum,
+            View::Spectrum => View::Scope,
+            View::Scope => View::Cover,
+        }
+    }
+}
+
+/// Step of the visualizer state machine.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// Clearing the visualizer area, from the given row.
+    Clear(u8),
+    /// Waiting for samples.
+    Capture,
+    /// Windowing and bit-reversal of the FFT input.
+    Window,
+    /// Running the given stage of the FFT.
+    Butterflies(usize),
+    /// Computing the band levels from the FFT output.
+    Bands,
+    /// Drawing the spectrum.
+    DrawSpectrum,
+    /// Drawing the oscilloscope, from the given column.
+    DrawScope(usize),
+}
+
+/// Spectrum analyzer and oscilloscope.
+pub struct Visualizer {
+    view: View,
+    stage: Stage,
+    /// FFT input/output (real part), also the oscilloscope samples.
+    re: [i16; FFT_LEN],
+    /// FFT input/output (imaginary part).
+    im: [i16; FFT_LEN],
+    /// Number of FFT points captured so far.
+    captured: usize,
+    /// Height of the spectrum bars.
+    bars: [u8; BANDS],
+    /// Height of the spectrum bars on screen.
+    drawn_bars: [u8; BANDS],
+    /// Height of the peak caps.
+    peaks: [u8; BANDS],
+    /// Height of the peak caps on screen (0 if not drawn).
+    drawn_peaks: [u8; BANDS],
+    /// Frames left before each peak cap falls.
+    peak_hold: [u8; BANDS],
+    /// Segment (top and bottom rows) of each oscilloscope column on screen.
+    drawn_scope: [(u8, u8); SCOPE_LEN],
+}
+
+impl Visualizer {
+    /// Create a new visualizer, showing the cover art.
+    pub fn new() -> Self {
+        Self {
+            view: View::Cover,
+            stage: Stage::Capture,
+            re: [0; FFT_LEN],
+            im: [0; FFT_LEN],
+            captured: 0,
+            bars: [0; BANDS],
+            drawn_bars: [0; BANDS],
+            peaks: [0; BANDS],
+            drawn_peaks: [0; BANDS],
+            peak_hold: [0; BANDS],
+            drawn_scope: [SCOPE_EMPTY; SCOPE_LEN],
+        }
+    }
+
+    /// The current view.
+    #[inline(always)]
+    pub fn view(&self) -> View {
+        self.view
+    }
+
+    /// Change the view.
+    ///
+    /// The visualizer area is cleared progressively by the next steps (unless the cover art is
+    /// shown, it is up to the caller to draw it).
+    pub fn set_view(&mut self, view: View) {
+        self.view = view;
+        self.captured = 0;
+        self.bars = [0; BANDS];
+        self.drawn_bars = [0; BANDS];
+        self.peaks = [0; BANDS];
+        self.drawn_peaks = [0; BANDS];
+        self.drawn_scope = [SCOPE_EMPTY; SCOPE_LEN];
+        self.stage = match view {
+            View::Cover => Stage::Capture,
+            View::Spectrum | View::Scope => Stage::Clear(0),
+        };
+    }
+
+    /// Feed the visualizer with samples read from the track.
+    ///
+    /// Samples are only captured when the visualizer is ready for a new frame, otherwise this
+    /// does nothing.
+    pub fn feed(&mut self, samples: &[u8]) {
+        if self.stage != Stage::Capture {
+            return;
+        }
+        match self.view {
+            View::Cover => {}
+            View::Spectrum => {
+                for chunk in samples.chunks_exact(DECIMATION) {
+                    if self.captured == FFT_LEN {
+                        break;
+                    }
+                    let sum: i16 = chunk.iter().map(|&s| s as i16).sum();
+                    self.re[self.captured] = sum - 128 * DECIMATION as i16;
+                    self.im[self.captured] = 0;
+                    self.captured += 1;
+                }
+                if self.captured == FFT_LEN {
+                    self.captured = 0;
+                    self.stage = Stage::Window;
+                }
+            }
+            View::Scope => {
+                if samples.len() <= SCOPE_LEN {
+                    return;
+                }
+                // Start on a rising edge (if any), so that periodic waveforms stand still
+                let trigger = samples[..samples.len() - SCOPE_LEN]
+                    .windows(2)
+                    .position(|w| w[0] < 128 && w[1] >= 128)
+                    .unwrap_or(0);
+                for (dst, &src) in self.re.iter_mut().zip(&samples[trigger..]) {
+                    *dst = src as i16;
+                }
+                self.stage = Stage::DrawScope(0);
+            }
+        }
+    }
+
+    /// Run the next step of the visualizer (computation or drawing).
+    ///
+    /// This must be called once per iteration of the playing loop, each step is short enough to
+    /// keep the audio FIFO fed.
+    pub fn step(&mut self, display: &mut OledDisplay<display::Initialized>) {
+        self.stage = match self.stage {
+            Stage::Clear(row) => {
+                let last = row.saturating_add(CLEAR_ROWS_PER_STEP - 1).min(Y_BOTTOM);
+                let _ = display.fill_rectangle(0, row, 127, last, Rgb565::BLACK);
+                if last == Y_BOTTOM {
+                    Stage::Capture
+                } else {
+                    Stage::Clear(last + 1)
+                }
+            }
+            Stage::Capture => Stage::Capture,
+            Stage::Window => {
+                self.window();
+                Stage::Butterflies(0)
+            }
+            Stage::Butterflies(stage) => {
+                self.butterflies(stage);
+                if stage + 1 == FFT_STAGES {
+                    Stage::Bands
+                } else {
+                    Stage::Butterflies(stage + 1)
+                }
+            }
+            Stage::Bands => {
+                self.bands();
+                Stage::DrawSpectrum
+            }
+            Stage::DrawSpectrum => {
+                self.draw_spectrum(display);
+                Stage::Capture
+            }
+            Stage::DrawScope(column) => {
+                let end = column + SCOPE_COLUMNS_PER_STEP;
+                self.draw_scope(display, column..end);
+                if end == SCOPE_LEN {
+                    Stage::Capture
+                } else {
+                    Stage::DrawScope(end)
+                }
+            }
+        };
+    }
+
+    /// Apply the Hann window and reorder the FFT input (bit-reversal permutation).
+    fn window(&mut self) {
+        for (i, x) in self.re.iter_mut().enumerate() {
+            let w = HANN[i.min(FFT_LEN - 1 - i)];
+            *x = (mul_small(*x as i32, w as u32) >> 8) as i16;
+        }
+        for i in 0..FFT_LEN {
+            let j = ((i as u8).reverse_bits() >> (8 - FFT_STAGES)) as usize;
+            if j > i {
+                self.re.swap(i, j);
+            }
+        }
+    }
+
+    /// Run one stage of the radix-2 decimation-in-time FFT.
+    ///
+    /// Each butterfly scales its outputs by 1/2, so the values stay within 16 bits.
+    fn butterflies(&mut self, stage: usize) {
+        let half = 1 << stage;
+        let twiddle_shift = FFT_STAGES - 1 - stage;
+        for start in (0..FFT_LEN).step_by(2 * half) {
+            for j in 0..half {
+                let (a, b) = (start + j, start + j + half);
+                let (ar, ai) = (self.re[a] as i32, self.im[a] as i32);
+                let (br, bi) = (self.re[b] as i32, self.im[b] as i32);
+                // t = b * e^(-2*pi*i*k/N)
+                let (tr, ti) = if j == 0 {
+                    (br, bi)
+                } else {
+                    let k = j << twiddle_shift;
+                    let cos = TWIDDLE_COS[k];
+                    let sin = TWIDDLE_COS[k.abs_diff(FFT_LEN / 4)];
+                    (
+                        mul_q12(br, cos) + mul_q12(bi, sin),
+                        mul_q12(bi, cos) - mul_q12(br, sin),
+                    )
+                };
+                self.re[a] = ((ar + tr) >> 1) as i16;
+                self.im[a] = ((ai + ti) >> 1) as i16;
+                self.re[b] = ((ar - tr) >> 1) as i16;
+                self.im[b] = ((ai - ti) >> 1) as i16;
+            }
+        }
+    }
+
+    /// Compute the height of the bars and peak caps from the FFT output.
+    fn bands(&mut self) {
+        for band in 0..BANDS {
+            let magnitude = (BAND_EDGES[band]..BAND_EDGES[band + 1])
+                .map(|bin| {
+                    // Alpha max plus beta min approximation of the magnitude (within ~7%)
+                    let (re, im) = (self.re[bin].unsigned_abs(), self.im[bin].unsigned_abs());
+                    let (max, min) = (re.max(im) as u32, re.min(im) as u32);
+                    max + (min >> 2) + (min >> 3)
+                })
+                .max()
+                .unwrap_or(0);
+            // Logarithmic scale: 2px per 0.75dB above the noise floor
+            let height = (log2_eighths(magnitude).saturating_sub(8) * 2).min(MAX_HEIGHT as u32);
+            let bar = (height as u8).max(self.bars[band].saturating_sub(BAR_FALL));
+            self.bars[band] = bar;
+
+            if bar >= self.peaks[band] {
+                self.peaks[band] = bar;
+                self.peak_hold[band] = PEAK_HOLD_FRAMES;
+            } else if self.peak_hold[band] > 0 {
+                self.peak_hold[band] -= 1;
+            } else {
+                self.peaks[band] = self.peaks[band].saturating_sub(PEAK_FALL).max(bar);
+            }
+        }
+    }
+
+    /// Draw the spectrum bars and peak caps (only what changed).
+    fn draw_spectrum(&mut self, display: &mut OledDisplay<display::Initialized>) {
+        for band in 0..BANDS {
+            let x0 = band as u8 * (BAR_WIDTH + 1);
+            let x1 = x0 + BAR_WIDTH - 1;
+            let (old, new) = (self.drawn_bars[band], self.bars[band]);
+            let (old_peak, peak) = (self.drawn_peaks[band], self.peaks[band]);
+
+            // Erase the old peak cap (it is always above the bar, with a 1px gap)
+            if old_peak != peak && old_peak != 0 {
+                let y = Y_BOTTOM - old_peak - 2;
+                let _ = display.fill_rectangle(x0, y, x1, y + 1, Rgb565::BLACK);
+            }
+            // Grow or shrink the bar
+            if new > old {
+                let _ =
+                    display.fill_rectangle(x0, Y_BOTTOM + 1 - new, x1, Y_BOTTOM - old, BAR_COLOR);
+            } else if new < old {
+                let _ = display.fill_rectangle(
+                    x0,
+                    Y_BOTTOM + 1 - old,
+                    x1,
+                    Y_BOTTOM - new,
+                    Rgb565::BLACK,
+                );
+            }
+            // Draw the new peak cap
+            if old_peak != peak && peak != 0 {
+                let y = Y_BOTTOM - peak - 2;
+                let _ = display.fill_rectangle(x0, y, x1, y + 1, PEAK_COLOR);
+            }
+
+            self.drawn_bars[band] = new;
+            self.drawn_peaks[band] = peak;
+        }
+    }
+
+    /// Draw some columns of the oscilloscope (only the columns that changed).
+    ///
+    /// Each column is a vertical segment joining the previous sample to the current one.
+    fn draw_scope(
+        &mut self,
+        display: &mut OledDisplay<display::Initialized>,
+        columns: core::ops::Range<usize>,
+    ) {
+        let y_of = |sample: i16| (SCOPE_CENTER - (((sample as i32 - 128) * 3) >> 3)) as u8;
+        for column in columns {
+            let y = y_of(self.re[column]);
+            let previous = if column > 0 {
+                y_of(self.re[column - 1])
+            } else {
+                y
+            };
+            let segment = (y.min(previous), y.max(previous));
+            let drawn = self.drawn_scope[column];
+            if segment == drawn {
+                continue;
+            }
+            let x = column as u8;
+            if drawn != SCOPE_EMPTY {
+                let _ = display.fill_rectangle(x, drawn.0, x, drawn.1, Rgb565::BLACK);
+            }
+            let _ = display.fill_rectangle(x, segment.0, x, segment.1, SCOPE_COLOR);
+            self.drawn_scope[column] = segment;
+        }
+    }
+}
+
+/// Multiply by a small positive factor, with shifts and adds.
+///
+/// The generic software multiplication loops over all the bits of its operands (32 iterations
+/// for a negative one), looping over the bits of a small factor is much cheaper.
+#[inline(always)]
+fn mul_small(mut x: i32, mut factor: u32) -> i32 {
+    let mut acc = 0;
+    while factor != 0 {
+        if factor & 1 != 0 {
+            acc += x;
+        }
+        x <<= 1;
+        factor >>= 1;
+    }
+    acc
+}
+
+/// Multiply by a Q12 fixed-point factor (between -1 and 1).
+#[inline(always)]
+fn mul_q12(x: i32, factor: i16) -> i32 {
+    let product = mul_small(x, factor.unsigned_abs() as u32) >> 12;
+    if factor < 0 { -product } else { product }
+}
+
+/// Approximate `8 * log2(x)` (0 for x <= 1).
+fn log2_eighths(x: u32) -> u32 {
+    if x <= 1 {
+        return 0;
+    }
+    let bits = 31 - x.leading_zeros();
+    // The 3 bits after the leading one give a linear approximation of the fractional part
+    let fraction = if bits >= 3 {
+        (x >> (bits - 3)) & 7
+    } else {
+        (x << (3 - bits)) & 7
+    };
+    bits * 8 + fraction
+}
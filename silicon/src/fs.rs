@@ -1,5 +1,6 @@
-use crate::peripheral::SdCard;
+use crate::peripheral::{SD_DATA_CONFIG, SdCard, SdCardSpi};
 use embedded_sdmmc::{TimeSource, Timestamp};
+use silicon_hal::spi::{self, Config};
 
 /// A TimeSource implementation that always returns a zero timestamp.
 pub struct ZeroTimeSource;
@@ -17,4 +18,37 @@ impl TimeSource for ZeroTimeSource {
 }
 
 pub type VolumeManager = embedded_sdmmc::VolumeManager<SdCard, ZeroTimeSource>;
+
+/// Initialize the SD card if not done yet (at the slow clock of its SPI bus), then switch the
+/// bus to full speed ([`SD_DATA_CONFIG`]).
+///
+/// The card is initialized lazily otherwise, and would stay at the slow clock: call this before
+/// the first access to the card, and again if it was not ready yet.
+pub fn init_sdcard(sdcard: &SdCard) {
+    if let Err(err) = sdcard.num_bytes() {
+        // No card (yet): keep the slow clock, for the next try
+        warn!("sdcard: init failed ({:?})", err);
+        return;
+    }
+    if let Err(err) = sdcard.spi(|dev| reconfigure_sd_spi(dev.bus_mut(), SD_DATA_CONFIG)) {
+        warn!("sdcard: spi not ready ({:?})", err);
+    }
+}
+
+/// Change the configuration of the SPI bus of the SD card.
+///
+/// If the ongoing transfers time out, the master is stuck: it is reset, then configured.
+///
+/// # Returns
+///
+/// * `Result<(), spi::Error>` - An error if the master is still not usable after its reset.
+pub fn reconfigure_sd_spi(spi: &mut SdCardSpi, config: Config) -> Result<(), spi::Error> {
+    match spi.reconfigure(config) {
+        Err(spi::Error::Timeout) => {
+            spi.initialize()?;
+            spi.reconfigure(config)
+        }
+        result => result,
+    }
+}
 pub use embedded_sdmmc::{RawDirectory, RawVolume};
@@ -0,0 +1,62 @@
+//! Logging macros of the application.
+//!
+//! With the `log-serial` or `log-console` feature, [`error!`], [`warn!`] and [`info!`] forward
+//! the records to the logger through [`log`], a single function (the call sites only build the
+//! arguments). Without them (the default), the records are type-checked
+//! but compiled out, with their formatting code: nothing of the logger is linked in the image.
+
+#[cfg(any(feature = "log-serial", feature = "log-console"))]
+macro_rules! error {
+    ($($arg:tt)+) => {
+        $crate::logging::log(::log::Level::Error, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[cfg(any(feature = "log-serial", feature = "log-console"))]
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        $crate::logging::log(::log::Level::Warn, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[cfg(any(feature = "log-serial", feature = "log-console"))]
+macro_rules! info {
+    ($($arg:tt)+) => {
+        $crate::logging::log(::log::Level::Info, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[cfg(not(any(feature = "log-serial", feature = "log-console")))]
+macro_rules! error {
+    ($($arg:tt)+) => { if false { let _ = format_args!($($arg)+); } };
+}
+
+#[cfg(not(any(feature = "log-serial", feature = "log-console")))]
+macro_rules! warn {
+    ($($arg:tt)+) => { if false { let _ = format_args!($($arg)+); } };
+}
+
+#[cfg(not(any(feature = "log-serial", feature = "log-console")))]
+macro_rules! info {
+    ($($arg:tt)+) => { if false { let _ = format_args!($($arg)+); } };
+}
+
+/// Forward a record to the logger, if its level is enabled.
+///
+/// # Arguments
+///
+/// * `level` - The level of the record.
+/// * `target` - The module path of the call site.
+/// * `args` - The message.
+#[cfg(any(feature = "log-serial", feature = "log-console"))]
+#[inline(never)]
+pub fn log(level: ::log::Level, target: &'static str, args: core::fmt::Arguments) {
+    if level <= ::log::STATIC_MAX_LEVEL && level <= ::log::max_level() {
+        let record = ::log::Record::builder()
+            .args(args)
+            .level(level)
+            .target(target)
+            .build();
+        ::log::logger().log(&record);
+    }
+}
@@ -1,8 +1,8 @@
 #![no_std]
 #![no_main]
 
-use embedded_hal::digital::OutputPin as _;
-use silicon_hal::gpio::{AudioViz, IntoPin as _};
+#[macro_use]
+mod logging;
 
 mod app;
 mod display;
@@ -12,8 +12,26 @@ mod utils;
 
 pub use utils::{VoidUnwrap, delay_ms};
 
+/// Panic handler of the `panic-screen` feature: a crash screen with the location and the message
+/// of the panic, and the error ID blinked on the LEDs.
+#[cfg(feature = "panic-screen")]
 #[panic_handler]
-fn __panic(_info: &core::panic::PanicInfo) -> ! {
+fn __panic(info: &core::panic::PanicInfo) -> ! {
+    silicon_hal::interrupt::disable();
+    let panic = silicon_hal::fault::Panic::from_info(info);
+    silicon_hal::crash::record_panic(&panic);
+    // Safety: The interrupts are disabled and the application is stopped
+    unsafe { panic.report() }
+}
+
+#[cfg(not(feature = "panic-screen"))]
+#[panic_handler]
+fn __panic(info: &core::panic::PanicInfo) -> ! {
+    use embedded_hal::digital::OutputPin as _;
+    use silicon_hal::gpio::{AudioViz, IntoPin as _};
+
+    // Keep a trace of the panic for the next boot
+    silicon_hal::crash::record_panic(&silicon_hal::fault::Panic::from_info(info));
     // In case of panic, just loop indefinitely
     // Also try to get a led to light up or something -- unsafe but useful for debugging
     let mut gpio = unsafe { silicon_hal::gpio::Gpio::steal() };
@@ -22,7 +40,9 @@ fn __panic(_info: &core::panic::PanicInfo) -> ! {
     } // Ensure audio viz is disabled to get the LedBank back
     let mut led0 = gpio.take_led0().unwrap().into_pin();
     let mut led7 = gpio.take_led7().unwrap().into_pin();
+    // Keep blinking until the next power-up, not until the watchdog resets the board
     loop {
+        silicon_hal::watchdog::feed();
         led0.set_low();
         led7.set_high();
         delay_ms(1000);
@@ -36,7 +56,10 @@ fn __panic(_info: &core::panic::PanicInfo) -> ! {
 #[silicon_hal::entry]
 fn main() -> ! {
     let peripheral = silicon_hal::init();
+    #[cfg(any(feature = "log-serial", feature = "log-console"))]
+    silicon_hal::logger::init(silicon_hal::logger::LevelFilter::Debug);
     let mut app_state = app::AppState::new(peripheral);
+    silicon_hal::watchdog::start(app::WATCHDOG_TIMEOUT);
 
     loop {
         if let Some(new_state) = app_state.run() {
@@ -46,5 +69,14 @@ fn main() -> ! {
         }
     }
 
-    loop {}
+    error!("player stopped");
+    #[cfg(feature = "log-console")]
+    {
+        // Safety: The application is stopped, the OLED display is not used anymore
+        unsafe { silicon_hal::logger::console::show() };
+    }
+    // Stopped on purpose, not stuck: keep the screen until the next power-up
+    loop {
+        silicon_hal::watchdog::feed();
+    }
 }
@@ -3,6 +3,7 @@ pub use btns::*;
 pub use leds::*;
 pub use oled::*;
 pub use sdcard::*;
+pub use timer::*;
 
 #[allow(dead_code)]
 mod audio_streamer {
@@ -29,32 +30,30 @@ mod oled {
     //! These types are used to simplify the interface for working with the OLED display.
     use core::convert::Infallible;
 
-    use embedded_hal_bus::spi::ExclusiveDevice;
     use silicon_hal::{
         delay::IntrDelay,
         display::DisplayPeripheral,
         gpio::{
             Pin,
-            never_bank::NeverPin,
             spi_oled_bank::{SpiOledCs, SpiOledDc, SpiOledRes},
         },
-        spi::{Spi, Spi1},
+        spi::{Spi, Spi1, SpiBusDevice},
     };
 
     /// Delayer used for the OLED display.
     pub type OledDelay = IntrDelay;
     /// SPI interface used for the OLED display.
     pub type OledSpi = Spi<Spi1, OledDelay>;
-    /// Pin CS type for the OLED SPI display (never).
-    pub type OledNeverCsPin = Pin<NeverPin>;
     /// Pin CS type for the OLED SPI display.
     pub type OledCsPin = Pin<SpiOledCs>;
     /// Pin DC type for the OLED SPI display.
     pub type OledDcPin = Pin<SpiOledDc>;
     /// Pin RESET type for the OLED SPI display.
     pub type OledResetPin = Pin<SpiOledRes>;
-    /// SPI device type for the OLED display.
-    pub type OledSpiDevice = ExclusiveDevice<OledSpi, OledNeverCsPin, OledDelay>;
+    /// SPI device type for the OLED display (the chip select is driven by the display driver).
+    ///
+    /// The fault and panic screens of the HAL re-create the display with this same type.
+    pub type OledSpiDevice = SpiBusDevice<Spi1, OledDelay>;
     /// OLED display peripheral type.
     pub type OledDisplay<STATE> = DisplayPeripheral<
         OledSpiDevice,
@@ -77,8 +76,16 @@ mod sdcard {
     use silicon_hal::{
         delay::IntrDelay,
         gpio::{Pin, spi_sdcard_bank::SpiSdCs},
-        spi::{Spi, Spi0},
+        spi::{Config, Spi, Spi0},
+    };
+
+    /// SPI configuration of the SDCard during its initialization (at most 400kHz).
+    pub const SD_INIT_CONFIG: Config = Config {
+        frequency: 400_000,
+        ..Config::DEFAULT
     };
+    /// SPI configuration of the SDCard once initialized (full speed, 40MHz).
+    pub const SD_DATA_CONFIG: Config = Config::DEFAULT;
 
     /// Delayer used for the SDCard.
     pub type SdCardDelay = IntrDelay;
@@ -100,6 +107,14 @@ mod leds {
     pub use silicon_hal::gpio::LedBank;
 }
 
+#[allow(dead_code)]
+mod timer {
+    //! Type aliases for Timer peripherals
+    //!
+    //! These types are used to simplify the interface for working with the Timer.
+    pub use silicon_hal::timer::Timer0;
+}
+
 #[allow(dead_code)]
 mod btns {
     //! Type aliases for Button peripherals